@@ -0,0 +1,104 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use termplt::plotting::{
+    axes::{Axes, AxesPositioning},
+    canvas::{BufferType, TerminalCanvas},
+    colors,
+    graph::Graph,
+    line::LineStyle,
+    marker::MarkerStyle,
+    point::Point,
+    series::Series,
+    text::TextStyle,
+};
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 600;
+
+// a noisy sine wave, like a long metrics export
+fn wave(num_points: usize) -> Vec<Point<f64>> {
+    (0..num_points)
+        .map(|i| {
+            let x = i as f64 / num_points as f64 * 20.;
+            let noise = ((i * 7919) % 101) as f64 / 100. - 0.5;
+            Point::new(x, x.sin() * 10. + noise)
+        })
+        .collect()
+}
+
+fn axes() -> Axes {
+    Axes::new(
+        AxesPositioning::XY(LineStyle::default_with_thickness(1)),
+        TextStyle::with_color(colors::WHITE),
+    )
+}
+
+fn draw(graph: Graph<f64>) -> Vec<u8> {
+    TerminalCanvas::new(WIDTH, HEIGHT, colors::BLACK)
+        .with_buffer(BufferType::Uniform(40))
+        .with_graph(graph)
+        .draw()
+        .unwrap()
+        .get_bytes()
+}
+
+fn scatter(c: &mut Criterion) {
+    let points = wave(1_000_000);
+    let graph = Graph::new()
+        .with_series(
+            Series::new(&points).with_marker_style(MarkerStyle::FilledCircle {
+                size: 2,
+                color: colors::LIME,
+            }),
+        )
+        .with_axes(axes());
+    c.bench_function("scatter 1M points", |b| {
+        b.iter(|| draw(black_box(graph.clone())))
+    });
+}
+
+fn line(c: &mut Criterion) {
+    let points = wave(1_000_000);
+    let graph = Graph::new()
+        .with_series(
+            Series::new(&points)
+                .with_marker_style(MarkerStyle::Pixel {
+                    color: colors::LIME,
+                })
+                .with_line_style(LineStyle::Solid {
+                    color: colors::LIME,
+                    thickness: 1,
+                }),
+        )
+        .with_axes(axes());
+    c.bench_function("line 1M points", |b| {
+        b.iter(|| draw(black_box(graph.clone())))
+    });
+}
+
+fn thick_lines(c: &mut Criterion) {
+    let points = wave(1_000);
+    let style = LineStyle::default_with_thickness(8);
+    let graph = (0..200).fold(
+        Graph::new()
+            .with_series(Series::new(&points))
+            .with_axes(axes()),
+        |graph, i| {
+            let offset = i as f64 / 10.;
+            graph
+                .with_hline(offset - 10., style)
+                .with_vline(offset, style)
+        },
+    );
+    c.bench_function("thick reference lines", |b| {
+        b.iter(|| draw(black_box(graph.clone())))
+    });
+}
+
+criterion_group! {
+    name = benches;
+    // a single draw of a million points takes long enough that a few samples are representative
+    config = Criterion::default().sample_size(10);
+    targets = scatter, line, thick_lines
+}
+criterion_main!(benches);
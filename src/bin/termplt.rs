@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fs;
+use std::io::{self, IsTerminal};
 use std::path::Path;
 
 use rgb::RGB8;
@@ -7,35 +8,37 @@ use termplt::{
     get_window_size,
     kitty_graphics::ctrl_seq::{PixelFormat, Transmission},
     plotting::{
-        axes::{Axes, AxesPositioning},
+        axes::{Axes, AxesPositioning, SecondaryYAxis},
+        candlestick::{Candlestick, CandlestickKind, Ohlc},
         canvas::{BufferType, TerminalCanvas},
+        categories::Categories,
+        colorbar::Colorbar,
+        colormaps::Colormap,
         colors,
         graph::Graph,
         grid_lines::GridLines,
+        interpolation::LineInterpolation,
         line::LineStyle,
         marker::MarkerStyle,
         point::Point,
-        series::Series,
+        series::{Series, YAxis},
+        sparkline::Sparkline,
+        subplots::Subplots,
         text::TextStyle,
+        theme::Theme,
     },
-    terminal_commands::images::Image,
+    terminal_commands::{images::Image, osc_cmds},
 };
 
+#[cfg(feature = "ttf")]
+use termplt::plotting::font::Font;
+
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
 // ---------------------------------------------------------------------------
-// Default palette for automatic color/marker cycling
+// Default markers for automatic marker cycling; colors cycle through the theme's palette
 // ---------------------------------------------------------------------------
 
-struct PaletteEntry {
-    color: RGB8,
-    marker_fn: fn(u32, RGB8) -> MarkerStyle,
-}
-
-const fn palette_entry(color: RGB8, marker_fn: fn(u32, RGB8) -> MarkerStyle) -> PaletteEntry {
-    PaletteEntry { color, marker_fn }
-}
-
 fn filled_circle(size: u32, color: RGB8) -> MarkerStyle {
     MarkerStyle::FilledCircle { size, color }
 }
@@ -48,18 +51,48 @@ fn filled_square(size: u32, color: RGB8) -> MarkerStyle {
 fn hollow_square(size: u32, color: RGB8) -> MarkerStyle {
     MarkerStyle::HollowSquare { size, color }
 }
+fn filled_triangle_up(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::FilledTriangleUp { size, color }
+}
+fn hollow_triangle_down(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::HollowTriangleDown { size, color }
+}
+fn filled_diamond(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::FilledDiamond { size, color }
+}
+fn hollow_star(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::HollowStar { size, color }
+}
+fn cross(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::Cross { size, color }
+}
+fn plus(size: u32, color: RGB8) -> MarkerStyle {
+    MarkerStyle::Plus { size, color }
+}
 
-const DEFAULT_PALETTE: &[PaletteEntry] = &[
-    palette_entry(colors::BLUE, filled_circle),
-    palette_entry(colors::RED, hollow_circle),
-    palette_entry(colors::LIME, filled_square),
-    palette_entry(colors::ORANGE, hollow_square),
-    palette_entry(colors::CYAN, filled_circle),
-    palette_entry(colors::MAGENTA, hollow_circle),
+const DEFAULT_MARKERS: &[fn(u32, RGB8) -> MarkerStyle] = &[
+    filled_circle,
+    hollow_circle,
+    filled_square,
+    hollow_square,
+    filled_triangle_up,
+    hollow_triangle_down,
+    filled_diamond,
+    hollow_star,
+    cross,
+    plus,
 ];
 
 const DEFAULT_MARKER_SIZE: u32 = 2;
 const DEFAULT_LINE_THICKNESS: u32 = 0;
+const SIZE_VALUE_SCALE: u32 = 4;
+// ANSI palette indices of blue, red, green, yellow, cyan and magenta, which series are colored
+// from in turn when matching the terminal's colors
+const TERMINAL_PALETTE: [u8; 6] = [4, 1, 2, 3, 6, 5];
+// room left at the canvas edges beyond what the labels around each graph need
+const CANVAS_PADDING: u32 = 4;
+// the canvas is held in memory at the square of the factor
+const MAX_SUPERSAMPLING: u32 = 4;
 
 // ---------------------------------------------------------------------------
 // Series specification (parsed from CLI args)
@@ -71,6 +104,23 @@ enum DataSource {
     File(String),
 }
 
+/// How x values (the first data column) are interpreted.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum XType {
+    Numeric,
+    Category,
+}
+
+impl XType {
+    fn from_name(name: &str) -> Result<XType> {
+        match name.to_ascii_lowercase().as_str() {
+            "numeric" => Ok(XType::Numeric),
+            "category" => Ok(XType::Category),
+            _ => Err(format!("Unknown x type '{}'. Valid types: numeric, category", name).into()),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct SeriesSpec {
     data_source: DataSource,
@@ -80,6 +130,14 @@ struct SeriesSpec {
     line_style: Option<String>,
     line_color: Option<String>,
     line_thickness: Option<u32>,
+    interpolation: Option<String>,
+    colormap: Option<String>,
+    label: Option<String>,
+    subplot: Option<(usize, usize)>,
+    y_axis: Option<String>,
+    // reference lines without a color use the theme's foreground color
+    hlines: Vec<(f64, Option<RGB8>)>,
+    vlines: Vec<(f64, Option<RGB8>)>,
 }
 
 impl SeriesSpec {
@@ -92,6 +150,13 @@ impl SeriesSpec {
             line_style: None,
             line_color: None,
             line_thickness: None,
+            interpolation: None,
+            colormap: None,
+            label: None,
+            subplot: None,
+            y_axis: None,
+            hlines: Vec::new(),
+            vlines: Vec::new(),
         }
     }
 }
@@ -103,6 +168,10 @@ impl SeriesSpec {
 fn print_help(topic: Option<&str>) {
     match topic {
         Some("colors") => {
+            println!(
+                "Colors are given as a name, a hex code (#1e90ff or #f80), rgb(30, 144, 255),"
+            );
+            println!("hsl(210, 100%, 56%) or an index into the 256-color palette (e.g. 208).\n");
             println!("Available colors:\n");
             for (name, _) in colors::all_names() {
                 println!("  {name}");
@@ -114,8 +183,52 @@ fn print_help(topic: Option<&str>) {
             println!("  HollowCircle");
             println!("  FilledSquare");
             println!("  HollowSquare");
+            println!("  FilledTriangleUp, HollowTriangleUp");
+            println!("  FilledTriangleDown, HollowTriangleDown");
+            println!("  FilledTriangleLeft, HollowTriangleLeft");
+            println!("  FilledTriangleRight, HollowTriangleRight");
+            println!("  FilledDiamond, HollowDiamond");
+            println!("  FilledStar, HollowStar");
+            println!("  Cross          (diagonal, like an x)");
+            println!("  Plus");
+            println!("  Point          (small filled circle, half the size)");
+            println!("  Pixel          (single pixel, ignores --marker_size)");
             println!("  None           (line only, no markers)");
         }
+        Some("interpolations") => {
+            println!("Available line interpolations:\n");
+            println!("  Linear         (default)");
+            println!("  StepPre        step to the next value at the start of each interval");
+            println!("  StepPost       hold each value until the next point");
+            println!("  StepMid        step halfway between points");
+            println!("  CatmullRom     smooth curve through every point");
+            println!("  MonotoneCubic  smooth curve that does not overshoot the data");
+        }
+        Some("colormaps") => {
+            println!("Available colormaps:\n");
+            for (name, _) in Colormap::all_names() {
+                println!("  {name}");
+            }
+        }
+        Some("themes") => {
+            println!("Available themes:\n");
+            for name in Theme::all_names() {
+                println!("  {name}");
+            }
+            println!(
+                "  auto           (default) the terminal's own colors, or dark if it does not"
+            );
+            println!("                 report them");
+            println!("\nA theme file sets colors with 'key = value' lines:\n");
+            println!("  base = light             built-in theme to start from (default: dark)");
+            println!("  background = \"#fdf6e3\"  color name, #rrggbb, #rgb, rgb(r,g,b), hsl(h,s%,l%)");
+            println!("                          or 256-color index (see --help colors)");
+            println!("  foreground = orange      reference lines");
+            println!("  axes = black");
+            println!("  grid = gainsboro");
+            println!("  text = black");
+            println!("  palette = [navy, \"rgb(220,20,60)\", 37]  series colors, in turn");
+        }
         _ => {
             println!(
                 "\
@@ -126,26 +239,80 @@ Render 2D plots in a Kitty-compatible terminal.
 DATA (at least one required, repeat for multiple series):
   --data \"(x,y),(x,y),...\"   Inline data points
   --data_file <path>         Read x,y data from a file
+  --ohlc_file <path>         Draw a candlestick chart from t,open,high,low,close[,volume] rows;
+                             a volume column adds a volume panel below the chart, and any
+                             --data series are drawn over the candles
+  --ohlc_kind <kind>         candle (default) or bar (OHLC bars with open/close ticks)
+  --sparkline <cols>[x<rows>]
+                             Draw the single series as a sparkline: a bare line sized to the
+                             given number of terminal cells (1 row by default), printed inline
+                             without a trailing newline; --marker_style marks the last value
 
 STYLE (applies to the preceding --data or --data_file):
-  --marker_style <style>     FilledCircle, HollowCircle, FilledSquare, HollowSquare, None
-  --marker_color <color>     Named color (e.g. Blue, DARK_RED, lime)
+  --marker_style <style>     FilledCircle, HollowSquare, FilledTriangleUp, HollowStar, Cross,
+                             Pixel, None, ... (see --help markers)
+  --marker_color <color>     Color name (e.g. Blue, DARK_RED, lime), #rrggbb, #rgb, rgb(r,g,b),
+                             hsl(h,s%,l%) or 256-color index (see --help colors)
   --marker_size <pixels>     Marker radius in pixels (default: {DEFAULT_MARKER_SIZE})
   --line_style <style>       Solid (default) or None (scatter plot, no connecting lines)
-  --line_color <color>       Named color for connecting line
+  --line_color <color>       Color of the connecting line, in any --marker_color format
   --line_thickness <pixels>  Line thickness in pixels (default: {DEFAULT_LINE_THICKNESS})
+  --interpolation <mode>     Linear (default), StepPre, StepPost, StepMid, CatmullRom, MonotoneCubic
+  --colormap <name>          Color markers by the third column of an x,y,c data file using the
+                             named colormap (e.g. viridis); an optional fourth column (x,y,c,s)
+                             scales marker size from --marker_size up to {SIZE_VALUE_SCALE}x that size
+  --label <text>             Name shown for the series in the legend
+  --y_axis <axis>            primary (default) or secondary; secondary series are scaled to a
+                             separate y axis drawn on the right-hand side
+  --subplot <row,col>        Draw the series in the given panel of a grid of graphs; rows and
+                             columns start at 1 from the top left (default: 1,1)
+  --hline <y>[,color]        Draw a horizontal reference line (e.g. a threshold) across
+                             the series' graph; repeat for several lines (default color: the
+                             theme's foreground)
+  --vline <x>[,color]        Draw a vertical reference line (e.g. a deployment time)
+
+TEXT:
+  --x_title <text>           Title centered below the x axis
+  --y_title <text>           Title left of the y axis, reading from bottom to top
+  --font <path>              Draw labels with a TTF/OTF font (or 'default' for the bundled
+                             font), anti-aliased and sized to the terminal's rows; requires
+                             termplt to be built with the ttf feature
+
+COLORS:
+  --theme <name|path>        auto (default: the terminal's background, text and palette colors),
+                             dark, light, solarized, high-contrast, colorblind or a theme file
+                             (see --help themes)
+
+AXES:
+  --x_type <type>            numeric (default) or category; category x values are names (e.g.
+                             Mon, Tue) placed at evenly spaced positions in order of appearance
 
 OTHER:
+  --supersample <factor>     Draw plots at 2 to {MAX_SUPERSAMPLING} times the resolution and average them down,
+                             smoothing markers, diagonal lines and curves (default: 1, off)
   --verbose, -v              Print debug info (terminal size, canvas, buffer, etc.)
   --help, -h                 Show this help message
-  --help colors              List all available color names
+  --help colors              List all available color names and color formats
   --help markers             List all available marker styles
+  --help colormaps           List all available colormaps
+  --help themes              List all built-in themes and the theme file format
+  --help interpolations      List all available line interpolations
 
 Examples:
   termplt --data \"(1,1),(2,4),(3,9)\"
   termplt --data_file data.csv --marker_color Red --line_color Red
+  termplt --data_file data.csv --line_color \"#1e90ff\" --marker_color \"rgb(255,136,0)\"
   termplt --data_file a.txt --line_style None  (scatter plot, no lines)
-  termplt --data_file a.txt --data_file b.txt"
+  termplt --data_file a.txt --data_file b.txt
+  termplt --data_file counter.csv --interpolation StepPost
+  termplt --data_file xyc.csv --colormap viridis --line_style None
+  termplt --data \"(Mon,3),(Tue,5),(Wed,4)\" --x_type category
+  termplt --data_file rps.csv --data_file latency.csv --y_axis secondary
+  termplt --data_file cpu.csv --subplot 1,1 --data_file mem.csv --subplot 2,1
+  termplt --data_file latency.csv --hline 250,Red --vline 1700000000
+  termplt --ohlc_file prices.csv --data_file moving_avg.csv
+  termplt --data_file a.txt --theme light
+  printf 'cpu: '; termplt --sparkline 12 --data_file cpu.csv; echo ' 43%'"
             );
         }
     }
@@ -158,6 +325,15 @@ Examples:
 #[derive(Debug)]
 struct CliArgs {
     specs: Vec<SeriesSpec>,
+    ohlc_file: Option<String>,
+    ohlc_kind: CandlestickKind,
+    sparkline: Option<(u32, u32)>,
+    x_type: XType,
+    x_title: Option<String>,
+    y_title: Option<String>,
+    font: Option<String>,
+    theme: Option<String>,
+    supersampling: u32,
     verbose: bool,
 }
 
@@ -165,6 +341,15 @@ fn parse_args(args: Vec<String>) -> Result<CliArgs> {
     let mut specs: Vec<SeriesSpec> = Vec::new();
     let mut current: Option<SeriesSpec> = None;
     let mut verbose = false;
+    let mut x_type = XType::Numeric;
+    let mut ohlc_file = None;
+    let mut ohlc_kind = CandlestickKind::Candle;
+    let mut sparkline = None;
+    let mut font = None;
+    let mut theme = None;
+    let mut supersampling = 1;
+    let mut x_title = None;
+    let mut y_title = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -178,6 +363,57 @@ fn parse_args(args: Vec<String>) -> Result<CliArgs> {
                 print_help(topic);
                 std::process::exit(0);
             }
+            "--x_type" => {
+                i += 1;
+                let val = args.get(i).ok_or("--x_type requires a value")?;
+                x_type = XType::from_name(val)?;
+            }
+            "--x_title" => {
+                i += 1;
+                let val = args.get(i).ok_or("--x_title requires a value")?;
+                x_title = Some(parse_title("--x_title", val)?);
+            }
+            "--y_title" => {
+                i += 1;
+                let val = args.get(i).ok_or("--y_title requires a value")?;
+                y_title = Some(parse_title("--y_title", val)?);
+            }
+            "--font" => {
+                i += 1;
+                let val = args
+                    .get(i)
+                    .ok_or("--font requires a file path or 'default'")?;
+                font = Some(val.clone());
+            }
+            "--theme" => {
+                i += 1;
+                let val = args
+                    .get(i)
+                    .ok_or("--theme requires a theme name or file path")?;
+                theme = Some(val.clone());
+            }
+            "--supersample" => {
+                i += 1;
+                let val = args.get(i).ok_or("--supersample requires a factor")?;
+                supersampling = parse_supersampling(val)?;
+            }
+            "--ohlc_file" => {
+                i += 1;
+                let val = args.get(i).ok_or("--ohlc_file requires a file path")?;
+                ohlc_file = Some(val.clone());
+            }
+            "--ohlc_kind" => {
+                i += 1;
+                let val = args.get(i).ok_or("--ohlc_kind requires a value")?;
+                ohlc_kind = resolve_ohlc_kind(val)?;
+            }
+            "--sparkline" => {
+                i += 1;
+                let val = args
+                    .get(i)
+                    .ok_or("--sparkline requires a size, e.g. 12 or 12x2")?;
+                sparkline = Some(parse_sparkline_size(val)?);
+            }
             "--data" => {
                 if let Some(spec) = current.take() {
                     specs.push(spec);
@@ -250,6 +486,62 @@ fn parse_args(args: Vec<String>) -> Result<CliArgs> {
                     .ok_or("--line_color must appear after --data or --data_file")?;
                 spec.line_color = Some(val.clone());
             }
+            "--interpolation" => {
+                i += 1;
+                let val = args.get(i).ok_or("--interpolation requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--interpolation must appear after --data or --data_file")?;
+                spec.interpolation = Some(val.clone());
+            }
+            "--colormap" => {
+                i += 1;
+                let val = args.get(i).ok_or("--colormap requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--colormap must appear after --data or --data_file")?;
+                spec.colormap = Some(val.clone());
+            }
+            "--label" => {
+                i += 1;
+                let val = args.get(i).ok_or("--label requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--label must appear after --data or --data_file")?;
+                spec.label = Some(val.clone());
+            }
+            "--y_axis" => {
+                i += 1;
+                let val = args.get(i).ok_or("--y_axis requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--y_axis must appear after --data or --data_file")?;
+                spec.y_axis = Some(val.clone());
+            }
+            "--subplot" => {
+                i += 1;
+                let val = args.get(i).ok_or("--subplot requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--subplot must appear after --data or --data_file")?;
+                spec.subplot = Some(parse_subplot(val)?);
+            }
+            "--hline" => {
+                i += 1;
+                let val = args.get(i).ok_or("--hline requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--hline must appear after --data or --data_file")?;
+                spec.hlines.push(parse_reference_line("--hline", val)?);
+            }
+            "--vline" => {
+                i += 1;
+                let val = args.get(i).ok_or("--vline requires a value")?;
+                let spec = current
+                    .as_mut()
+                    .ok_or("--vline must appear after --data or --data_file")?;
+                spec.vlines.push(parse_reference_line("--vline", val)?);
+            }
             "--line_thickness" => {
                 i += 1;
                 let val = args
@@ -280,13 +572,144 @@ fn parse_args(args: Vec<String>) -> Result<CliArgs> {
         specs.push(spec);
     }
 
-    if specs.is_empty() {
-        return Err("No data provided. Use --data or --data_file to supply data points.\n\
+    if specs.is_empty() && ohlc_file.is_none() {
+        return Err(
+            "No data provided. Use --data or --data_file to supply data points.\n\
                      Run 'termplt --help' for usage."
             .into());
     }
 
-    Ok(CliArgs { specs, verbose })
+    if ohlc_file.is_some() {
+        if x_type == XType::Category {
+            return Err(
+                "--ohlc_file requires numeric times and cannot be used with \
+                        --x_type category"
+                    .into(),
+            );
+        }
+        if specs.iter().any(|spec| spec.subplot.is_some()) {
+            return Err("--subplot cannot be used with --ohlc_file".into());
+        }
+    }
+
+    if sparkline.is_some() {
+        if specs.len() != 1 || ohlc_file.is_some() {
+            return Err("--sparkline requires exactly one --data or --data_file series".into());
+        }
+        let spec = &specs[0];
+        if spec.subplot.is_some()
+            || spec.y_axis.is_some()
+            || spec.colormap.is_some()
+            || !spec.hlines.is_empty()
+            || !spec.vlines.is_empty()
+        {
+            return Err(
+                "--sparkline cannot be used with --subplot, --y_axis, --colormap, \
+                        --hline or --vline"
+                    .into(),
+            );
+        }
+    }
+
+    Ok(CliArgs {
+        specs,
+        ohlc_file,
+        ohlc_kind,
+        sparkline,
+        x_type,
+        x_title,
+        y_title,
+        font,
+        theme,
+        supersampling,
+        verbose,
+    })
+}
+
+/// Checks that an axis title is not blank. Its characters are checked once the label font is
+/// known.
+fn parse_title(flag: &str, s: &str) -> Result<String> {
+    if s.trim().is_empty() {
+        return Err(format!("{flag} requires a non-empty title").into());
+    }
+    Ok(s.to_string())
+}
+
+/// Checks that every character of a title or label can be drawn in the style of labels, e.g.
+/// "Category" for the kind of a category label.
+fn check_drawable(kind: &str, text: &str, style: &TextStyle) -> Result<()> {
+    match text.chars().find(|&c| !style.can_draw(c)) {
+        Some(c) => Err(format!(
+            "{} '{}' contains the character '{}', which cannot be drawn",
+            kind, text, c
+        )
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Parses a supersampling factor from 1 (off) to [`MAX_SUPERSAMPLING`].
+fn parse_supersampling(s: &str) -> Result<u32> {
+    match s.trim().parse::<u32>() {
+        Ok(factor) if (1..=MAX_SUPERSAMPLING).contains(&factor) => Ok(factor),
+        _ => Err(format!(
+            "Invalid supersampling factor '{s}'; expected a whole number from 1 to \
+             {MAX_SUPERSAMPLING}"
+        )
+        .into()),
+    }
+}
+
+/// Parses a "position[,color]" reference line, e.g. "250", "250,Red" or "250,#ff8800".
+fn parse_reference_line(flag: &str, s: &str) -> Result<(f64, Option<RGB8>)> {
+    let (position, color) = match s.split_once(',') {
+        Some((position, color)) => (position, Some(resolve_color(color.trim())?)),
+        None => (s, None),
+    };
+    let position = position
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| {
+            format!("{flag} value '{s}' must be a number, optionally followed by ',color'")
+        })?;
+    Ok((position, color))
+}
+
+/// Parses a sparkline size of "cols" or "colsxrows" terminal cells; the height defaults to one
+/// row so the sparkline fits in a line of text.
+fn parse_sparkline_size(s: &str) -> Result<(u32, u32)> {
+    let invalid = || {
+        format!(
+            "--sparkline value '{}' must be 'cols' or 'colsxrows' with positive integers",
+            s
+        )
+    };
+    let (cols, rows) = s.split_once('x').unwrap_or((s, "1"));
+    let cols = cols.trim().parse::<u32>().map_err(|_| invalid())?;
+    let rows = rows.trim().parse::<u32>().map_err(|_| invalid())?;
+    if cols == 0 || rows == 0 {
+        return Err(invalid().into());
+    }
+    Ok((cols, rows))
+}
+
+/// Parses a 1-based "row,col" subplot position into a 0-based (row, col) pair.
+fn parse_subplot(s: &str) -> Result<(usize, usize)> {
+    let invalid = || {
+        format!(
+            "--subplot value '{}' must be 'row,col' with rows and columns starting at 1",
+            s
+        )
+    };
+    let (row, col) = s.split_once(',').ok_or_else(invalid)?;
+    let row = row.trim().parse::<usize>().map_err(|_| invalid())?;
+    let col = col.trim().parse::<usize>().map_err(|_| invalid())?;
+    if row == 0 || col == 0 {
+        return Err(invalid().into());
+    }
+    Ok((row - 1, col - 1))
 }
 
 // ---------------------------------------------------------------------------
@@ -330,6 +753,31 @@ fn parse_inline_data(s: &str) -> Result<Vec<Point<f64>>> {
     Ok(points)
 }
 
+/// Parses inline "(name,y),(name,y),..." data where the x values are category names.
+fn parse_inline_category_data(s: &str) -> Result<Vec<(String, f64)>> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("Inline data string is empty".into());
+    }
+
+    let s = s.strip_prefix('(').unwrap_or(s);
+    let s = s.strip_suffix(')').unwrap_or(s);
+
+    s.split("),(")
+        .map(|pair| {
+            let pair = pair.trim();
+            let (name, y) = pair
+                .rsplit_once(',')
+                .ok_or_else(|| format!("Invalid point '({})'. Expected format: (name,y)", pair))?;
+            let y: f64 = y
+                .trim()
+                .parse()
+                .map_err(|_| format!("Cannot parse y value '{}' as a number", y.trim()))?;
+            Ok((name.trim().to_string(), y))
+        })
+        .collect()
+}
+
 fn is_header_line(line: &str) -> bool {
     // A line is a header if the first non-whitespace, non-comment token cannot be parsed as f64
     let trimmed = line.trim();
@@ -344,15 +792,53 @@ fn is_header_line(line: &str) -> bool {
     first_token.parse::<f64>().is_err()
 }
 
+fn is_category_header_line(line: &str) -> bool {
+    // With category x values only the y column can tell a header apart from data
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return false;
+    }
+    let y_token = trimmed
+        .split([',', '\t'])
+        .nth(1)
+        .or_else(|| trimmed.split_whitespace().nth(1))
+        .unwrap_or("");
+    y_token.trim().parse::<f64>().is_err()
+}
+
+/// Data read for a single series; value columns are only present when requested.
+#[derive(Debug)]
+struct SeriesData {
+    points: Vec<Point<f64>>,
+    // category names for each point; x values are assigned once all categories are known
+    x_labels: Option<Vec<String>>,
+    color_values: Option<Vec<f64>>,
+    size_values: Option<Vec<f64>>,
+}
+
 fn parse_data_file(path: &Path) -> Result<Vec<Point<f64>>> {
+    Ok(parse_data_file_columns(path, false, XType::Numeric)?.points)
+}
+
+/// Parses x,y rows from a file. When `with_values` is set, rows must have a third (color value)
+/// column and may have a fourth (size value) column; the fourth column must then be present on
+/// every row. With category x values, the first column is kept as a name rather than parsed.
+fn parse_data_file_columns(path: &Path, with_values: bool, x_type: XType) -> Result<SeriesData> {
     let content = fs::read_to_string(path)
         .map_err(|e| format!("Cannot read file '{}': {}", path.display(), e))?;
 
     let mut points = Vec::new();
+    let mut x_labels = Vec::new();
+    let mut color_values = Vec::new();
+    let mut size_values = Vec::new();
     let mut lines = content.lines().peekable();
 
     // Auto-detect and skip header
-    if lines.peek().is_some_and(|line| is_header_line(line)) {
+    let header_fn = match x_type {
+        XType::Numeric => is_header_line,
+        XType::Category => is_category_header_line,
+    };
+    if lines.peek().is_some_and(|line| header_fn(line)) {
         lines.next();
     }
 
@@ -386,14 +872,21 @@ fn parse_data_file(path: &Path) -> Result<Vec<Point<f64>>> {
             .into());
         }
 
-        let x: f64 = tokens[0].parse().map_err(|_| {
-            format!(
-                "{}:{}: cannot parse x value '{}' as a number",
-                path.display(),
-                line_num + 1,
-                tokens[0]
-            )
-        })?;
+        let x: f64 = match x_type {
+            XType::Numeric => tokens[0].parse().map_err(|_| {
+                format!(
+                    "{}:{}: cannot parse x value '{}' as a number (use --x_type category for \
+                     names)",
+                    path.display(),
+                    line_num + 1,
+                    tokens[0]
+                )
+            })?,
+            XType::Category => {
+                x_labels.push(tokens[0].to_string());
+                0.
+            }
+        };
         let y: f64 = tokens[1].parse().map_err(|_| {
             format!(
                 "{}:{}: cannot parse y value '{}' as a number",
@@ -404,45 +897,279 @@ fn parse_data_file(path: &Path) -> Result<Vec<Point<f64>>> {
         })?;
 
         points.push(Point::new(x, y));
+
+        if with_values {
+            let parse_value = |ind: usize, name: &str| -> Result<f64> {
+                let token = tokens.get(ind).ok_or_else(|| {
+                    format!(
+                        "{}:{}: expected a {} value in column {} (x,y,c[,s])",
+                        path.display(),
+                        line_num + 1,
+                        name,
+                        ind + 1
+                    )
+                })?;
+                Ok(token.parse().map_err(|_| {
+                    format!(
+                        "{}:{}: cannot parse {} value '{}' as a number",
+                        path.display(),
+                        line_num + 1,
+                        name,
+                        token
+                    )
+                })?)
+            };
+
+            color_values.push(parse_value(2, "color")?);
+            // the size column is optional, but must be used consistently
+            if tokens.len() > 3 || !size_values.is_empty() {
+                if size_values.len() != points.len() - 1 {
+                    return Err(format!(
+                        "{}:{}: size column must be present on every row or on none",
+                        path.display(),
+                        line_num + 1
+                    )
+                    .into());
+                }
+                size_values.push(parse_value(3, "size")?);
+            }
+        }
     }
 
     if points.is_empty() {
         return Err(format!("No data points found in '{}'", path.display()).into());
     }
 
-    Ok(points)
+    Ok(SeriesData {
+        points,
+        x_labels: (x_type == XType::Category).then_some(x_labels),
+        color_values: with_values.then_some(color_values),
+        size_values: (!size_values.is_empty()).then_some(size_values),
+    })
+}
+
+/// Parses time,open,high,low,close[,volume] rows from a file. The volume column is optional, but
+/// must then be present on every row.
+fn parse_ohlc_file(path: &Path) -> Result<Vec<Ohlc>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read file '{}': {}", path.display(), e))?;
+
+    let mut rows = Vec::new();
+    let mut lines = content.lines().peekable();
+    if lines.peek().is_some_and(|line| is_header_line(line)) {
+        lines.next();
+    }
+
+    for (line_num, line) in lines.enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let tokens: Vec<&str> = trimmed
+            .split([',', '\t'])
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect();
+        let tokens = if tokens.len() == 1 {
+            trimmed.split_whitespace().collect::<Vec<&str>>()
+        } else {
+            tokens
+        };
+
+        if !(5..=6).contains(&tokens.len()) {
+            return Err(format!(
+                "{}:{}: expected 5 or 6 values (t,o,h,l,c[,v]), got {}",
+                path.display(),
+                line_num + 1,
+                tokens.len()
+            )
+            .into());
+        }
+        let values = tokens
+            .iter()
+            .map(|token| {
+                token.parse::<f64>().map_err(|_| {
+                    format!(
+                        "{}:{}: cannot parse value '{}' as a number",
+                        path.display(),
+                        line_num + 1,
+                        token
+                    )
+                })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut row = Ohlc::new(values[0], values[1], values[2], values[3], values[4]);
+        if let Some(&volume) = values.get(5) {
+            row = row.with_volume(volume);
+        }
+        // the volume column is optional, but must be used consistently
+        if rows
+            .last()
+            .is_some_and(|prev: &Ohlc| prev.volume.is_some() != row.volume.is_some())
+        {
+            return Err(format!(
+                "{}:{}: volume column must be present on every row or on none",
+                path.display(),
+                line_num + 1
+            )
+            .into());
+        }
+        row.validate()
+            .map_err(|e| format!("{}:{}: {}", path.display(), line_num + 1, e))?;
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return Err(format!("No OHLC rows found in '{}'", path.display()).into());
+    }
+    Ok(rows)
 }
 
 // ---------------------------------------------------------------------------
 // Color and marker style resolution
 // ---------------------------------------------------------------------------
 
-fn resolve_color(name: &str) -> Result<RGB8> {
-    colors::from_name(name).ok_or_else(|| {
-        let preview: Vec<&str> = colors::all_names()
-            .iter()
-            .take(8)
-            .map(|(n, _)| *n)
-            .collect();
+fn resolve_color(spec: &str) -> Result<RGB8> {
+    colors::parse(spec)
+        .map_err(|e| format!("{e} (run 'termplt --help colors' for the color names)").into())
+}
+
+fn resolve_colormap(name: &str) -> Result<Colormap> {
+    Colormap::from_name(name).ok_or_else(|| {
         format!(
-            "Unknown color '{}'. Valid colors: {}, ... (run 'termplt --help colors' for full list)",
-            name,
-            preview.join(", ")
+            "Unknown colormap '{}'. Run 'termplt --help colormaps' for the full list",
+            name
         )
         .into()
     })
 }
 
+fn resolve_interpolation(name: &str) -> Result<LineInterpolation> {
+    LineInterpolation::from_name(name).ok_or_else(|| {
+        format!(
+            "Unknown interpolation '{}'. Valid modes: Linear, StepPre, StepPost, StepMid, \
+             CatmullRom, MonotoneCubic",
+            name
+        )
+        .into()
+    })
+}
+
+fn resolve_y_axis(name: &str) -> Result<YAxis> {
+    match name.to_ascii_lowercase().as_str() {
+        "primary" => Ok(YAxis::Primary),
+        "secondary" => Ok(YAxis::Secondary),
+        _ => Err(format!("Unknown y axis '{}'. Valid axes: primary, secondary", name).into()),
+    }
+}
+
+fn resolve_ohlc_kind(name: &str) -> Result<CandlestickKind> {
+    match name.to_ascii_lowercase().as_str() {
+        "candle" => Ok(CandlestickKind::Candle),
+        "bar" => Ok(CandlestickKind::Bar),
+        _ => Err(format!("Unknown OHLC kind '{}'. Valid kinds: candle, bar", name).into()),
+    }
+}
+
+/// Gets the style of labels, drawn with the given font or else the bitmap font. It still needs
+/// to be sized to match the terminal's text with [`TextStyle::with_cell_size`].
+#[cfg(feature = "ttf")]
+fn resolve_text_style(font: Option<&str>, color: RGB8) -> Result<TextStyle> {
+    let style = TextStyle::with_color(color);
+    match font {
+        None => Ok(style),
+        Some(name) if name.eq_ignore_ascii_case("default") => Ok(style.with_font(Font::bundled())),
+        Some(path) => Ok(style.with_font(Font::from_file(path)?)),
+    }
+}
+
+/// Gets the style of labels, drawn with the bitmap font. It still needs to be sized to match
+/// the terminal's text with [`TextStyle::with_cell_size`].
+#[cfg(not(feature = "ttf"))]
+fn resolve_text_style(font: Option<&str>, color: RGB8) -> Result<TextStyle> {
+    match font {
+        None => Ok(TextStyle::with_color(color)),
+        Some(_) => Err("--font requires termplt to be built with the ttf feature".into()),
+    }
+}
+
+/// Gets a built-in theme by name, a theme matching the terminal's colors for "auto", or a
+/// theme loaded from a file. Without a theme, plots match the terminal's colors.
+fn resolve_theme(name: Option<&str>) -> Result<Theme> {
+    let name = name.unwrap_or("auto");
+    if let Some(theme) = Theme::from_name(name) {
+        return Ok(theme);
+    }
+    if name.eq_ignore_ascii_case("auto") {
+        return Ok(terminal_theme());
+    }
+    if !Path::new(name).exists() {
+        return Err(format!(
+            "Unknown theme '{}'. Valid themes: {}, auto, or the path of a theme file",
+            name,
+            Theme::all_names().join(", ")
+        )
+        .into());
+    }
+    Theme::from_file(name)
+}
+
+/// Gets a theme matching the terminal's background, foreground and ANSI palette colors, so that
+/// plots blend with the user's color scheme. Terminals that do not report their colors are most
+/// often dark, so the dark theme is used for them.
+fn terminal_theme() -> Theme {
+    if !io::stdin().is_terminal() {
+        return Theme::dark();
+    }
+    let Ok(background) = osc_cmds::get_background_color() else {
+        return Theme::dark();
+    };
+    // stop querying at the first color the terminal does not report, since each unanswered
+    // query waits for the full response timeout
+    let theme = match osc_cmds::get_foreground_color() {
+        Ok(foreground) => Theme::for_terminal(background, foreground),
+        Err(_) => return Theme::for_background(background).with_background(background),
+    };
+    let palette = TERMINAL_PALETTE
+        .iter()
+        .map(|&index| osc_cmds::get_palette_color(index))
+        .collect::<Result<Vec<_>>>();
+    match palette {
+        Ok(palette) => theme.with_palette(&palette),
+        Err(_) => theme,
+    }
+}
+
 fn resolve_marker_style(name: &str, size: u32, color: RGB8) -> Result<Option<MarkerStyle>> {
     match name.to_ascii_lowercase().as_str() {
         "filledcircle" => Ok(Some(MarkerStyle::FilledCircle { size, color })),
         "hollowcircle" => Ok(Some(MarkerStyle::HollowCircle { size, color })),
         "filledsquare" => Ok(Some(MarkerStyle::FilledSquare { size, color })),
         "hollowsquare" => Ok(Some(MarkerStyle::HollowSquare { size, color })),
+        "filledtriangleup" => Ok(Some(MarkerStyle::FilledTriangleUp { size, color })),
+        "hollowtriangleup" => Ok(Some(MarkerStyle::HollowTriangleUp { size, color })),
+        "filledtriangledown" => Ok(Some(MarkerStyle::FilledTriangleDown { size, color })),
+        "hollowtriangledown" => Ok(Some(MarkerStyle::HollowTriangleDown { size, color })),
+        "filledtriangleleft" => Ok(Some(MarkerStyle::FilledTriangleLeft { size, color })),
+        "hollowtriangleleft" => Ok(Some(MarkerStyle::HollowTriangleLeft { size, color })),
+        "filledtriangleright" => Ok(Some(MarkerStyle::FilledTriangleRight { size, color })),
+        "hollowtriangleright" => Ok(Some(MarkerStyle::HollowTriangleRight { size, color })),
+        "filleddiamond" => Ok(Some(MarkerStyle::FilledDiamond { size, color })),
+        "hollowdiamond" => Ok(Some(MarkerStyle::HollowDiamond { size, color })),
+        "filledstar" => Ok(Some(MarkerStyle::FilledStar { size, color })),
+        "hollowstar" => Ok(Some(MarkerStyle::HollowStar { size, color })),
+        "cross" => Ok(Some(MarkerStyle::Cross { size, color })),
+        "plus" => Ok(Some(MarkerStyle::Plus { size, color })),
+        "point" => Ok(Some(MarkerStyle::Point { size, color })),
+        "pixel" => Ok(Some(MarkerStyle::Pixel { color })),
         "none" => Ok(None),
         _ => Err(format!(
             "Unknown marker style '{}'. Valid styles: FilledCircle, HollowCircle, \
-             FilledSquare, HollowSquare, None",
+             FilledSquare, HollowSquare, Filled/HollowTriangleUp, Filled/HollowTriangleDown, \
+             Filled/HollowTriangleLeft, Filled/HollowTriangleRight, FilledDiamond, \
+             HollowDiamond, FilledStar, HollowStar, Cross, Plus, Point, Pixel, None",
             name
         )
         .into()),
@@ -453,18 +1180,83 @@ fn resolve_marker_style(name: &str, size: u32, color: RGB8) -> Result<Option<Mar
 // Series building
 // ---------------------------------------------------------------------------
 
-fn build_series(spec: SeriesSpec, index: usize) -> Result<Series<f64>> {
-    let points = match &spec.data_source {
-        DataSource::Inline(s) => parse_inline_data(s)?,
-        DataSource::File(p) => parse_data_file(Path::new(p))?,
-    };
+fn load_series_data(spec: &SeriesSpec, x_type: XType) -> Result<SeriesData> {
+    let with_values = spec.colormap.is_some();
+    match &spec.data_source {
+        DataSource::Inline(_) if with_values => Err(
+            "--colormap requires --data_file with x,y,c rows; inline data only supports (x,y)"
+                .into(),
+        ),
+        DataSource::Inline(s) if x_type == XType::Category => {
+            let (x_labels, y_values): (Vec<_>, Vec<_>) =
+                parse_inline_category_data(s)?.into_iter().unzip();
+            Ok(SeriesData {
+                points: y_values.into_iter().map(|y| Point::new(0., y)).collect(),
+                x_labels: Some(x_labels),
+                color_values: None,
+                size_values: None,
+            })
+        }
+        DataSource::Inline(s) => Ok(SeriesData {
+            points: parse_inline_data(s)?,
+            x_labels: None,
+            color_values: None,
+            size_values: None,
+        }),
+        DataSource::File(p) if with_values || x_type == XType::Category => {
+            parse_data_file_columns(Path::new(p), with_values, x_type)
+        }
+        DataSource::File(p) => Ok(SeriesData {
+            points: parse_data_file(Path::new(p))?,
+            x_labels: None,
+            color_values: None,
+            size_values: None,
+        }),
+    }
+}
 
-    let palette = &DEFAULT_PALETTE[index % DEFAULT_PALETTE.len()];
-    let marker_size = spec.marker_size.unwrap_or(DEFAULT_MARKER_SIZE);
+/// Assigns x positions to category-labeled points. All series share one set of categories, in
+/// order of first appearance.
+fn assign_category_positions(data: &mut [SeriesData], style: &TextStyle) -> Result<Categories> {
+    let mut categories = Categories::new();
+    for series_data in data.iter_mut() {
+        let Some(labels) = &series_data.x_labels else {
+            continue;
+        };
+        for (point, label) in series_data.points.iter_mut().zip(labels) {
+            check_drawable("Category", label, style)?;
+            point.x = categories.position(label);
+        }
+    }
+    Ok(categories)
+}
+
+/// Gets the range spanned by the color values of all series, if any are colored by value.
+fn color_value_range(data: &[SeriesData]) -> Option<(f64, f64)> {
+    data.iter()
+        .filter_map(|d| d.color_values.as_ref())
+        .flatten()
+        .fold(None, |range, &v| match range {
+            None => Some((v, v)),
+            Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+        })
+}
+
+fn build_series_from_data(
+    spec: SeriesSpec,
+    data: SeriesData,
+    index: usize,
+    color_range: Option<(f64, f64)>,
+    theme: &Theme,
+    text_style: &TextStyle,
+) -> Result<Series<f64>> {
+    let points = data.points;
+
+    let marker_size = spec.marker_size.unwrap_or(DEFAULT_MARKER_SIZE);
     let line_thickness = spec.line_thickness.unwrap_or(DEFAULT_LINE_THICKNESS);
 
     // Resolve colors — if only one is set, the other matches it
-    let default_color = palette.color;
+    let default_color = theme.series_color(index);
     let marker_color = spec
         .marker_color
         .as_deref()
@@ -483,7 +1275,8 @@ fn build_series(spec: SeriesSpec, index: usize) -> Result<Series<f64>> {
     let marker_style = if let Some(style_name) = &spec.marker_style {
         resolve_marker_style(style_name, marker_size, effective_marker_color)?
     } else {
-        Some((palette.marker_fn)(marker_size, effective_marker_color))
+        let marker_fn = DEFAULT_MARKERS[index % DEFAULT_MARKERS.len()];
+        Some(marker_fn(marker_size, effective_marker_color))
     };
 
     let mut series = Series::new(&points);
@@ -519,6 +1312,31 @@ fn build_series(spec: SeriesSpec, index: usize) -> Result<Series<f64>> {
         });
     }
 
+    if let Some(name) = &spec.interpolation {
+        series = series.with_interpolation(resolve_interpolation(name)?);
+    }
+
+    if let Some(name) = &spec.y_axis {
+        series = series.with_y_axis(resolve_y_axis(name)?);
+    }
+
+    if let Some(label) = &spec.label {
+        check_drawable("Label", label, text_style)?;
+        series = series.with_label(label);
+    }
+
+    // Per-point styling from value columns
+    if let Some(cmap_name) = &spec.colormap {
+        let colormap = resolve_colormap(cmap_name)?;
+        if let (Some(values), Some((min, max))) = (&data.color_values, color_range) {
+            series = series.with_color_values(values, colormap, min, max);
+        }
+        if let Some(values) = &data.size_values {
+            let max_size = u32::max(marker_size * SIZE_VALUE_SCALE, 1);
+            series = series.with_size_values(values, marker_size, max_size);
+        }
+    }
+
     Ok(series)
 }
 
@@ -526,6 +1344,42 @@ fn build_series(spec: SeriesSpec, index: usize) -> Result<Series<f64>> {
 // Main
 // ---------------------------------------------------------------------------
 
+/// A graph in the subplot grid, with its top left cell at a 0-based (row, col) position.
+struct Panel {
+    position: (usize, usize),
+    row_span: usize,
+    graph: Graph<f64>,
+}
+
+/// Resolves the colormap shown in each subplot's colorbar. A colorbar can only show one
+/// colormap, so series sharing a subplot must not use different ones.
+fn colorbar_colormaps(specs: &[SeriesSpec]) -> Result<Vec<((usize, usize), Colormap)>> {
+    let mut colormaps: Vec<((usize, usize), Colormap)> = Vec::new();
+    for spec in specs {
+        let Some(name) = spec.colormap.as_deref() else {
+            continue;
+        };
+        let colormap = resolve_colormap(name)?;
+        let position = spec.subplot.unwrap_or((0, 0));
+        match colormaps.iter().find(|(p, _)| *p == position) {
+            Some((_, existing)) if *existing != colormap => {
+                return Err(format!(
+                    "Series in the same subplot use different colormaps ({:?} and {:?}); \
+                     a colorbar can only show one",
+                    existing, colormap
+                )
+                .into());
+            }
+            Some(_) => {}
+            None => colormaps.push((position, colormap)),
+        }
+    }
+    Ok(colormaps)
+}
+
+// the price panel of a candlestick chart is this many times taller than the volume panel
+const PRICE_PANEL_ROWS: usize = 3;
+
 fn run() -> Result<()> {
     let args: Vec<String> = std::env::args().skip(1).collect();
 
@@ -536,14 +1390,43 @@ fn run() -> Result<()> {
 
     let cli = parse_args(args)?;
     let verbose = cli.verbose;
+    let theme = resolve_theme(cli.theme.as_deref())?;
+    if verbose {
+        eprintln!("[verbose] theme: {:?}", theme);
+    }
+    if let Some((cols, rows)) = cli.sparkline {
+        return draw_sparkline(&cli.specs[0], cli.x_type, cols, rows, &theme, verbose);
+    }
 
-    // Build all series
-    let mut graph = Graph::new();
-    for (i, spec) in cli.specs.into_iter().enumerate() {
+    // Titles, categories and series labels are checked against the label font, which with
+    // --font can draw characters the bitmap font lacks
+    let text_style = resolve_text_style(cli.font.as_deref(), theme.text())?;
+    for title in cli.x_title.iter().chain(&cli.y_title) {
+        check_drawable("Title", title, &text_style)?;
+    }
+
+    // Load all data up front so that value-colored series share one color range
+    let mut data = cli
+        .specs
+        .iter()
+        .map(|spec| load_series_data(spec, cli.x_type))
+        .collect::<Result<Vec<_>>>()?;
+    let categories = match cli.x_type {
+        XType::Category => Some(assign_category_positions(&mut data, &text_style)?),
+        XType::Numeric => None,
+    };
+    let color_range = color_value_range(&data);
+    let colorbar_colormaps = colorbar_colormaps(&cli.specs)?;
+
+    // Build all series, grouped by the subplot they are drawn in
+    let mut panels: Vec<Panel> = Vec::new();
+    for (i, (spec, data)) in cli.specs.into_iter().zip(data).enumerate() {
         if verbose {
             eprintln!("[verbose] series {}: {:?}", i, spec);
         }
-        let series = build_series(spec, i)?;
+        let position = spec.subplot.unwrap_or((0, 0));
+        let (hlines, vlines) = (spec.hlines.clone(), spec.vlines.clone());
+        let series = build_series_from_data(spec, data, i, color_range, &theme, &text_style)?;
         if verbose {
             eprintln!(
                 "[verbose] series {}: {} points, marker={:?}, line={:?}",
@@ -553,25 +1436,67 @@ fn run() -> Result<()> {
                 series.line_style()
             );
         }
-        graph = graph.with_series(series);
+        let panel = match panels.iter().position(|p| p.position == position) {
+            Some(index) => &mut panels[index],
+            None => {
+                panels.push(Panel {
+                    position,
+                    row_span: 1,
+                    graph: Graph::new(),
+                });
+                panels.last_mut().unwrap()
+            }
+        };
+        let mut graph = std::mem::replace(&mut panel.graph, Graph::new()).with_series(series);
+        let reference_style = |color: Option<RGB8>| LineStyle::Solid {
+            color: color.unwrap_or(theme.foreground()),
+            thickness: 0,
+        };
+        for (y, color) in hlines {
+            graph = graph.with_hline(y, reference_style(color));
+        }
+        for (x, color) in vlines {
+            graph = graph.with_vline(x, reference_style(color));
+        }
+        panel.graph = graph;
     }
 
-    // Add axes and grid lines
-    let axes_thickness: u32 = 1;
-    graph = graph
-        .with_axes(Axes::new(
-            AxesPositioning::XY(LineStyle::Solid {
-                color: colors::WHITE,
-                thickness: axes_thickness,
+    // Draw candles under any series in the first panel, with volume bars in a panel below
+    let ohlc_rows = cli
+        .ohlc_file
+        .as_deref()
+        .map(|path| parse_ohlc_file(Path::new(path)))
+        .transpose()?;
+    let has_volume = ohlc_rows
+        .as_ref()
+        .is_some_and(|rows| rows.iter().all(|row| row.volume.is_some()));
+    if let Some(rows) = &ohlc_rows {
+        if verbose {
+            eprintln!("[verbose] ohlc: {} rows, volume={}", rows.len(), has_volume);
+        }
+        let candlestick = Candlestick::new(rows, cli.ohlc_kind);
+        match panels.first_mut() {
+            Some(panel) => {
+                panel.graph =
+                    std::mem::replace(&mut panel.graph, Graph::new()).with_candlestick(candlestick)
+            }
+            None => panels.push(Panel {
+                position: (0, 0),
+                row_span: 1,
+                graph: Graph::new().with_candlestick(candlestick),
             }),
-            TextStyle::with_color(colors::WHITE),
-        ))
-        .with_grid_lines(GridLines::XY(LineStyle::Solid {
-            color: colors::GRAY,
-            thickness: 0,
-        }));
+        }
+        if has_volume {
+            panels[0].row_span = PRICE_PANEL_ROWS;
+            panels.push(Panel {
+                position: (PRICE_PANEL_ROWS, 0),
+                row_span: 1,
+                graph: Graph::new().with_candlestick(Candlestick::volume(rows)),
+            });
+        }
+    }
 
-    // Determine canvas size from terminal window
+    // Labels are sized to match the terminal's own text
     let win = get_window_size()?;
     if verbose {
         eprintln!(
@@ -580,48 +1505,112 @@ fn run() -> Result<()> {
         );
     }
 
+    let text_style = text_style.with_cell_size(win.pix_per_col, win.pix_per_row);
+
+    // Add axes, grid lines and legends to every panel
+    let axes_thickness: u32 = 1;
+    for panel in panels.iter_mut() {
+        let mut graph = std::mem::replace(&mut panel.graph, Graph::new());
+        if let Some(categories) = &categories {
+            graph = graph.with_x_categories(categories);
+        }
+        if graph.data().iter().any(|s| s.y_axis() == YAxis::Secondary) {
+            graph = graph.with_secondary_y_axis(SecondaryYAxis::new(
+                LineStyle::Solid {
+                    color: theme.axes(),
+                    thickness: axes_thickness,
+                },
+                text_style.clone(),
+            ));
+        }
+        let mut axes = Axes::new(
+            AxesPositioning::XY(LineStyle::Solid {
+                color: theme.axes(),
+                thickness: axes_thickness,
+            }),
+            text_style.clone(),
+        );
+        if let Some(title) = &cli.x_title {
+            axes = axes.with_x_title(title);
+        }
+        if let Some(title) = &cli.y_title {
+            axes = axes.with_y_title(title);
+        }
+        graph = graph
+            .with_axes(axes)
+            .with_grid_lines(GridLines::XY(LineStyle::Solid {
+                color: theme.grid(),
+                thickness: 0,
+            }))
+            .with_legend(text_style.clone());
+
+        // Show a colorbar for value-colored series
+        let colormap = colorbar_colormaps
+            .iter()
+            .find(|(position, _)| *position == panel.position)
+            .map(|(_, colormap)| *colormap);
+        if let (Some(colormap), Some((min, max))) = (colormap, color_range) {
+            graph = graph.with_colorbar(Colorbar::new(colormap, min, max, text_style.clone()));
+        }
+        panel.graph = graph;
+    }
+    let rows = panels
+        .iter()
+        .map(|p| p.position.0 + p.row_span)
+        .max()
+        .unwrap_or(1);
+    let cols = panels.iter().map(|p| p.position.1 + 1).max().unwrap_or(1);
+
+    // Determine canvas size from terminal window
     let size = std::cmp::min(win.x_pix, win.y_pix) / 2;
     let size = std::cmp::max(size, 200); // minimum 200px
-    let width = size;
-    let height = size;
-    let buffer = std::cmp::max(size / 10, 20);
-
+    // each panel of a subplot grid gets the size of a single graph, within the window
+    let width = std::cmp::max(std::cmp::min(size * cols as u32, win.x_pix), size);
+    let grid_height = match has_volume {
+        true => size + size / PRICE_PANEL_ROWS as u32,
+        false => size * rows as u32,
+    };
+    let height = std::cmp::max(std::cmp::min(grid_height, win.y_pix), size);
+    // margins are sized to fit the tick labels, titles, colorbars and legends of each graph
+    let buffer_type = BufferType::Auto(CANVAS_PADDING);
+    let canvas = TerminalCanvas::new(width, height, theme.background())
+        .with_supersampling(cli.supersampling);
+    let canvas = match (rows, cols) {
+        (1, 1) => canvas
+            .with_buffer(buffer_type)
+            .with_graph(panels.remove(0).graph),
+        _ => {
+            let mut subplots = panels.into_iter().fold(
+                Subplots::new(rows, cols).with_panel_buffer(buffer_type),
+                |subplots, p| {
+                    let (row, col) = p.position;
+                    subplots.with_spanning_graph(row, col, p.row_span, 1, p.graph)
+                },
+            );
+            // keep the volume bars lined up under the candles
+            if has_volume {
+                subplots = subplots.with_shared_x();
+            }
+            canvas.with_subplots(subplots)
+        }
+    };
     if verbose {
-        eprintln!("[verbose] canvas: {}x{} pixels", width, height);
-        eprintln!("[verbose] buffer: {} pixels (uniform)", buffer);
-
-        let largest_marker = graph
-            .data()
-            .iter()
-            .map(|s| s.marker_style().size())
-            .max()
-            .unwrap_or(0);
-        let axes_bound = 2 * axes_thickness;
-        let inset = u32::max(largest_marker, axes_bound);
-        let drawable_w = (width - 1).saturating_sub(2 * (buffer + inset));
-        let drawable_h = (height - 1).saturating_sub(2 * (buffer + inset));
         eprintln!(
-            "[verbose] largest marker: {}, axes bound: {}, effective inset: {}",
-            largest_marker, axes_bound, inset
+            "[verbose] canvas: {}x{} pixels ({}x{} panels), padding: {} pixels",
+            width, height, rows, cols, CANVAS_PADDING
         );
+        // for subplots this is the area shared by the panels
+        let limits = canvas.get_drawable_limits()?;
+        let (w, h) = limits.span();
         eprintln!(
-            "[verbose] estimated drawable area: ~{}x{} pixels",
-            drawable_w, drawable_h
+            "[verbose] drawable area: {}x{} pixels at ({}, {})",
+            w + 1,
+            h + 1,
+            limits.min().x,
+            limits.min().y
         );
-        if drawable_w == 0 || drawable_h == 0 {
-            eprintln!(
-                "[verbose] WARNING: drawable area is zero! Canvas {}x{} is too small \
-                 for buffer ({}) + inset ({}). Consider a larger terminal window.",
-                width, height, buffer, inset
-            );
-        }
     }
-
-    let bytes = TerminalCanvas::new(width, height, colors::BLACK)
-        .with_buffer(BufferType::Uniform(buffer))
-        .with_graph(graph)
-        .draw()?
-        .get_bytes();
+    let bytes = canvas.draw()?.get_bytes();
 
     Image::new(
         PixelFormat::Rgb { width, height },
@@ -635,6 +1624,68 @@ fn run() -> Result<()> {
     Ok(())
 }
 
+/// Draws a series as a sparkline filling the given number of terminal cells, leaving the cursor
+/// just after it so that text can follow on the same line.
+fn draw_sparkline(
+    spec: &SeriesSpec,
+    x_type: XType,
+    cols: u32,
+    rows: u32,
+    theme: &Theme,
+    verbose: bool,
+) -> Result<()> {
+    let data = load_series_data(spec, x_type)?;
+    let values = data.points.iter().map(|p| p.y).collect::<Vec<_>>();
+    if let Some(value) = values.iter().find(|v| !v.is_finite()) {
+        return Err(format!("Sparkline values must be finite, got {value}").into());
+    }
+
+    let color = match &spec.line_color {
+        Some(name) => resolve_color(name)?,
+        None => theme.series_color(0),
+    };
+    let thickness = spec.line_thickness.unwrap_or(DEFAULT_LINE_THICKNESS);
+    let mut sparkline = Sparkline::new(&values)
+        .with_line_style(LineStyle::Solid { color, thickness })
+        .with_background(theme.background());
+    if let Some(name) = &spec.marker_style {
+        let marker_color = match &spec.marker_color {
+            Some(name) => resolve_color(name)?,
+            None => color,
+        };
+        let size = spec.marker_size.unwrap_or(DEFAULT_MARKER_SIZE);
+        if let Some(marker_style) = resolve_marker_style(name, size, marker_color)? {
+            sparkline = sparkline.with_end_marker(marker_style);
+        }
+    }
+
+    let win = get_window_size()?;
+    let width = cols * win.pix_per_col;
+    let height = rows * win.pix_per_row;
+    if verbose {
+        eprintln!(
+            "[verbose] sparkline: {} values, {}x{} cells, {}x{} pixels",
+            values.len(),
+            cols,
+            rows,
+            width,
+            height
+        );
+    }
+
+    let bytes = sparkline.draw(width, height)?.get_bytes();
+    Image::new(
+        PixelFormat::RgbBounded {
+            width,
+            height,
+            rows,
+            cols,
+        },
+        Transmission::Direct(bytes),
+    )?
+    .display()
+}
+
 fn main() {
     if let Err(e) = run() {
         eprintln!("Error: {e}");
@@ -650,6 +1701,13 @@ fn main() {
 mod tests {
     use super::*;
 
+    fn build_series(spec: SeriesSpec, index: usize) -> Result<Series<f64>> {
+        let data = load_series_data(&spec, XType::Numeric)?;
+        let color_range = color_value_range(std::slice::from_ref(&data));
+        let style = TextStyle::default();
+        build_series_from_data(spec, data, index, color_range, &Theme::dark(), &style)
+    }
+
     // -- parse_args tests --
 
     #[test]
@@ -873,6 +1931,23 @@ mod tests {
             .is_none());
     }
 
+    #[test]
+    fn resolve_marker_style_new_shapes() {
+        let color = colors::RED;
+        assert!(matches!(
+            resolve_marker_style("HollowTriangleLeft", 3, color).unwrap(),
+            Some(MarkerStyle::HollowTriangleLeft { size: 3, .. })
+        ));
+        assert!(matches!(
+            resolve_marker_style("filledstar", 3, color).unwrap(),
+            Some(MarkerStyle::FilledStar { size: 3, .. })
+        ));
+        assert!(matches!(
+            resolve_marker_style("PIXEL", 3, color).unwrap(),
+            Some(MarkerStyle::Pixel { .. })
+        ));
+    }
+
     #[test]
     fn resolve_marker_style_invalid_errors() {
         assert!(resolve_marker_style("Triangle", 2, colors::RED).is_err());
@@ -893,6 +1968,16 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Unknown color"));
     }
 
+    #[test]
+    fn resolve_color_accepts_hex_rgb_hsl_and_indices() {
+        assert_eq!(resolve_color("#ff0000").unwrap(), colors::RED);
+        assert_eq!(resolve_color("#00f").unwrap(), colors::BLUE);
+        assert_eq!(resolve_color("rgb(0,255,0)").unwrap(), colors::LIME);
+        assert_eq!(resolve_color("hsl(0,100%,50%)").unwrap(), colors::RED);
+        assert_eq!(resolve_color("196").unwrap(), colors::RED);
+        assert!(resolve_color("#12").is_err());
+    }
+
     // -- build_series tests --
 
     #[test]
@@ -931,6 +2016,33 @@ mod tests {
         assert!(series.line_style().is_some());
     }
 
+    #[test]
+    fn build_series_with_interpolation() {
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.interpolation = Some("step-post".into());
+        let series = build_series(spec, 0).unwrap();
+        assert_eq!(series.interpolation(), LineInterpolation::StepPost);
+    }
+
+    #[test]
+    fn build_series_interpolation_invalid_errors() {
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.interpolation = Some("bezier".into());
+        let result = build_series(spec, 0);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown interpolation")
+        );
+    }
+
+    #[test]
+    fn parse_args_interpolation_before_data_errors() {
+        let args = vec!["--interpolation".into(), "StepPre".into()];
+        assert!(parse_args(args).is_err());
+    }
+
     #[test]
     fn build_series_line_invalid_errors() {
         let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
@@ -965,6 +2077,418 @@ mod tests {
         assert!(cli.verbose);
     }
 
+    #[test]
+    fn parse_args_colormap_flag() {
+        let args = vec![
+            "--data_file".into(),
+            "xyc.csv".into(),
+            "--colormap".into(),
+            "viridis".into(),
+        ];
+        let cli = parse_args(args).unwrap();
+        assert_eq!(cli.specs[0].colormap.as_deref(), Some("viridis"));
+    }
+
+    #[test]
+    fn parse_args_subplot_and_label_flags() {
+        let args = vec![
+            "--data".into(),
+            "(1,2)".into(),
+            "--subplot".into(),
+            "2,3".into(),
+            "--label".into(),
+            "cpu".into(),
+            "--data".into(),
+            "(1,2)".into(),
+        ];
+        let cli = parse_args(args).unwrap();
+        assert_eq!(cli.specs[0].subplot, Some((1, 2)));
+        assert_eq!(cli.specs[0].label.as_deref(), Some("cpu"));
+        assert_eq!(cli.specs[1].subplot, None);
+    }
+
+    #[test]
+    fn build_series_with_y_axis() {
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.y_axis = Some("Secondary".into());
+        assert_eq!(build_series(spec, 0).unwrap().y_axis(), YAxis::Secondary);
+
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.y_axis = Some("left".into());
+        let err = build_series(spec, 0).unwrap_err().to_string();
+        assert!(err.contains("Unknown y axis"));
+    }
+
+    #[test]
+    fn parse_subplot_rejects_invalid_positions() {
+        assert_eq!(parse_subplot(" 1, 1").unwrap(), (0, 0));
+        for val in ["0,1", "1", "a,b", "1,2,3"] {
+            let err = parse_subplot(val).unwrap_err().to_string();
+            assert!(err.contains("row,col"), "{val}: {err}");
+        }
+    }
+
+    #[test]
+    fn build_series_with_label() {
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.label = Some("cpu".into());
+        let series = build_series(spec, 0).unwrap();
+        assert_eq!(series.label(), Some("cpu"));
+
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.label = Some("cpu [%]".into());
+        assert!(build_series(spec, 0).is_err());
+    }
+
+    #[test]
+    fn parse_data_file_columns_with_color_values() {
+        let path = std::env::temp_dir().join("termplt_test_xyc.csv");
+        fs::write(&path, "x,y,c\n1,2,10\n3,4,20\n").unwrap();
+        let data = parse_data_file_columns(&path, true, XType::Numeric).unwrap();
+        assert_eq!(
+            data.points,
+            vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)]
+        );
+        assert_eq!(data.color_values, Some(vec![10.0, 20.0]));
+        assert_eq!(data.size_values, None);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn parse_data_file_columns_with_size_values() {
+        let path = std::env::temp_dir().join("termplt_test_xycs.csv");
+        fs::write(&path, "1 2 10 5\n3 4 20 6\n").unwrap();
+        let data = parse_data_file_columns(&path, true, XType::Numeric).unwrap();
+        assert_eq!(data.color_values, Some(vec![10.0, 20.0]));
+        assert_eq!(data.size_values, Some(vec![5.0, 6.0]));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn parse_data_file_columns_missing_color_errors() {
+        let path = std::env::temp_dir().join("termplt_test_xy_no_c.csv");
+        fs::write(&path, "1,2\n3,4\n").unwrap();
+        let result = parse_data_file_columns(&path, true, XType::Numeric);
+        assert!(result.unwrap_err().to_string().contains("color value"));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn parse_data_file_columns_inconsistent_size_errors() {
+        let path = std::env::temp_dir().join("termplt_test_xycs_partial.csv");
+        fs::write(&path, "1,2,10,5\n3,4,20\n").unwrap();
+        assert!(parse_data_file_columns(&path, true, XType::Numeric).is_err());
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn build_series_with_colormap_colors_points() {
+        let path = std::env::temp_dir().join("termplt_test_build_xyc.csv");
+        fs::write(&path, "1,2,0\n3,4,5\n5,6,10\n").unwrap();
+        let mut spec = SeriesSpec::new(DataSource::File(path.display().to_string()));
+        spec.colormap = Some("Magma".into());
+        let series = build_series(spec, 0).unwrap();
+        assert_eq!(
+            series.marker_style_at(0).color(),
+            Colormap::Magma.color_at(0.)
+        );
+        assert_eq!(
+            series.marker_style_at(2).color(),
+            Colormap::Magma.color_at(1.)
+        );
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn build_series_colormap_with_inline_data_errors() {
+        let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+        spec.colormap = Some("viridis".into());
+        assert!(build_series(spec, 0).is_err());
+    }
+
+    #[test]
+    fn colorbar_colormaps_per_subplot() {
+        let mut first = SeriesSpec::new(DataSource::File("a.csv".into()));
+        first.colormap = Some("viridis".into());
+        let mut second = SeriesSpec::new(DataSource::File("b.csv".into()));
+        second.colormap = Some("Magma".into());
+        second.subplot = Some((0, 1));
+        let plain = SeriesSpec::new(DataSource::Inline("(1,2)".into()));
+        assert_eq!(
+            colorbar_colormaps(&[first, second, plain]).unwrap(),
+            vec![((0, 0), Colormap::Viridis), ((0, 1), Colormap::Magma)]
+        );
+    }
+
+    #[test]
+    fn colorbar_colormaps_mixed_in_one_subplot_errors() {
+        let mut first = SeriesSpec::new(DataSource::File("a.csv".into()));
+        first.colormap = Some("viridis".into());
+        let mut second = SeriesSpec::new(DataSource::File("b.csv".into()));
+        second.colormap = Some("Magma".into());
+        let err = colorbar_colormaps(&[first, second]).unwrap_err();
+        assert!(err.to_string().contains("different colormaps"));
+    }
+
+    #[test]
+    fn resolve_colormap_invalid_errors() {
+        let result = resolve_colormap("jet");
+        assert!(result.unwrap_err().to_string().contains("Unknown colormap"));
+    }
+
+    // -- categorical x values --
+
+    #[test]
+    fn parse_args_font() {
+        let args = vec![
+            "--font".into(),
+            "default".into(),
+            "--data".into(),
+            "(1,1)".into(),
+        ];
+        assert_eq!(parse_args(args).unwrap().font.as_deref(), Some("default"));
+        let args = vec!["--data".into(), "(1,1)".into(), "--font".into()];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn resolve_text_style_without_font_uses_bitmap_text() {
+        let style = resolve_text_style(None, colors::WHITE).unwrap();
+        assert_eq!(style.color(), colors::WHITE);
+        #[cfg(not(feature = "ttf"))]
+        assert!(resolve_text_style(Some("default"), colors::WHITE).is_err());
+        #[cfg(feature = "ttf")]
+        assert!(
+            resolve_text_style(Some("default"), colors::WHITE)
+                .unwrap()
+                .font()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn parse_args_theme() {
+        let args = vec![
+            "--data".into(),
+            "(1,1)".into(),
+            "--theme".into(),
+            "light".into(),
+        ];
+        assert_eq!(parse_args(args).unwrap().theme.as_deref(), Some("light"));
+        let args = vec!["--data".into(), "(1,1)".into(), "--theme".into()];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn parse_args_supersample() {
+        let args = |factor: &str| {
+            vec![
+                "--data".into(),
+                "(1,1)".into(),
+                "--supersample".into(),
+                factor.into(),
+            ]
+        };
+        assert_eq!(
+            parse_args(vec!["--data".into(), "(1,1)".into()])
+                .unwrap()
+                .supersampling,
+            1
+        );
+        assert_eq!(parse_args(args("4")).unwrap().supersampling, 4);
+        assert!(parse_args(args("0")).is_err());
+        assert!(parse_args(args("8")).is_err());
+        assert!(parse_args(args("two")).is_err());
+    }
+
+    #[test]
+    fn resolve_theme_by_name_or_file() {
+        assert_eq!(
+            resolve_theme(Some("Solarized")).unwrap(),
+            Theme::solarized()
+        );
+        assert!(resolve_theme(Some("neon")).is_err());
+
+        let path = std::env::temp_dir().join("termplt_test_theme.toml");
+        fs::write(&path, "base = \"light\"\ngrid = silver\n").unwrap();
+        let theme = resolve_theme(path.to_str()).unwrap();
+        assert_eq!(theme, Theme::light().with_grid(colors::SILVER));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn series_colors_follow_theme_palette() {
+        let theme = Theme::light();
+        for index in [0, 1, 12] {
+            let spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
+            let data = load_series_data(&spec, XType::Numeric).unwrap();
+            let series =
+                build_series_from_data(spec, data, index, None, &theme, &TextStyle::default())
+                    .unwrap();
+            match series.line_style() {
+                Some(LineStyle::Solid { color, .. }) => {
+                    assert_eq!(*color, theme.series_color(index))
+                }
+                other => panic!("unexpected line style {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn resolve_text_style_matches_cell_size() {
+        assert_eq!(
+            resolve_text_style(None, colors::WHITE)
+                .unwrap()
+                .with_cell_size(8, 16)
+                .scale(),
+            1
+        );
+        assert_eq!(
+            resolve_text_style(None, colors::WHITE)
+                .unwrap()
+                .with_cell_size(20, 40)
+                .scale(),
+            3
+        );
+    }
+
+    #[test]
+    fn parse_args_x_type() {
+        let args = vec![
+            "--x_type".into(),
+            "Category".into(),
+            "--data".into(),
+            "(a,1)".into(),
+        ];
+        assert_eq!(parse_args(args).unwrap().x_type, XType::Category);
+        let args = vec!["--data".into(), "(1,1)".into()];
+        assert_eq!(parse_args(args).unwrap().x_type, XType::Numeric);
+        let args = vec![
+            "--x_type".into(),
+            "date".into(),
+            "--data".into(),
+            "(1,1)".into(),
+        ];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn parse_args_axis_titles() {
+        let args = vec![
+            "--data".into(),
+            "(1,1)".into(),
+            "--x_title".into(),
+            "time (s)".into(),
+            "--y_title".into(),
+            "requests".into(),
+        ];
+        let cli = parse_args(args).unwrap();
+        assert_eq!(cli.x_title.as_deref(), Some("time (s)"));
+        assert_eq!(cli.y_title.as_deref(), Some("requests"));
+        let args = vec!["--data".into(), "(1,1)".into(), "--y_title".into()];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn title_with_undrawable_character_errors() {
+        let style = resolve_text_style(None, colors::WHITE).unwrap();
+        for title in ["Latency [ms]", "µs"] {
+            let err = check_drawable("Title", title, &style).unwrap_err();
+            assert!(err.to_string().contains("cannot be drawn"), "{err}");
+        }
+        assert!(check_drawable("Title", "time (s)", &style).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "ttf")]
+    fn font_draws_characters_without_bitmaps() {
+        let style = resolve_text_style(Some("default"), colors::WHITE).unwrap();
+        for text in ["Latency [ms]", "µs", "Café"] {
+            assert!(check_drawable("Label", text, &style).is_ok());
+        }
+        assert!(check_drawable("Label", "\u{e000}", &style).is_err());
+    }
+
+    #[test]
+    fn parse_args_empty_axis_title_errors() {
+        for flag in ["--x_title", "--y_title"] {
+            for title in ["", "   "] {
+                let args = vec!["--data".into(), "(1,1)".into(), flag.into(), title.into()];
+                let err = parse_args(args).unwrap_err().to_string();
+                assert!(err.contains("non-empty"), "{err}");
+            }
+        }
+    }
+
+    #[test]
+    fn parse_inline_category_data_valid() {
+        let data = parse_inline_category_data("(Mon,3),(Tue, 5.5)").unwrap();
+        assert_eq!(
+            data,
+            vec![("Mon".to_string(), 3.), ("Tue".to_string(), 5.5)]
+        );
+        assert!(parse_inline_category_data("(Mon)").is_err());
+        assert!(parse_inline_category_data("(Mon,x)").is_err());
+    }
+
+    #[test]
+    fn parse_data_file_with_category_x_values() {
+        let path = std::env::temp_dir().join("termplt_test_categories.csv");
+        fs::write(&path, "day,count\nMon,3\nTue,5\n").unwrap();
+        let data = parse_data_file_columns(&path, false, XType::Category).unwrap();
+        assert_eq!(data.x_labels.unwrap(), vec!["Mon", "Tue"]);
+        assert_eq!(data.points[1].y, 5.);
+        assert!(parse_data_file(&path).is_err());
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn category_positions_are_shared_between_series() {
+        let spec_a = SeriesSpec::new(DataSource::Inline("(Mon,1),(Tue,2)".into()));
+        let spec_b = SeriesSpec::new(DataSource::Inline("(Wed,1),(Mon,2)".into()));
+        let mut data = vec![
+            load_series_data(&spec_a, XType::Category).unwrap(),
+            load_series_data(&spec_b, XType::Category).unwrap(),
+        ];
+        let categories = assign_category_positions(&mut data, &TextStyle::default()).unwrap();
+        assert_eq!(categories.names(), &["Mon", "Tue", "Wed"]);
+        assert_eq!(data[1].points, vec![Point::new(2., 1.), Point::new(0., 2.)]);
+    }
+
+    #[test]
+    fn category_with_undrawable_character_errors() {
+        let spec = SeriesSpec::new(DataSource::Inline("(a&b,1)".into()));
+        let mut data = vec![load_series_data(&spec, XType::Category).unwrap()];
+        let result = assign_category_positions(&mut data, &TextStyle::default());
+        assert!(result.unwrap_err().to_string().contains("cannot be drawn"));
+    }
+
+    #[test]
+    fn color_value_range_spans_all_series() {
+        let data = vec![
+            SeriesData {
+                points: vec![Point::new(0., 0.)],
+                x_labels: None,
+                color_values: Some(vec![5., 2.]),
+                size_values: None,
+            },
+            SeriesData {
+                points: vec![Point::new(0., 0.)],
+                x_labels: None,
+                color_values: None,
+                size_values: None,
+            },
+            SeriesData {
+                points: vec![Point::new(0., 0.)],
+                x_labels: None,
+                color_values: Some(vec![-1., 3.]),
+                size_values: None,
+            },
+        ];
+        assert_eq!(color_value_range(&data), Some((-1., 5.)));
+        assert_eq!(color_value_range(&data[1..2]), None);
+    }
+
     #[test]
     fn build_series_marker_none() {
         let mut spec = SeriesSpec::new(DataSource::Inline("(1,2),(3,4)".into()));
@@ -973,4 +2497,166 @@ mod tests {
         // Should have zero-size marker
         assert_eq!(series.marker_style().size(), 0);
     }
+
+    #[test]
+    fn parse_ohlc_file_with_and_without_volume() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("termplt_test_ohlc.csv");
+        fs::write(&path, "t,o,h,l,c,v\n1,10,12,9,11,100\n2,11,11.5,7,8,300\n").unwrap();
+        let rows = parse_ohlc_file(&path).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], Ohlc::new(2., 11., 11.5, 7., 8.).with_volume(300.));
+
+        fs::write(&path, "1 10 12 9 11\n").unwrap();
+        assert_eq!(parse_ohlc_file(&path).unwrap()[0].volume, None);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn parse_ohlc_file_invalid_rows_error() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("termplt_test_ohlc_invalid.csv");
+        for (content, expected) in [
+            ("1,10,12,9\n", "expected 5 or 6 values"),
+            ("1,10,12,9,11,100\n2,11,12,9,11\n", "volume column"),
+            ("1,10,9,8,8.5\n", "must bound"),
+            ("1,10,12,9,x\n", "cannot parse value 'x'"),
+        ] {
+            fs::write(&path, content).unwrap();
+            let err = parse_ohlc_file(&path).unwrap_err().to_string();
+            assert!(err.contains(expected), "{content}: {err}");
+        }
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn parse_args_ohlc_flags() {
+        let args = vec![
+            "--ohlc_file".into(),
+            "prices.csv".into(),
+            "--ohlc_kind".into(),
+            "Bar".into(),
+        ];
+        let cli = parse_args(args).unwrap();
+        assert!(cli.specs.is_empty());
+        assert_eq!(cli.ohlc_file.as_deref(), Some("prices.csv"));
+        assert_eq!(cli.ohlc_kind, CandlestickKind::Bar);
+
+        let args = vec![
+            "--ohlc_file".into(),
+            "prices.csv".into(),
+            "--data".into(),
+            "(1,2)".into(),
+            "--subplot".into(),
+            "2,1".into(),
+        ];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn parse_args_sparkline_sizes() {
+        let args = vec![
+            "--sparkline".into(),
+            "12".into(),
+            "--data".into(),
+            "(1,2)".into(),
+        ];
+        assert_eq!(parse_args(args).unwrap().sparkline, Some((12, 1)));
+
+        let args = vec![
+            "--sparkline".into(),
+            "12x2".into(),
+            "--data".into(),
+            "(1,2)".into(),
+        ];
+        assert_eq!(parse_args(args).unwrap().sparkline, Some((12, 2)));
+
+        for size in ["0", "12x0", "x2", "wide"] {
+            let args = vec![
+                "--sparkline".into(),
+                size.into(),
+                "--data".into(),
+                "(1,2)".into(),
+            ];
+            assert!(parse_args(args).is_err(), "size {size} should be rejected");
+        }
+    }
+
+    #[test]
+    fn parse_args_sparkline_requires_single_series() {
+        let args = vec![
+            "--sparkline".into(),
+            "12".into(),
+            "--data".into(),
+            "(1,2)".into(),
+            "--data".into(),
+            "(1,3)".into(),
+        ];
+        assert!(parse_args(args).is_err());
+
+        let args = vec![
+            "--sparkline".into(),
+            "12".into(),
+            "--data".into(),
+            "(1,2)".into(),
+            "--y_axis".into(),
+            "secondary".into(),
+        ];
+        assert!(parse_args(args).is_err());
+    }
+
+    #[test]
+    fn draw_sparkline_non_finite_value_errors() {
+        let spec = SeriesSpec::new(DataSource::Inline("(1,2),(2,nan)".into()));
+        let err = draw_sparkline(&spec, XType::Numeric, 10, 1, &Theme::dark(), false).unwrap_err();
+        assert!(err.to_string().contains("must be finite"));
+    }
+
+    #[test]
+    fn parse_args_reference_lines() {
+        let args = vec![
+            "--data".into(),
+            "(1,2)".into(),
+            "--hline".into(),
+            "250".into(),
+            "--hline".into(),
+            "300, red".into(),
+            "--vline".into(),
+            "1.5".into(),
+            "--vline".into(),
+            "2.5,rgb(0, 0, 255)".into(),
+        ];
+        let cli = parse_args(args).unwrap();
+        assert_eq!(
+            cli.specs[0].hlines,
+            vec![(250., None), (300., Some(colors::RED))]
+        );
+        assert_eq!(
+            cli.specs[0].vlines,
+            vec![(1.5, None), (2.5, Some(colors::BLUE))]
+        );
+
+        for args in [
+            vec![
+                "--hline".into(),
+                "250".into(),
+                "--data".into(),
+                "(1,2)".into(),
+            ],
+            vec![
+                "--data".into(),
+                "(1,2)".into(),
+                "--vline".into(),
+                "soon".into(),
+            ],
+            vec![
+                "--data".into(),
+                "(1,2)".into(),
+                "--hline".into(),
+                "1,nocolor".into(),
+            ],
+        ] {
+            assert!(parse_args(args).is_err());
+        }
+    }
 }
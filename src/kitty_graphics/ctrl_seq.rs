@@ -23,9 +23,24 @@ impl CtrlSeq for Transmission {
 #[derive(Clone)]
 pub enum PixelFormat {
     Png,
-    PngBounded { rows: u32, cols: u32 },
-    Rgb { width: u32, height: u32 },
-    Rgba { width: u32, height: u32 },
+    PngBounded {
+        rows: u32,
+        cols: u32,
+    },
+    Rgb {
+        width: u32,
+        height: u32,
+    },
+    RgbBounded {
+        width: u32,
+        height: u32,
+        rows: u32,
+        cols: u32,
+    },
+    Rgba {
+        width: u32,
+        height: u32,
+    },
 }
 
 impl CtrlSeq for PixelFormat {
@@ -37,6 +52,12 @@ impl CtrlSeq for PixelFormat {
                 width: pix_width,
                 height: pix_height,
             } => format!("f=24,s={pix_width},v={pix_height}"),
+            PixelFormat::RgbBounded {
+                width: pix_width,
+                height: pix_height,
+                rows,
+                cols,
+            } => format!("f=24,s={pix_width},v={pix_height},c={cols},r={rows}"),
             PixelFormat::Rgba {
                 width: pix_width,
                 height: pix_height,
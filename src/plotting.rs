@@ -1,15 +1,35 @@
+pub mod annotation;
+pub mod arrow;
 pub mod axes;
+pub mod boxplot;
+pub mod candlestick;
 pub mod canvas;
+pub mod categories;
+pub mod colorbar;
+pub mod colormaps;
 pub mod colors;
 pub mod common;
+pub mod contour;
+#[cfg(feature = "ttf")]
+pub mod font;
 pub mod graph;
 mod graph_limits;
 pub mod grid_lines;
+pub mod interpolation;
+mod layout;
+pub mod legend;
 pub mod limits;
 pub mod line;
 mod line_positioning;
 pub mod marker;
 pub mod numbers;
+pub mod pie;
 pub mod point;
+pub mod polar;
+pub mod quiver;
+pub mod reference;
 pub mod series;
+pub mod sparkline;
+pub mod subplots;
 pub mod text;
+pub mod theme;
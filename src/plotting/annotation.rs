@@ -0,0 +1,196 @@
+use super::{
+    arrow::Arrow,
+    common::{Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable},
+    limits::Limits,
+    line::LineStyle,
+    point::Point,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+
+// pixels left between the text and the tail of its arrow
+const ARROW_GAP: f64 = 2.;
+
+/// Text anchored to a point in data coordinates, e.g. to call out a peak or an outage. The point
+/// is scaled along with the series of the graph, while the text can be moved away from it by a
+/// fixed number of pixels, with an optional arrow from the text back to the point.
+#[derive(Debug, Clone)]
+pub struct Annotation<T: Graphable> {
+    point: Point<T>,
+    text: String,
+    style: TextStyle,
+    alignment: fn(Point<u32>) -> TextPositioning,
+    offset: Point<i32>,
+    arrow: Option<LineStyle>,
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for Annotation<T> {
+    type ConvertTo = Annotation<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        self.map_point(|p| p.convert_to(convert_fn))
+    }
+}
+
+impl<T: Graphable> Annotation<T> {
+    /// Creates an annotation with the text centered on the point.
+    pub fn new(point: Point<T>, text: &str, style: TextStyle) -> Annotation<T> {
+        Annotation {
+            point,
+            text: String::from(text),
+            style,
+            alignment: TextPositioning::Centered,
+            offset: Point::new(0, 0),
+            arrow: None,
+        }
+    }
+
+    /// Sets how the text is placed relative to its position, e.g.
+    /// `with_alignment(TextPositioning::LeftAligned)`.
+    pub fn with_alignment(mut self, alignment: fn(Point<u32>) -> TextPositioning) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Moves the text away from the point by a number of pixels; positive values move it right
+    /// and up.
+    pub fn with_offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = Point::new(x, y);
+        self
+    }
+
+    /// Draws an arrow from the text to the point. Nothing is drawn unless the text is offset
+    /// clear of the point.
+    pub fn with_arrow(mut self, style: LineStyle) -> Self {
+        self.arrow = Some(style);
+        self
+    }
+
+    pub fn point(&self) -> &Point<T> {
+        &self.point
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn map_point<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> Annotation<U> {
+        Annotation {
+            point: map_fn(self.point),
+            text: self.text.clone(),
+            style: self.style.clone(),
+            alignment: self.alignment,
+            offset: self.offset,
+            arrow: self.arrow,
+        }
+    }
+}
+
+impl<T: FloatConvertable + Graphable> Annotation<T> {
+    /// Gets the label for the text, once the point has been scaled to pixels.
+    pub fn get_label(&self) -> Label {
+        let point = self.point.convert_to_f64();
+        let position = Point::new(
+            point.x + self.offset.x as f64,
+            point.y + self.offset.y as f64,
+        );
+        let txt = Text::new(&self.text, self.style.clone());
+        Label::new(txt, (self.alignment)(position.round()))
+    }
+
+    /// Gets the arrow from the edge of the text to the point, if the point is outside the text.
+    pub fn get_arrow(&self) -> Option<Arrow> {
+        let style = self.arrow?;
+        let tip = self.point.convert_to_f64();
+        let label_limits = self.get_label().limits().convert_to_f64();
+        let min = *label_limits.min() - Point::new(ARROW_GAP, ARROW_GAP);
+        let max = *label_limits.max() + Point::new(ARROW_GAP, ARROW_GAP);
+        let outside = |p: Point<f64>| p.x < min.x || p.x > max.x || p.y < min.y || p.y > max.y;
+        if !outside(tip) {
+            return None;
+        }
+
+        // start from the text position and leave the box around the text towards the tip
+        let start = self.get_label().pos().point().convert_to_f64();
+        let dir = tip - start;
+        let exit = |from: f64, delta: f64, low: f64, high: f64| match delta {
+            d if d > 0. => (high - from) / d,
+            d if d < 0. => (low - from) / d,
+            _ => f64::INFINITY,
+        };
+        let t = f64::min(
+            exit(start.x, dir.x, min.x, max.x),
+            exit(start.y, dir.y, min.y, max.y),
+        );
+        Some(Arrow::new(start + dir * t.clamp(0., 1.), tip, style))
+    }
+}
+
+impl<T: FloatConvertable + Graphable> Drawable for Annotation<T> {
+    /// Draws the arrow; the text is drawn separately as a [`Label`].
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        match self.get_arrow() {
+            Some(arrow) => arrow.draw(sink),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T, U> Scalable<T, U> for Annotation<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = Annotation<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        self.map_point(|p| p.scale_to(old_limits, new_limits))
+    }
+}
+
+impl<T> Shiftable<T> for Annotation<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(self, amount: Point<T>) -> Self {
+        self.map_point(|p| p + amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    #[test]
+    fn offset_moves_text_but_not_point() {
+        let annotation = Annotation::new(Point::new(50., 50.), "peak", TextStyle::default())
+            .with_alignment(TextPositioning::LeftAligned)
+            .with_offset(10, -5);
+        let label = annotation.get_label();
+        assert_eq!(label.pos().point(), &Point::new(60, 45));
+        assert_eq!(label.limits().min().x, 60);
+        assert!(annotation.get_mask().unwrap().is_empty());
+    }
+
+    #[test]
+    fn arrow_runs_from_text_edge_to_point() {
+        let annotation = Annotation::new(Point::new(50., 50.), "x", TextStyle::default())
+            .with_offset(0, 40)
+            .with_arrow(LineStyle::Solid {
+                color: colors::RED,
+                thickness: 0,
+            });
+        let label_limits = annotation.get_label().limits();
+        let arrow = annotation.get_arrow().unwrap();
+        assert_eq!(arrow.tip(), &Point::new(50., 50.));
+        assert_eq!(arrow.tail().x, 50.);
+        assert_eq!(arrow.tail().y, label_limits.min().y as f64 - ARROW_GAP);
+    }
+
+    #[test]
+    fn no_arrow_when_point_is_under_text() {
+        let annotation = Annotation::new(Point::new(50., 50.), "long text", TextStyle::default())
+            .with_offset(2, 0)
+            .with_arrow(LineStyle::default());
+        assert!(annotation.get_arrow().is_none());
+    }
+}
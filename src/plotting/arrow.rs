@@ -0,0 +1,184 @@
+use super::{
+    common::{Drawable, PixelSink},
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    point::Point,
+};
+use crate::common::Result;
+
+// head length as a fraction of the arrow length, so heads grow with the arrow
+const HEAD_FRACTION: f64 = 0.3;
+const MIN_HEAD_LENGTH: f64 = 2.;
+// half the width of the head's base, as a fraction of the head length
+const HEAD_HALF_WIDTH: f64 = 0.5;
+
+/// Straight arrow from a tail to a tip, in pixel space. The shaft is drawn as a [`Line`] and the
+/// head as a filled triangle whose length is proportional to the length of the arrow.
+#[derive(Debug, Clone)]
+pub struct Arrow {
+    tail: Point<f64>,
+    tip: Point<f64>,
+    style: LineStyle,
+    head_length: f64,
+}
+
+impl Arrow {
+    pub fn new(tail: Point<f64>, tip: Point<f64>, style: LineStyle) -> Arrow {
+        let length = tail.dist(&tip);
+        let head_length = f64::min(f64::max(HEAD_FRACTION * length, MIN_HEAD_LENGTH), length);
+        Arrow {
+            tail,
+            tip,
+            style,
+            head_length,
+        }
+    }
+
+    /// Sets the length of the head in pixels; it is capped at the length of the arrow.
+    pub fn with_head_length(mut self, head_length: f64) -> Self {
+        self.head_length = f64::min(head_length, self.length());
+        self
+    }
+
+    pub fn tail(&self) -> &Point<f64> {
+        &self.tail
+    }
+
+    pub fn tip(&self) -> &Point<f64> {
+        &self.tip
+    }
+
+    pub fn style(&self) -> &LineStyle {
+        &self.style
+    }
+
+    pub fn head_length(&self) -> f64 {
+        self.head_length
+    }
+
+    pub fn length(&self) -> f64 {
+        self.tail.dist(&self.tip)
+    }
+
+    /// Gets the corners of the head: the tip, followed by the two ends of its base.
+    pub fn head(&self) -> [Point<f64>; 3] {
+        let length = self.length();
+        let dir = (self.tip - self.tail) / length;
+        let normal = Point::new(-dir.y, dir.x);
+        let base = self.tip - dir * self.head_length;
+        let half_width = HEAD_HALF_WIDTH * self.head_length;
+        [
+            self.tip,
+            base + normal * half_width,
+            base - normal * half_width,
+        ]
+    }
+
+    /// Draws the pixels inside the head, by testing the center of every pixel in its bounding
+    /// box against each edge.
+    fn draw_head<S: PixelSink + ?Sized>(&self, sink: &mut S) {
+        let [a, b, c] = self.head();
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0.) as u32;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0.) as u32;
+        let max_x = a.x.max(b.x).max(c.x).ceil().max(0.) as u32;
+        let max_y = a.y.max(b.y).max(c.y).ceil().max(0.) as u32;
+
+        let edge = |p: &Point<f64>, q: &Point<f64>, r: &Point<f64>| {
+            (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)
+        };
+        let color = self.style.color();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let p = Point::new(x as f64, y as f64);
+                let signs = [edge(&a, &b, &p), edge(&b, &c, &p), edge(&c, &a, &p)];
+                if signs.iter().all(|s| *s >= 0.) || signs.iter().all(|s| *s <= 0.) {
+                    sink.set_pixel(&Point::new(x, y), &color);
+                }
+            }
+        }
+    }
+}
+
+impl Drawable for Arrow {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        // an arrow shorter than a pixel has no direction to show
+        if self.length() < 1. {
+            sink.set_pixel(&self.tail.round(), &self.style.color());
+            return Ok(());
+        }
+
+        // the shaft stops where the head starts so thick shafts don't poke through the tip
+        let dir = (self.tip - self.tail) / self.length();
+        let shaft_end = self.tip - dir * self.head_length;
+        Line::new(
+            LinePositioning::BetweenPoints {
+                start: self.tail.round(),
+                end: shaft_end.round(),
+            },
+            self.style,
+        )
+        .draw(sink)?;
+        self.draw_head(sink);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn arrow(tail: (f64, f64), tip: (f64, f64)) -> Arrow {
+        Arrow::new(
+            Point::new(tail.0, tail.1),
+            Point::new(tip.0, tip.1),
+            LineStyle::default(),
+        )
+    }
+
+    #[test]
+    fn head_scales_with_length() {
+        assert_eq!(arrow((0., 0.), (10., 0.)).head_length(), 3.);
+        assert_eq!(arrow((0., 0.), (40., 0.)).head_length(), 12.);
+        // short arrows keep a visible head, but never longer than the arrow
+        assert_eq!(arrow((0., 0.), (4., 0.)).head_length(), 2.);
+        assert_eq!(arrow((0., 0.), (1., 0.)).head_length(), 1.);
+    }
+
+    #[test]
+    fn head_points_towards_the_tip() {
+        let arrow = arrow((10., 10.), (10., 30.)).with_head_length(6.);
+        let [tip, left, right] = arrow.head();
+        assert_eq!(tip, Point::new(10., 30.));
+        assert_eq!(left, Point::new(7., 24.));
+        assert_eq!(right, Point::new(13., 24.));
+
+        let points = arrow
+            .get_mask()
+            .unwrap()
+            .into_iter()
+            .flat_map(|m| m.points)
+            .collect::<Vec<_>>();
+        assert!(points.contains(&Point::new(10, 30)));
+        assert!(points.contains(&Point::new(13, 24)));
+        assert!(points.contains(&Point::new(10, 10)));
+        assert!(!points.contains(&Point::new(13, 29)));
+    }
+
+    #[test]
+    fn tiny_arrow_is_a_single_pixel() {
+        let masks = Arrow::new(
+            Point::new(5., 5.),
+            Point::new(5.2, 5.),
+            LineStyle::Solid {
+                color: colors::RED,
+                thickness: 0,
+            },
+        )
+        .get_mask()
+        .unwrap();
+        assert_eq!(masks.len(), 1);
+        assert_eq!(masks[0].points, vec![Point::new(5, 5)]);
+        assert_eq!(masks[0].color, colors::RED);
+    }
+}
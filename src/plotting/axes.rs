@@ -1,5 +1,5 @@
 use super::{
-    common::{Drawable, FloatConvertable, Graphable, MaskPoints},
+    common::{Drawable, FloatConvertable, Graphable, MaskPoints, PixelSink},
     grid_lines::NUM_GRID_SECTIONS,
     limits::Limits,
     line::{Line, LineStyle},
@@ -9,6 +9,11 @@ use super::{
 };
 use crate::common::Result;
 
+// minimum space between neighboring labels before they are rotated or skipped
+const LABEL_GAP: f64 = 4.;
+// angle of category labels that are too long to fit side by side
+const SLANTED_LABEL_DEGREES: f64 = 45.;
+
 #[derive(Debug, Clone)]
 pub enum AxesPositioning {
     XOnly(LineStyle),
@@ -16,15 +21,55 @@ pub enum AxesPositioning {
     XY(LineStyle),
 }
 
+/// Right-hand y axis for series plotted against a second set of y values, e.g. latency next to
+/// request rates. Its ticks line up with the ticks of the primary y axis.
+#[derive(Debug, Clone)]
+pub struct SecondaryYAxis {
+    line_style: LineStyle,
+    style: TextStyle,
+}
+
 #[derive(Debug, Clone)]
 pub struct Axes {
     positioning: AxesPositioning,
     style: TextStyle,
+    x_categories: Option<Vec<String>>,
+    x_title: Option<String>,
+    y_title: Option<String>,
 }
 
 impl Axes {
     pub fn new(positioning: AxesPositioning, style: TextStyle) -> Axes {
-        Axes { positioning, style }
+        Axes {
+            positioning,
+            style,
+            x_categories: None,
+            x_title: None,
+            y_title: None,
+        }
+    }
+
+    /// Names the x axis with a title centered below its labels.
+    pub fn with_x_title(mut self, title: &str) -> Self {
+        self.x_title = Some(String::from(title));
+        self
+    }
+
+    /// Names the y axis with a title left of its labels, reading from bottom to top.
+    pub fn with_y_title(mut self, title: &str) -> Self {
+        self.y_title = Some(String::from(title));
+        self
+    }
+
+    /// Labels the x axis with category names instead of numbers. The category at index `i` is
+    /// placed at x value `i`.
+    pub fn with_x_categories<S: AsRef<str>>(mut self, categories: &[S]) -> Self {
+        self.x_categories = Some(categories.iter().map(|c| c.as_ref().to_string()).collect());
+        self
+    }
+
+    pub fn x_categories(&self) -> Option<&[String]> {
+        self.x_categories.as_deref()
     }
 
     pub fn positioning(&self) -> &AxesPositioning {
@@ -49,28 +94,120 @@ impl Axes {
         let x_starts = x_starts_graph.iter().zip(x_starts_canvas);
         let y_starts = y_starts_graph.iter().zip(y_starts_canvas);
 
-        let x_labels = |line_style: &LineStyle| {
-            x_starts
+        // get the text for each x label along with its x position on the canvas
+        let x_ticks = match &self.x_categories {
+            Some(categories) => {
+                let (graph_span_x, _) = graph_limits.span();
+                let (canvas_span_x, _) = canvas_limits.span();
+                let ticks = categories
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| {
+                        (graph_limits.min().x..=graph_limits.max().x).contains(&(*i as f64))
+                    })
+                    .map(|(i, name)| {
+                        let x = if graph_span_x == 0. {
+                            (canvas_limits.min().x + canvas_limits.max().x) / 2.
+                        } else {
+                            canvas_limits.min().x
+                                + (i as f64 - graph_limits.min().x) / graph_span_x * canvas_span_x
+                        };
+                        (Text::new(name, self.style.clone()), x)
+                    })
+                    .collect::<Vec<_>>();
+
+                // slant all labels if any of them would run into a neighbor, or turn them to read
+                // vertically if even slanted labels would be too close together
+                let spacing = match graph_span_x {
+                    0. => f64::INFINITY,
+                    _ => canvas_span_x / graph_span_x,
+                };
+                let crowded = ticks
+                    .iter()
+                    .any(|(txt, _)| txt.width() as f64 + LABEL_GAP > spacing);
+                let tallest = ticks.iter().map(|(txt, _)| txt.height()).max().unwrap_or(0);
+                let slanted_spacing = spacing * SLANTED_LABEL_DEGREES.to_radians().sin();
+                let rotation = match crowded {
+                    false => 0.,
+                    true if tallest as f64 + LABEL_GAP <= slanted_spacing => SLANTED_LABEL_DEGREES,
+                    true => 90.,
+                };
+
+                // slanted labels end at their position rather than being centered on it
+                ticks
+                    .into_iter()
+                    .map(|(txt, x)| {
+                        let txt = txt.with_rotation(rotation);
+                        match rotation == SLANTED_LABEL_DEGREES {
+                            true => {
+                                let shift = txt.width() as f64 / 2.;
+                                (txt, x - shift)
+                            }
+                            false => (txt, x),
+                        }
+                    })
+                    .collect()
+            }
+            None => x_starts
                 .map(|(graph_start, canvas_start)| {
                     let txt = Text::from_number(graph_start.x, 3, self.style.clone());
                     let mut x = canvas_start.x;
-                    let y = canvas_start.y
-                        - line_style.thickness().convert_to_f64() * 2.
-                        - (txt.height() as f64 / 2.);
 
                     // shift positioning if number is negative
                     if graph_start.x < 0. {
-                        x -= Text::new("-", TextStyle::default()).width() as f64 / 2.;
+                        x -= Text::new("-", self.style.clone()).width() as f64 / 2.;
                     }
+                    (txt, x)
+                })
+                .collect::<Vec<_>>(),
+        };
+        // numeric labels are thinned out when crowded, while every category keeps its name
+        let x_ticks = match &self.x_categories {
+            Some(_) => x_ticks,
+            None => {
+                let widest = x_ticks
+                    .iter()
+                    .map(|(txt, _)| txt.width())
+                    .max()
+                    .unwrap_or(0);
+                let (canvas_span_x, _) = canvas_limits.span();
+                let spacing = canvas_span_x / NUM_GRID_SECTIONS as f64;
+                skip_crowded(x_ticks, widest as f64, spacing)
+            }
+        };
 
+        let x_labels = |line_style: &LineStyle| {
+            x_ticks
+                .into_iter()
+                .map(|(txt, x)| {
+                    let y = canvas_limits.min().y
+                        - line_style.thickness().convert_to_f64() * 2.
+                        - (txt.height() as f64 / 2.);
                     Label::new(txt, TextPositioning::Centered(Point::new(x, y).floor()))
                 })
                 .collect::<Vec<_>>()
         };
+        let y_ticks = y_starts
+            .map(|(graph_start, canvas_start)| {
+                let txt = Text::from_number(graph_start.y, 3, self.style.clone());
+                (txt, canvas_start)
+            })
+            .collect::<Vec<_>>();
+        let tallest = y_ticks
+            .iter()
+            .map(|(txt, _)| txt.height())
+            .max()
+            .unwrap_or(0);
+        let (_, canvas_span_y) = canvas_limits.span();
+        let y_ticks = skip_crowded(
+            y_ticks,
+            tallest as f64,
+            canvas_span_y / NUM_GRID_SECTIONS as f64,
+        );
         let y_labels = |line_style: &LineStyle| {
-            y_starts
-                .map(|(graph_start, canvas_start)| {
-                    let txt = Text::from_number(graph_start.y, 3, self.style.clone());
+            y_ticks
+                .into_iter()
+                .map(|(txt, canvas_start)| {
                     let x = canvas_start.x
                         - line_style.thickness().convert_to_f64() * 2.
                         - (txt.width() as f64 / 2.);
@@ -115,13 +252,55 @@ impl Axes {
                 x_lab.into_iter().chain(y_lab).collect::<Vec<_>>()
             }
         };
-        Ok(labels)
+        let titles = self.get_titles(&canvas_limits, &labels);
+        Ok(labels.into_iter().chain(titles).collect())
     }
 
-    pub fn get_mask<T: FloatConvertable + Graphable>(
+    /// Gets the titles of the axes, placed outside the tick labels.
+    fn get_titles(&self, canvas_limits: &Limits<f64>, tick_labels: &[Label]) -> Vec<Label> {
+        let min = canvas_limits.min().floor();
+        let center = Point::new(
+            (canvas_limits.min().x + canvas_limits.max().x) / 2.,
+            (canvas_limits.min().y + canvas_limits.max().y) / 2.,
+        )
+        .floor();
+        let gap = LABEL_GAP as u32;
+
+        // x tick labels are the ones below the graph area, and y tick labels the ones left of it
+        let below = tick_labels
+            .iter()
+            .map(|label| label.limits())
+            .filter(|limits| limits.max().y <= min.y)
+            .map(|limits| limits.min().y)
+            .min()
+            .unwrap_or(min.y);
+        let left = tick_labels
+            .iter()
+            .map(|label| label.limits())
+            .filter(|limits| limits.max().x <= min.x && limits.max().y > min.y)
+            .map(|limits| limits.min().x)
+            .min()
+            .unwrap_or(min.x);
+
+        let mut titles = Vec::new();
+        if let Some(title) = &self.x_title {
+            let txt = Text::new(title, self.style.clone());
+            let point = Point::new(center.x, below.saturating_sub(gap));
+            titles.push(Label::new(txt, TextPositioning::Below(point)));
+        }
+        if let Some(title) = &self.y_title {
+            let txt = Text::new(title, self.style.clone()).rotated();
+            let point = Point::new(left.saturating_sub(gap), center.y);
+            titles.push(Label::new(txt, TextPositioning::RightAligned(point)));
+        }
+        titles
+    }
+
+    pub fn draw<T: FloatConvertable + Graphable, S: PixelSink + ?Sized>(
         &self,
         limits: &Limits<T>,
-    ) -> Result<Vec<MaskPoints>> {
+        sink: &mut S,
+    ) -> Result<()> {
         let limits = limits.convert_to_f64();
         let (limit_span_x, limit_span_y) = limits.span();
 
@@ -135,7 +314,7 @@ impl Axes {
                 );
                 let length = limit_span_x;
                 let pos = LinePositioning::Horizontal { start, length };
-                Line::new(pos, *line_style).get_mask()
+                Line::new(pos, *line_style).draw(sink)
             }
             AxesPositioning::YOnly(line_style) => {
                 let start = Point::new(
@@ -144,7 +323,7 @@ impl Axes {
                 );
                 let length = limit_span_y;
                 let pos = LinePositioning::Vertical { start, length };
-                Line::new(pos, *line_style).get_mask()
+                Line::new(pos, *line_style).draw(sink)
             }
             AxesPositioning::XY(line_style) => {
                 let pos_x = LinePositioning::Horizontal {
@@ -162,10 +341,293 @@ impl Axes {
                     length: limit_span_y,
                 };
 
-                let mut mask = Line::new(pos_x, *line_style).get_mask()?;
-                mask.extend(Line::new(pos_y, *line_style).get_mask()?);
-                Ok(mask)
+                Line::new(pos_x, *line_style).draw(sink)?;
+                Line::new(pos_y, *line_style).draw(sink)
             }
         }
     }
+
+    pub fn get_mask<T: FloatConvertable + Graphable>(
+        &self,
+        limits: &Limits<T>,
+    ) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(limits, &mut masks)?;
+        Ok(masks)
+    }
+}
+
+impl SecondaryYAxis {
+    pub fn new(line_style: LineStyle, style: TextStyle) -> SecondaryYAxis {
+        SecondaryYAxis { line_style, style }
+    }
+
+    pub fn line_style(&self) -> &LineStyle {
+        &self.line_style
+    }
+
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    fn tick_texts<T: FloatConvertable + Graphable>(&self, value_limits: &Limits<T>) -> Vec<Text> {
+        let (_, y_starts) = value_limits.convert_to_f64().chunk(NUM_GRID_SECTIONS);
+        y_starts
+            .iter()
+            .map(|start| Text::from_number(start.y, 3, self.style.clone()))
+            .collect()
+    }
+
+    /// Gets the horizontal space (in pixels) needed to the right of the graph area to draw the
+    /// axis and its labels.
+    pub fn reserved_width<T: FloatConvertable + Graphable>(&self, value_limits: &Limits<T>) -> u32 {
+        let max_label_width = self
+            .tick_texts(value_limits)
+            .iter()
+            .map(|txt| txt.width() as u32)
+            .max()
+            .unwrap_or(0);
+        2 * self.line_style.thickness() + max_label_width + 1
+    }
+
+    /// Generates the tick labels along the right edge of the canvas limits. The value limits
+    /// define the numbers shown at the bottom and top of the axis.
+    pub fn get_labels<T: FloatConvertable + Graphable>(
+        &self,
+        canvas_limits: &Limits<T>,
+        value_limits: &Limits<T>,
+    ) -> Result<Vec<Label>> {
+        let canvas_limits = canvas_limits.convert_to_f64();
+        let (_, y_starts) = canvas_limits.chunk(NUM_GRID_SECTIONS);
+        let ticks = self
+            .tick_texts(value_limits)
+            .into_iter()
+            .zip(y_starts)
+            .collect::<Vec<_>>();
+        let tallest = ticks.iter().map(|(txt, _)| txt.height()).max().unwrap_or(0);
+        let (_, canvas_span_y) = canvas_limits.span();
+        let spacing = canvas_span_y / NUM_GRID_SECTIONS as f64;
+        let labels = skip_crowded(ticks, tallest as f64, spacing)
+            .into_iter()
+            .map(|(txt, start)| {
+                let x = canvas_limits.max().x
+                    + self.line_style.thickness().convert_to_f64() * 2.
+                    + (txt.width() as f64 / 2.);
+                Label::new(
+                    txt,
+                    TextPositioning::Centered(Point::new(x, start.y).ceil()),
+                )
+            })
+            .collect();
+        Ok(labels)
+    }
+
+    pub fn draw<T: FloatConvertable + Graphable, S: PixelSink + ?Sized>(
+        &self,
+        canvas_limits: &Limits<T>,
+        sink: &mut S,
+    ) -> Result<()> {
+        let limits = canvas_limits.convert_to_f64();
+        let (_, limit_span_y) = limits.span();
+        let start = Point::new(
+            limits.max().x + self.line_style.thickness().convert_to_f64(),
+            limits.min().y,
+        );
+        let pos = LinePositioning::Vertical {
+            start,
+            length: limit_span_y,
+        };
+        Line::new(pos, self.line_style).draw(sink)
+    }
+
+    pub fn get_mask<T: FloatConvertable + Graphable>(
+        &self,
+        canvas_limits: &Limits<T>,
+    ) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(canvas_limits, &mut masks)?;
+        Ok(masks)
+    }
+}
+
+/// Keeps every n-th of a row of evenly spaced ticks, with n as small as possible such that labels
+/// of the given size (along the row) do not run into each other. Where possible, n evenly divides
+/// the gaps between ticks so that the first and last ticks are both kept.
+fn skip_crowded<T>(ticks: Vec<T>, label_size: f64, spacing: f64) -> Vec<T> {
+    let last = ticks.len().saturating_sub(1);
+    if last == 0 || label_size + LABEL_GAP <= spacing {
+        return ticks;
+    }
+    let min_step = match spacing > 0. {
+        true => ((label_size + LABEL_GAP) / spacing).ceil() as usize,
+        false => last,
+    };
+    let step = (min_step..=last)
+        .find(|step| last.is_multiple_of(*step))
+        .unwrap_or(min_step);
+    ticks.into_iter().step_by(step).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn category_labels(categories: &[&str], canvas_width: f64) -> Vec<Label> {
+        let axes = Axes::new(
+            AxesPositioning::XOnly(LineStyle::default()),
+            TextStyle::default(),
+        )
+        .with_x_categories(categories);
+        let last = (categories.len() - 1) as f64;
+        let graph_limits = Limits::new(Point::new(-0.5, 0.), Point::new(last + 0.5, 10.));
+        let canvas_limits = Limits::new(
+            Point::new(100., 100.),
+            Point::new(100. + canvas_width, 200.),
+        );
+        axes.get_labels(&canvas_limits, &graph_limits).unwrap()
+    }
+
+    #[test]
+    fn category_labels_are_centered_on_positions() {
+        let labels = category_labels(&["a", "b"], 200.);
+        let xs = labels.iter().map(|l| l.pos().point().x).collect::<Vec<_>>();
+        assert_eq!(xs, vec![150, 250]);
+        assert!(labels.iter().all(|l| !l.txt().is_rotated()));
+    }
+
+    #[test]
+    fn secondary_axis_labels_sit_right_of_the_graph() {
+        let axis = SecondaryYAxis::new(LineStyle::default_with_thickness(1), TextStyle::default());
+        let canvas_limits = Limits::new(Point::new(10., 10.), Point::new(110., 210.));
+        let value_limits = Limits::new(Point::new(0., 0.), Point::new(1., 500.));
+        let labels = axis.get_labels(&canvas_limits, &value_limits).unwrap();
+
+        assert_eq!(labels.len(), NUM_GRID_SECTIONS as usize + 1);
+        assert_eq!(labels.first().unwrap().pos().point().y, 10);
+        assert_eq!(labels.last().unwrap().pos().point().y, 210);
+        let right_edge = 110 + axis.reserved_width(&value_limits);
+        for label in labels {
+            assert!(label.limits().min().x > 110);
+            assert!(label.limits().max().x <= right_edge);
+        }
+    }
+
+    fn numeric_labels(canvas_size: f64, style: TextStyle) -> Vec<Label> {
+        let axes = Axes::new(AxesPositioning::XY(LineStyle::default()), style);
+        let graph_limits = Limits::new(Point::new(0., 0.), Point::new(1000., 1000.));
+        let canvas_limits = Limits::new(
+            Point::new(100., 100.),
+            Point::new(100. + canvas_size, 100. + canvas_size),
+        );
+        axes.get_labels(&canvas_limits, &graph_limits).unwrap()
+    }
+
+    #[test]
+    fn roomy_numeric_labels_are_all_kept() {
+        let labels = numeric_labels(1000., TextStyle::default());
+        assert_eq!(labels.len(), 2 * (NUM_GRID_SECTIONS as usize + 1));
+    }
+
+    #[test]
+    fn crowded_numeric_labels_are_skipped() {
+        let labels = numeric_labels(300., TextStyle::new(colors::WHITE, 2, 1));
+        assert!(labels.len() < 2 * (NUM_GRID_SECTIONS as usize + 1));
+
+        // neither the x nor the y labels overlap, and both ends of each axis are labelled
+        let (x_labels, y_labels): (Vec<_>, Vec<_>) =
+            labels.iter().partition(|l| l.pos().point().y < 100);
+        for labels in [&x_labels, &y_labels] {
+            for pair in labels.windows(2) {
+                assert!(!pair[0].limits().intersects(pair[1].limits()));
+            }
+        }
+        let xs = x_labels
+            .iter()
+            .map(|l| l.pos().point().x)
+            .collect::<Vec<_>>();
+        assert!(xs.first().unwrap().abs_diff(100) <= 20);
+        assert!(xs.last().unwrap().abs_diff(400) <= 20);
+        let ys = y_labels
+            .iter()
+            .map(|l| l.pos().point().y)
+            .collect::<Vec<_>>();
+        assert_eq!((ys[0], *ys.last().unwrap()), (100, 400));
+    }
+
+    #[test]
+    fn skip_crowded_prefers_steps_that_keep_both_ends() {
+        let ticks = (0..=10).collect::<Vec<_>>();
+        assert_eq!(skip_crowded(ticks.clone(), 6., 10.), ticks);
+        assert_eq!(
+            skip_crowded(ticks.clone(), 12., 10.),
+            vec![0, 2, 4, 6, 8, 10]
+        );
+        // a step of 3 would be enough, but 5 also labels the last tick
+        assert_eq!(skip_crowded(ticks.clone(), 22., 10.), vec![0, 5, 10]);
+        assert_eq!(skip_crowded(ticks, 200., 10.), vec![0]);
+    }
+
+    #[test]
+    fn crowded_secondary_axis_labels_are_skipped() {
+        let axis = SecondaryYAxis::new(LineStyle::default(), TextStyle::default());
+        let canvas_limits = Limits::new(Point::new(10., 10.), Point::new(110., 110.));
+        let value_limits = Limits::new(Point::new(0., 0.), Point::new(1., 500.));
+        let labels = axis.get_labels(&canvas_limits, &value_limits).unwrap();
+        let ys = labels.iter().map(|l| l.pos().point().y).collect::<Vec<_>>();
+        assert_eq!(ys, vec![10, 30, 50, 70, 90, 110]);
+    }
+
+    #[test]
+    fn crowded_category_labels_are_rotated() {
+        let labels = category_labels(&["monday", "tuesday", "wednesday"], 120.);
+        assert!(labels.iter().all(|l| l.txt().is_rotated()));
+        // rotated labels hang below the axis
+        assert!(labels.iter().all(|l| l.limits().max().y <= 100));
+    }
+
+    #[test]
+    fn long_category_labels_are_slanted_unless_very_crowded() {
+        let names = ["monday", "tuesday", "wednesday"];
+        let slanted = category_labels(&names, 120.);
+        assert!(
+            slanted
+                .iter()
+                .all(|l| l.txt().rotation() == SLANTED_LABEL_DEGREES)
+        );
+        // the slanted labels end at their category
+        let xs = slanted
+            .iter()
+            .map(|l| l.limits().max().x)
+            .collect::<Vec<_>>();
+        assert_eq!(xs, vec![120, 160, 200]);
+
+        let vertical = category_labels(&names, 45.);
+        assert!(vertical.iter().all(|l| l.txt().rotation() == 90.));
+    }
+
+    #[test]
+    fn titles_sit_outside_tick_labels() {
+        let axes = Axes::new(
+            AxesPositioning::XY(LineStyle::default()),
+            TextStyle::default(),
+        )
+        .with_x_title("time")
+        .with_y_title("requests");
+        let graph_limits = Limits::new(Point::new(0., 0.), Point::new(10., 10.));
+        let canvas_limits = Limits::new(Point::new(100., 100.), Point::new(300., 300.));
+        let labels = axes.get_labels(&canvas_limits, &graph_limits).unwrap();
+        let (ticks, titles) = labels.split_at(labels.len() - 2);
+
+        let x_title = titles[0].limits();
+        let y_title = titles[1].limits();
+        assert!(!titles[0].txt().is_rotated() && titles[1].txt().is_rotated());
+        assert_eq!(x_title.min().x + x_title.max().x, 400);
+        assert_eq!(y_title.min().y + y_title.max().y, 400);
+        for tick in ticks {
+            assert!(!tick.limits().intersects(x_title.clone()));
+            assert!(!tick.limits().intersects(y_title.clone()));
+        }
+        assert!(x_title.max().y < 100 && y_title.max().x < 100);
+    }
 }
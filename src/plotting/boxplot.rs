@@ -0,0 +1,685 @@
+use super::{
+    categories::CATEGORY_PADDING,
+    colors,
+    common::{
+        Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable, magnify,
+    },
+    limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    marker::{Marker, MarkerStyle},
+    point::{Point, PointCollection},
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+const WHISKER_IQR_FACTOR: f64 = 1.5;
+const BOX_HALF_WIDTH: f64 = 0.3;
+const VIOLIN_HALF_WIDTH: f64 = 0.4;
+const NUM_VIOLIN_POINTS: usize = 40;
+
+/// Summary statistics for a group of samples. Quartiles are linearly interpolated between the
+/// closest ranks; whiskers extend to the furthest samples within 1.5 IQR of the box, and any
+/// samples beyond the whiskers are outliers.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoxStats {
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub whisker_low: f64,
+    pub whisker_high: f64,
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Computes the statistics for a set of samples. Non-finite samples are ignored.
+    pub fn from_samples(samples: &[f64]) -> BoxStats {
+        let sorted = sorted_samples(samples);
+        if sorted.is_empty() {
+            panic!("Box plot samples cannot be empty");
+        }
+
+        let q1 = quantile(&sorted, 0.25);
+        let median = quantile(&sorted, 0.5);
+        let q3 = quantile(&sorted, 0.75);
+
+        let iqr = q3 - q1;
+        let low_fence = q1 - WHISKER_IQR_FACTOR * iqr;
+        let high_fence = q3 + WHISKER_IQR_FACTOR * iqr;
+
+        let inliers = sorted
+            .iter()
+            .filter(|&&v| (low_fence..=high_fence).contains(&v))
+            .collect::<Vec<_>>();
+        let whisker_low = **inliers.first().unwrap_or(&&q1);
+        let whisker_high = **inliers.last().unwrap_or(&&q3);
+
+        let outliers = sorted
+            .iter()
+            .filter(|&&v| !(low_fence..=high_fence).contains(&v))
+            .copied()
+            .collect();
+
+        BoxStats {
+            q1,
+            median,
+            q3,
+            whisker_low,
+            whisker_high,
+            outliers,
+        }
+    }
+
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+}
+
+fn sorted_samples(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect::<Vec<_>>();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+/// Gets the quantile of already sorted data, interpolating linearly between ranks.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = usize::min(lower + 1, sorted.len() - 1);
+    let frac = pos - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * frac
+}
+
+/// Estimates the density of the samples using a gaussian kernel with Silverman's rule of thumb
+/// for the bandwidth. The density is evaluated at evenly spaced points between the smallest and
+/// largest sample and returned as (value, density) pairs.
+fn kernel_density(sorted: &[f64]) -> Vec<(f64, f64)> {
+    let n = sorted.len() as f64;
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    if min == max {
+        return vec![(min, 1.)];
+    }
+
+    let mean = sorted.iter().sum::<f64>() / n;
+    let std_dev = (sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+    let spread = if iqr > 0. {
+        f64::min(std_dev, iqr / 1.34)
+    } else {
+        std_dev
+    };
+    let bandwidth = 0.9 * spread * n.powf(-0.2);
+
+    let step = (max - min) / (NUM_VIOLIN_POINTS - 1) as f64;
+    (0..NUM_VIOLIN_POINTS)
+        .map(|i| {
+            let y = min + step * i as f64;
+            let density = sorted
+                .iter()
+                .map(|v| (-0.5 * ((y - v) / bandwidth).powi(2)).exp())
+                .sum::<f64>();
+            (y, density)
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BoxPlotKind {
+    Box,
+    Violin,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoxPlotStyle {
+    line: LineStyle,
+    fill: RGB8,
+    outlier: MarkerStyle,
+    // thickness of the hairlines inside violins and around hollow outliers
+    stroke: u32,
+}
+
+impl BoxPlotStyle {
+    pub fn new(line: LineStyle, fill: RGB8, outlier: MarkerStyle) -> BoxPlotStyle {
+        BoxPlotStyle {
+            line,
+            fill,
+            outlier,
+            stroke: 0,
+        }
+    }
+
+    pub fn line(&self) -> &LineStyle {
+        &self.line
+    }
+
+    pub fn fill(&self) -> RGB8 {
+        self.fill
+    }
+
+    pub fn outlier(&self) -> &MarkerStyle {
+        &self.outlier
+    }
+
+    /// Gets the style for drawing at `factor` times the resolution, with lines, hairlines and
+    /// outlier markers scaled to cover about the same area.
+    pub(crate) fn magnified(&self, factor: u32) -> BoxPlotStyle {
+        BoxPlotStyle {
+            line: self.line.magnified(factor),
+            fill: self.fill,
+            outlier: self.outlier.magnified(factor),
+            stroke: magnify(self.stroke, factor),
+        }
+    }
+}
+
+impl Default for BoxPlotStyle {
+    fn default() -> BoxPlotStyle {
+        BoxPlotStyle {
+            line: LineStyle::default(),
+            fill: colors::STEEL_BLUE,
+            outlier: MarkerStyle::HollowCircle {
+                size: 2,
+                color: colors::WHITE,
+            },
+            stroke: 0,
+        }
+    }
+}
+
+/// Geometry for a single group of a box plot. All values are stored as points so that the group
+/// can be converted, shifted, and scaled along with the rest of the graph; the center of the
+/// group is the x value of the median point.
+#[derive(Debug, Clone)]
+pub struct BoxGroup<T: Graphable> {
+    name: String,
+    box_min: Point<T>,
+    box_max: Point<T>,
+    median: Point<T>,
+    whisker_low: Point<T>,
+    whisker_high: Point<T>,
+    outliers: Vec<Point<T>>,
+    // right-hand edge of the violin; the left-hand edge is mirrored around the center
+    outline: Vec<Point<T>>,
+}
+
+impl<T: Graphable> BoxGroup<T> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn median(&self) -> &Point<T> {
+        &self.median
+    }
+
+    pub fn outliers(&self) -> &[Point<T>] {
+        &self.outliers
+    }
+
+    fn map_points<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> BoxGroup<U> {
+        BoxGroup {
+            name: self.name.clone(),
+            box_min: map_fn(self.box_min),
+            box_max: map_fn(self.box_max),
+            median: map_fn(self.median),
+            whisker_low: map_fn(self.whisker_low),
+            whisker_high: map_fn(self.whisker_high),
+            outliers: self.outliers.iter().map(|&p| map_fn(p)).collect(),
+            outline: self.outline.iter().map(|&p| map_fn(p)).collect(),
+        }
+    }
+}
+
+impl BoxGroup<f64> {
+    fn new(name: &str, center: f64, samples: &[f64], kind: BoxPlotKind) -> BoxGroup<f64> {
+        let stats = BoxStats::from_samples(samples);
+
+        let outline = match kind {
+            BoxPlotKind::Box => Vec::new(),
+            BoxPlotKind::Violin => {
+                let density = kernel_density(&sorted_samples(samples));
+                let max_density = density.iter().map(|(_, d)| *d).fold(0., f64::max);
+                density
+                    .into_iter()
+                    .map(|(y, d)| Point::new(center + VIOLIN_HALF_WIDTH * d / max_density, y))
+                    .collect()
+            }
+        };
+
+        BoxGroup {
+            name: name.to_string(),
+            box_min: Point::new(center - BOX_HALF_WIDTH, stats.q1),
+            box_max: Point::new(center + BOX_HALF_WIDTH, stats.q3),
+            median: Point::new(center, stats.median),
+            whisker_low: Point::new(center, stats.whisker_low),
+            whisker_high: Point::new(center, stats.whisker_high),
+            outliers: stats
+                .outliers
+                .iter()
+                .map(|&y| Point::new(center, y))
+                .collect(),
+            outline,
+        }
+    }
+
+    fn draw<S: PixelSink + ?Sized>(
+        &self,
+        kind: BoxPlotKind,
+        style: &BoxPlotStyle,
+        sink: &mut S,
+    ) -> Result<()> {
+        let line_style = *style.line();
+        let line_color = line_style.color();
+        let center = self.median.x;
+
+        match kind {
+            BoxPlotKind::Box => {
+                let box_min = self.box_min.round();
+                let box_max = self.box_max.round();
+                let (width, height) = Limits::new(box_min, box_max).span();
+
+                sink.set_rect(&box_min, &box_max, &style.fill());
+
+                let edges = [
+                    LinePositioning::Horizontal {
+                        start: box_min,
+                        length: width,
+                    },
+                    LinePositioning::Horizontal {
+                        start: Point::new(box_min.x, box_max.y),
+                        length: width,
+                    },
+                    LinePositioning::Vertical {
+                        start: box_min,
+                        length: height,
+                    },
+                    LinePositioning::Vertical {
+                        start: Point::new(box_max.x, box_min.y),
+                        length: height,
+                    },
+                ];
+                for pos in edges {
+                    Line::new(pos, line_style).draw(sink)?;
+                }
+
+                // the median is drawn thicker than the box so it stands out
+                let median_style = LineStyle::Solid {
+                    color: line_color,
+                    thickness: line_style.thickness() + 1,
+                };
+                let median_pos = LinePositioning::Horizontal {
+                    start: Point::new(box_min.x, self.median.round().y),
+                    length: width,
+                };
+                Line::new(median_pos, median_style).draw(sink)?;
+
+                // whisker caps are half as wide as the box
+                let cap_half_width = (self.box_max.x - center) / 2.;
+                for whisker in [self.whisker_low, self.whisker_high] {
+                    let start = Point::new(center - cap_half_width, whisker.y).round();
+                    let end = Point::new(center + cap_half_width, whisker.y).round();
+                    let pos = LinePositioning::Horizontal {
+                        start,
+                        length: end.x - start.x,
+                    };
+                    Line::new(pos, line_style).draw(sink)?;
+                }
+
+                let whiskers = [
+                    (self.whisker_low, Point::new(center, self.box_min.y)),
+                    (Point::new(center, self.box_max.y), self.whisker_high),
+                ];
+                for (start, end) in whiskers {
+                    let (start, end) = (start.round(), end.round());
+                    let pos = LinePositioning::Vertical {
+                        start,
+                        length: end.y.saturating_sub(start.y),
+                    };
+                    Line::new(pos, line_style).draw(sink)?;
+                }
+            }
+            BoxPlotKind::Violin => {
+                self.draw_violin_fill(&style.fill(), sink);
+
+                // outline both sides of the body
+                for pair in self.outline.windows(2) {
+                    let (right_a, right_b) = (pair[0], pair[1]);
+                    let left_a = Point::new(2. * center - right_a.x, right_a.y);
+                    let left_b = Point::new(2. * center - right_b.x, right_b.y);
+                    for (start, end) in [(right_a, right_b), (left_a, left_b)] {
+                        let pos = LinePositioning::BetweenPoints {
+                            start: start.round(),
+                            end: end.round(),
+                        };
+                        Line::new(pos, line_style).draw(sink)?;
+                    }
+                }
+
+                // a thin box inside the body shows the quartiles and whiskers
+                let whisker_start = self.whisker_low.round();
+                let whisker_end = self.whisker_high.round();
+                let whisker_pos = LinePositioning::Vertical {
+                    start: whisker_start,
+                    length: whisker_end.y.saturating_sub(whisker_start.y),
+                };
+                let whisker_style = LineStyle::Solid {
+                    color: line_color,
+                    thickness: style.stroke,
+                };
+                Line::new(whisker_pos, whisker_style).draw(sink)?;
+
+                let inner_half_width = u32::max(1, ((self.box_max.x - center) / 6.).round() as u32);
+                let center_px = self.median.round().x;
+                let iqr_min = Point::new(
+                    center_px.saturating_sub(inner_half_width),
+                    self.box_min.round().y,
+                );
+                let iqr_max = Point::new(center_px + inner_half_width, self.box_max.round().y);
+                sink.set_rect(&iqr_min, &iqr_max, &line_color);
+
+                let median_marker = MarkerStyle::FilledSquare {
+                    size: inner_half_width.saturating_sub(1),
+                    color: style.fill(),
+                };
+                Marker::new(self.median.round(), median_marker).draw(sink)?;
+            }
+        }
+
+        for outlier in &self.outliers {
+            let marker = Marker::new(outlier.round(), style.outlier().clone());
+            marker.with_stroke(style.stroke).draw(sink)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills the violin body by drawing each pixel row between the mirrored edges, interpolating
+    /// the edge between outline points.
+    fn draw_violin_fill<S: PixelSink + ?Sized>(&self, color: &RGB8, sink: &mut S) {
+        let center = self.median.x;
+        let mut fill_row = |y: u32, right_x: f64| {
+            let left = Point::new(2. * center - right_x, y as f64);
+            let right = Point::new(right_x, y as f64);
+            sink.set_rect(&left.round(), &right.round(), color);
+        };
+
+        if self.outline.len() == 1 {
+            let p = self.outline[0];
+            fill_row(p.round().y, p.x);
+            return;
+        }
+
+        for pair in self.outline.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (y_start, y_end) = (a.round().y, b.round().y);
+            for y in y_start..=y_end {
+                let frac = if b.y == a.y {
+                    0.
+                } else {
+                    ((y as f64 - a.y) / (b.y - a.y)).clamp(0., 1.)
+                };
+                fill_row(y, a.x + (b.x - a.x) * frac);
+            }
+        }
+    }
+}
+
+/// Box (or violin) plots for one or more named groups of samples. Groups are placed at
+/// consecutive integer x values starting at zero, so they line up with a categorical x axis
+/// using the group names.
+#[derive(Debug, Clone)]
+pub struct BoxPlot<T: Graphable> {
+    groups: Vec<BoxGroup<T>>,
+    kind: BoxPlotKind,
+    style: BoxPlotStyle,
+    limits: Limits<T>,
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for BoxPlot<T> {
+    type ConvertTo = BoxPlot<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        BoxPlot {
+            groups: self
+                .groups
+                .iter()
+                .map(|group| group.map_points(|p| p.convert_to(convert_fn)))
+                .collect(),
+            kind: self.kind,
+            style: self.style.clone(),
+            limits: self.limits.convert_to(convert_fn),
+        }
+    }
+}
+
+impl BoxPlot<f64> {
+    /// Creates a plot from named groups of samples. Panics if there are no groups or if any group
+    /// has no finite samples.
+    pub fn new<S: AsRef<str>>(groups: &[(S, Vec<f64>)], kind: BoxPlotKind) -> BoxPlot<f64> {
+        if groups.is_empty() {
+            panic!("Box plot must have at least one group");
+        }
+
+        let groups = groups
+            .iter()
+            .enumerate()
+            .map(|(i, (name, samples))| BoxGroup::new(name.as_ref(), i as f64, samples, kind))
+            .collect::<Vec<_>>();
+
+        // pad the x range so the outer groups are not drawn on the edges of the graph
+        let y_values = groups
+            .iter()
+            .flat_map(|g| {
+                [g.whisker_low, g.whisker_high]
+                    .into_iter()
+                    .chain(g.outliers.clone())
+            })
+            .collect::<Vec<_>>();
+        let y_limits = y_values.limits().unwrap();
+        let min = Point::new(-CATEGORY_PADDING, y_limits.min().y);
+        let max = Point::new(
+            (groups.len() - 1) as f64 + CATEGORY_PADDING,
+            y_limits.max().y,
+        );
+
+        BoxPlot {
+            groups,
+            kind,
+            style: BoxPlotStyle::default(),
+            limits: Limits::new(min, max),
+        }
+    }
+}
+
+impl<T: Graphable> BoxPlot<T> {
+    pub fn with_style(mut self, style: BoxPlotStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn groups(&self) -> &[BoxGroup<T>] {
+        &self.groups
+    }
+
+    pub fn kind(&self) -> BoxPlotKind {
+        self.kind
+    }
+
+    pub fn style(&self) -> &BoxPlotStyle {
+        &self.style
+    }
+
+    /// Gets the plot for drawing at `factor` times the resolution; see
+    /// [`BoxPlotStyle::magnified`].
+    pub(crate) fn magnified(&self, factor: u32) -> BoxPlot<T> {
+        let mut plot = self.clone();
+        plot.style = self.style.magnified(factor);
+        plot
+    }
+
+    pub fn limits(&self) -> &Limits<T> {
+        &self.limits
+    }
+
+    /// Gets the group names in x order, for use as axis categories.
+    pub fn categories(&self) -> Vec<String> {
+        self.groups.iter().map(|g| g.name.clone()).collect()
+    }
+
+    pub fn max_marker_size(&self) -> u32 {
+        match self.groups.iter().any(|g| !g.outliers.is_empty()) {
+            true => self.style.outlier.size(),
+            false => 0,
+        }
+    }
+}
+
+impl<T: Graphable> Drawable for BoxPlot<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let plot = self.convert_to_f64();
+        for group in &plot.groups {
+            group.draw(plot.kind, &plot.style, sink)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, U> Scalable<T, U> for BoxPlot<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = BoxPlot<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        BoxPlot {
+            groups: self
+                .groups
+                .iter()
+                .map(|group| group.map_points(|p| p.scale_to(old_limits, new_limits)))
+                .collect(),
+            kind: self.kind,
+            style: self.style.clone(),
+            limits: self.limits.scale_to(old_limits, new_limits),
+        }
+    }
+}
+
+impl<T> Shiftable<T> for BoxPlot<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(mut self, amount: Point<T>) -> Self {
+        self.groups = self
+            .groups
+            .iter()
+            .map(|group| group.map_points(|p| p + amount))
+            .collect();
+        self.limits = self.limits.shift_by(amount);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_for_odd_number_of_samples() {
+        let stats = BoxStats::from_samples(&[5., 1., 3., 2., 4.]);
+        assert_eq!(stats.q1, 2.);
+        assert_eq!(stats.median, 3.);
+        assert_eq!(stats.q3, 4.);
+        assert_eq!(stats.whisker_low, 1.);
+        assert_eq!(stats.whisker_high, 5.);
+        assert!(stats.outliers.is_empty());
+    }
+
+    #[test]
+    fn stats_interpolate_quartiles() {
+        let stats = BoxStats::from_samples(&[1., 2., 3., 4.]);
+        assert_eq!(stats.q1, 1.75);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.q3, 3.25);
+    }
+
+    #[test]
+    fn samples_beyond_whiskers_are_outliers() {
+        let stats = BoxStats::from_samples(&[-50., 1., 2., 3., 4., 5., 6., 7., 8., 100.]);
+        assert_eq!(stats.outliers, vec![-50., 100.]);
+        assert_eq!(stats.whisker_low, 1.);
+        assert_eq!(stats.whisker_high, 8.);
+    }
+
+    #[test]
+    fn stats_ignore_non_finite_samples() {
+        let stats = BoxStats::from_samples(&[f64::NAN, 1., 2., 3., f64::INFINITY]);
+        assert_eq!(stats.median, 2.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_samples_panic() {
+        BoxStats::from_samples(&[]);
+    }
+
+    #[test]
+    fn groups_are_placed_at_consecutive_x_values() {
+        let plot = BoxPlot::new(
+            &[("a", vec![1., 2., 3.]), ("b", vec![4., 5., 6.])],
+            BoxPlotKind::Box,
+        );
+        assert_eq!(plot.categories(), vec!["a", "b"]);
+        assert_eq!(plot.groups()[0].median().x, 0.);
+        assert_eq!(plot.groups()[1].median().x, 1.);
+        assert_eq!(
+            plot.limits(),
+            &Limits::new(Point::new(-0.5, 1.), Point::new(1.5, 6.))
+        );
+    }
+
+    #[test]
+    fn limits_include_outliers() {
+        let plot = BoxPlot::new(
+            &[("a", vec![1., 2., 3., 4., 5., 6., 7., 8., 100.])],
+            BoxPlotKind::Box,
+        );
+        assert_eq!(plot.limits().max().y, 100.);
+        assert_eq!(plot.max_marker_size(), plot.style().outlier().size());
+    }
+
+    #[test]
+    fn violin_outline_is_widest_at_mode() {
+        let plot = BoxPlot::new(
+            &[("a", vec![0., 4.9, 5., 5., 5.1, 10.])],
+            BoxPlotKind::Violin,
+        );
+        let outline = &plot.groups()[0].outline;
+        assert_eq!(outline.len(), NUM_VIOLIN_POINTS);
+        let widest = outline.iter().max_by(|a, b| a.x.total_cmp(&b.x)).unwrap();
+        assert!((widest.y - 5.).abs() < 0.5);
+        assert!((widest.x - VIOLIN_HALF_WIDTH).abs() < 1e-9);
+    }
+
+    #[test]
+    fn box_and_violin_draw_in_pixel_space() {
+        let groups = [("a", vec![1., 2., 3., 4., 20.]), ("b", vec![2., 3., 4.])];
+        for kind in [BoxPlotKind::Box, BoxPlotKind::Violin] {
+            let plot = BoxPlot::new(&groups, kind);
+            let old_limits = plot.limits().clone();
+            let new_limits = Limits::new(Point::new(10., 10.), Point::new(110., 110.));
+            let scaled = plot
+                .shift_by(*old_limits.min() * -1.)
+                .scale_to(&old_limits, &new_limits)
+                .shift_by(*new_limits.min());
+
+            let mask = scaled.get_mask().unwrap();
+            assert!(!mask.is_empty());
+            for p in mask.iter().flat_map(|m| &m.points) {
+                assert!(p.x >= 8 && p.x <= 112, "{p:?} outside graph area");
+                assert!(p.y >= 8 && p.y <= 112, "{p:?} outside graph area");
+            }
+        }
+    }
+}
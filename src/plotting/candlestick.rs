@@ -0,0 +1,489 @@
+use super::{
+    colors,
+    common::{
+        Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable, magnify,
+    },
+    limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    point::{Point, PointCollection},
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+// fraction of the smallest gap between consecutive times covered by half a candle body
+const BODY_HALF_WIDTH: f64 = 0.35;
+
+/// Open, high, low and close prices (and optionally the traded volume) for one time period.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ohlc {
+    pub time: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+}
+
+impl Ohlc {
+    pub fn new(time: f64, open: f64, high: f64, low: f64, close: f64) -> Ohlc {
+        Ohlc {
+            time,
+            open,
+            high,
+            low,
+            close,
+            volume: None,
+        }
+    }
+
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Checks that every value is finite, the high and low bound the open and close, and the
+    /// volume (if any) is not negative.
+    pub fn validate(&self) -> Result<()> {
+        let values = [self.time, self.open, self.high, self.low, self.close];
+        if values
+            .iter()
+            .chain(self.volume.iter())
+            .any(|v| !v.is_finite())
+        {
+            return Err(format!("OHLC values at time {} must be finite", self.time).into());
+        }
+        if self.high < f64::max(self.open, self.close) || self.low > f64::min(self.open, self.close)
+        {
+            return Err(format!(
+                "OHLC high {} and low {} at time {} must bound the open {} and close {}",
+                self.high, self.low, self.time, self.open, self.close
+            )
+            .into());
+        }
+        if self.volume.is_some_and(|v| v < 0.) {
+            return Err(format!("OHLC volume at time {} cannot be negative", self.time).into());
+        }
+        Ok(())
+    }
+
+    pub fn is_rising(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CandlestickKind {
+    /// Filled body between the open and close, with a wick out to the high and low.
+    Candle,
+    /// Vertical bar from the low to the high, with the open ticked on the left and the close
+    /// ticked on the right.
+    Bar,
+}
+
+#[derive(Debug, Clone)]
+pub struct CandlestickStyle {
+    up: RGB8,
+    down: RGB8,
+    wick_thickness: u32,
+}
+
+impl CandlestickStyle {
+    pub fn new(up: RGB8, down: RGB8, wick_thickness: u32) -> CandlestickStyle {
+        CandlestickStyle {
+            up,
+            down,
+            wick_thickness,
+        }
+    }
+
+    pub fn up(&self) -> RGB8 {
+        self.up
+    }
+
+    pub fn down(&self) -> RGB8 {
+        self.down
+    }
+
+    pub fn wick_thickness(&self) -> u32 {
+        self.wick_thickness
+    }
+
+    /// Gets the style for drawing at `factor` times the resolution, with the wick thickness
+    /// scaled to cover about the same width.
+    pub(crate) fn magnified(&self, factor: u32) -> CandlestickStyle {
+        CandlestickStyle {
+            wick_thickness: magnify(self.wick_thickness, factor),
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for CandlestickStyle {
+    fn default() -> CandlestickStyle {
+        CandlestickStyle {
+            up: colors::LIME_GREEN,
+            down: colors::CRIMSON,
+            wick_thickness: 0,
+        }
+    }
+}
+
+/// Geometry for a single period. The body spans the open and close and the wick spans the low
+/// and high; the center of the candle is the x value of the wick points.
+#[derive(Debug, Clone)]
+pub struct Candle<T: Graphable> {
+    body_min: Point<T>,
+    body_max: Point<T>,
+    wick_low: Point<T>,
+    wick_high: Point<T>,
+    rising: bool,
+}
+
+impl<T: Graphable> Candle<T> {
+    pub fn body_min(&self) -> &Point<T> {
+        &self.body_min
+    }
+
+    pub fn body_max(&self) -> &Point<T> {
+        &self.body_max
+    }
+
+    pub fn wick_low(&self) -> &Point<T> {
+        &self.wick_low
+    }
+
+    pub fn wick_high(&self) -> &Point<T> {
+        &self.wick_high
+    }
+
+    pub fn is_rising(&self) -> bool {
+        self.rising
+    }
+
+    fn map_points<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> Candle<U> {
+        Candle {
+            body_min: map_fn(self.body_min),
+            body_max: map_fn(self.body_max),
+            wick_low: map_fn(self.wick_low),
+            wick_high: map_fn(self.wick_high),
+            rising: self.rising,
+        }
+    }
+}
+
+impl Candle<f64> {
+    fn draw<S: PixelSink + ?Sized>(
+        &self,
+        kind: CandlestickKind,
+        style: &CandlestickStyle,
+        sink: &mut S,
+    ) -> Result<()> {
+        let color = match self.rising {
+            true => style.up,
+            false => style.down,
+        };
+        let line_style = LineStyle::Solid {
+            color,
+            thickness: style.wick_thickness,
+        };
+        let body_min = self.body_min.round();
+        let body_max = self.body_max.round();
+
+        let wick_low = self.wick_low.round();
+        let wick_high = self.wick_high.round();
+        if wick_high.y > wick_low.y {
+            let pos = LinePositioning::Vertical {
+                start: wick_low,
+                length: wick_high.y - wick_low.y,
+            };
+            Line::new(pos, line_style).draw(sink)?;
+        }
+
+        match kind {
+            CandlestickKind::Candle => sink.set_rect(&body_min, &body_max, &color),
+            CandlestickKind::Bar => {
+                let (open_y, close_y) = match self.rising {
+                    true => (body_min.y, body_max.y),
+                    false => (body_max.y, body_min.y),
+                };
+                let ticks = [
+                    (Point::new(body_min.x, open_y), wick_low.x - body_min.x),
+                    (Point::new(wick_low.x, close_y), body_max.x - wick_low.x),
+                ];
+                for (start, length) in ticks {
+                    let pos = LinePositioning::Horizontal { start, length };
+                    Line::new(pos, line_style).draw(sink)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Candlestick (or OHLC bar) chart of prices over time, with up periods (close at or above the
+/// open) and down periods drawn in different colors. Bodies are sized from the smallest gap
+/// between consecutive times so that neighbouring candles never overlap.
+#[derive(Debug, Clone)]
+pub struct Candlestick<T: Graphable> {
+    candles: Vec<Candle<T>>,
+    kind: CandlestickKind,
+    style: CandlestickStyle,
+    limits: Limits<T>,
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for Candlestick<T> {
+    type ConvertTo = Candlestick<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        Candlestick {
+            candles: self
+                .candles
+                .iter()
+                .map(|candle| candle.map_points(|p| p.convert_to(convert_fn)))
+                .collect(),
+            kind: self.kind,
+            style: self.style.clone(),
+            limits: self.limits.convert_to(convert_fn),
+        }
+    }
+}
+
+impl Candlestick<f64> {
+    /// Creates a chart from one row per period. Panics if there are no rows or if any row is
+    /// invalid (see [`Ohlc::validate`]).
+    pub fn new(rows: &[Ohlc], kind: CandlestickKind) -> Candlestick<f64> {
+        Self::from_spans(rows, kind, |row| (row.open, row.close, row.low, row.high))
+    }
+
+    /// Creates volume bars for the same rows: bodies rise from zero to each period's volume and
+    /// are colored by whether the price rose. Panics if any row has no volume.
+    pub fn volume(rows: &[Ohlc]) -> Candlestick<f64> {
+        Self::from_spans(rows, CandlestickKind::Candle, |row| {
+            let volume = row
+                .volume
+                .unwrap_or_else(|| panic!("OHLC row at time {} has no volume", row.time));
+            (0., volume, 0., 0.)
+        })
+    }
+
+    fn from_spans<F: Fn(&Ohlc) -> (f64, f64, f64, f64)>(
+        rows: &[Ohlc],
+        kind: CandlestickKind,
+        span_fn: F,
+    ) -> Candlestick<f64> {
+        if rows.is_empty() {
+            panic!("Candlestick chart must have at least one row");
+        }
+        if let Some(err) = rows.iter().find_map(|row| row.validate().err()) {
+            panic!("{err}");
+        }
+
+        let mut times = rows.iter().map(|row| row.time).collect::<Vec<_>>();
+        times.sort_by(f64::total_cmp);
+        let spacing = times
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|gap| *gap > 0.)
+            .fold(f64::INFINITY, f64::min);
+        let spacing = if spacing.is_finite() { spacing } else { 1. };
+        let half_width = BODY_HALF_WIDTH * spacing;
+
+        let candles = rows
+            .iter()
+            .map(|row| {
+                let (open, close, low, high) = span_fn(row);
+                let (wick_low, wick_high) = match low == high {
+                    // no wick; collapse it onto the bottom of the body
+                    true => (f64::min(open, close), f64::min(open, close)),
+                    false => (low, high),
+                };
+                Candle {
+                    body_min: Point::new(row.time - half_width, f64::min(open, close)),
+                    body_max: Point::new(row.time + half_width, f64::max(open, close)),
+                    wick_low: Point::new(row.time, wick_low),
+                    wick_high: Point::new(row.time, wick_high),
+                    rising: row.is_rising(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // pad the x range by half a period so the outer candles are not cut off
+        let y_limits = candles
+            .iter()
+            .flat_map(|c| [c.body_min, c.body_max, c.wick_low, c.wick_high])
+            .collect::<Vec<_>>()
+            .limits()
+            .unwrap();
+        let min = Point::new(times[0] - spacing / 2., y_limits.min().y);
+        let max = Point::new(times[times.len() - 1] + spacing / 2., y_limits.max().y);
+
+        Candlestick {
+            candles,
+            kind,
+            style: CandlestickStyle::default(),
+            limits: Limits::new(min, max),
+        }
+    }
+}
+
+impl<T: Graphable> Candlestick<T> {
+    pub fn with_style(mut self, style: CandlestickStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn candles(&self) -> &[Candle<T>] {
+        &self.candles
+    }
+
+    pub fn kind(&self) -> CandlestickKind {
+        self.kind
+    }
+
+    pub fn style(&self) -> &CandlestickStyle {
+        &self.style
+    }
+
+    /// Gets the chart for drawing at `factor` times the resolution; see
+    /// [`CandlestickStyle::magnified`].
+    pub(crate) fn magnified(&self, factor: u32) -> Candlestick<T> {
+        let mut chart = self.clone();
+        chart.style = self.style.magnified(factor);
+        chart
+    }
+
+    pub fn limits(&self) -> &Limits<T> {
+        &self.limits
+    }
+}
+
+impl<T: Graphable> Drawable for Candlestick<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let chart = self.convert_to_f64();
+        for candle in &chart.candles {
+            candle.draw(chart.kind, &chart.style, sink)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, U> Scalable<T, U> for Candlestick<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = Candlestick<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        Candlestick {
+            candles: self
+                .candles
+                .iter()
+                .map(|candle| candle.map_points(|p| p.scale_to(old_limits, new_limits)))
+                .collect(),
+            kind: self.kind,
+            style: self.style.clone(),
+            limits: self.limits.scale_to(old_limits, new_limits),
+        }
+    }
+}
+
+impl<T> Shiftable<T> for Candlestick<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(mut self, amount: Point<T>) -> Self {
+        self.candles = self
+            .candles
+            .iter()
+            .map(|candle| candle.map_points(|p| p + amount))
+            .collect();
+        self.limits = self.limits.shift_by(amount);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::graph::Graph;
+
+    fn rows() -> Vec<Ohlc> {
+        vec![
+            Ohlc::new(0., 10., 12., 9., 11.).with_volume(100.),
+            Ohlc::new(2., 11., 11.5, 7., 8.).with_volume(300.),
+            Ohlc::new(3., 8., 9., 8., 8.).with_volume(50.),
+        ]
+    }
+
+    #[test]
+    fn invalid_rows_are_rejected() {
+        assert!(Ohlc::new(0., 10., 9., 8., 8.5).validate().is_err());
+        assert!(Ohlc::new(0., 10., 12., 10.5, 11.).validate().is_err());
+        assert!(Ohlc::new(0., f64::NAN, 12., 9., 11.).validate().is_err());
+        assert!(
+            Ohlc::new(0., 10., 12., 9., 11.)
+                .with_volume(-1.)
+                .validate()
+                .is_err()
+        );
+        assert!(rows().iter().all(|row| row.validate().is_ok()));
+    }
+
+    #[test]
+    fn candles_are_sized_by_smallest_gap() {
+        let chart = Candlestick::new(&rows(), CandlestickKind::Candle);
+        let candle = &chart.candles()[0];
+        assert_eq!(candle.body_min(), &Point::new(-0.35, 10.));
+        assert_eq!(candle.body_max(), &Point::new(0.35, 11.));
+        assert_eq!(candle.wick_low(), &Point::new(0., 9.));
+        assert_eq!(candle.wick_high(), &Point::new(0., 12.));
+        assert!(candle.is_rising());
+        assert!(!chart.candles()[1].is_rising());
+
+        assert_eq!(
+            chart.limits(),
+            &Limits::new(Point::new(-0.5, 7.), Point::new(3.5, 12.))
+        );
+    }
+
+    #[test]
+    fn volume_bars_rise_from_zero() {
+        let volume = Candlestick::volume(&rows());
+        let bar = &volume.candles()[1];
+        assert_eq!(bar.body_min().y, 0.);
+        assert_eq!(bar.body_max().y, 300.);
+        assert_eq!(bar.wick_low(), bar.wick_high());
+        assert!(!bar.is_rising());
+        assert_eq!(volume.limits().max().y, 300.);
+    }
+
+    #[test]
+    fn candles_are_colored_by_direction() {
+        let rows = [
+            Ohlc::new(2., 10., 12., 9., 11.),
+            Ohlc::new(4., 11., 11.5, 7., 8.),
+        ];
+        let scaled = |kind| {
+            Graph::new()
+                .with_candlestick(Candlestick::new(&rows, kind))
+                .scale(Limits::new(Point::new(0., 0.), Point::new(40., 50.)))
+                .candlesticks()[0]
+                .clone()
+        };
+        let chart = scaled(CandlestickKind::Candle);
+        let masks = chart.get_mask().unwrap();
+        let style = CandlestickStyle::default();
+        assert!(masks.iter().any(|m| m.color == style.up()));
+        assert!(masks.iter().any(|m| m.color == style.down()));
+
+        let bars = scaled(CandlestickKind::Bar).get_mask().unwrap();
+        assert!(bars.iter().any(|m| m.color == style.up()));
+        assert!(bars.iter().any(|m| m.color == style.down()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_volume_panics() {
+        Candlestick::volume(&[Ohlc::new(0., 1., 1., 1., 1.)]);
+    }
+}
@@ -1,20 +1,26 @@
 use super::{
     axes::AxesPositioning,
-    common::{Drawable, FloatConvertable, Graphable, MaskPoints},
+    common::{AlphaMaskPoints, FloatConvertable, Graphable, PixelSink},
     graph::Graph,
+    layout,
     limits::Limits,
+    pie::PieChart,
     point::Point,
+    polar::PolarGraph,
+    subplots::Subplots,
     text::Label,
 };
 use crate::common::Result;
 use rgb::RGB8;
 
-#[derive(Debug)]
-struct CanvasBuffer {
-    left: u32,
-    top: u32,
-    right: u32,
-    bottom: u32,
+#[derive(Debug, Clone)]
+pub(crate) struct CanvasBuffer {
+    pub(crate) left: u32,
+    pub(crate) top: u32,
+    pub(crate) right: u32,
+    pub(crate) bottom: u32,
+    // grow the buffer to fit the labels around the plot
+    pub(crate) auto: bool,
 }
 
 pub enum BufferType {
@@ -23,6 +29,9 @@ pub enum BufferType {
     TopBottom(u32, u32),
     LeftRight(u32, u32),
     TopBottomLeftRight(u32, u32, u32, u32),
+    /// Leaves the given padding at every edge, plus whatever room the tick labels, axis titles,
+    /// colorbar and legend need so that none of them are clipped.
+    Auto(u32),
 }
 
 impl CanvasBuffer {
@@ -34,24 +43,32 @@ impl CanvasBuffer {
                 top: x,
                 right: x,
                 bottom: x,
+                auto: false,
             },
             BufferType::TopBottom(top, bottom) => CanvasBuffer {
                 left: 0,
                 top,
                 right: 0,
                 bottom,
+                auto: false,
             },
             BufferType::LeftRight(left, right) => CanvasBuffer {
                 left,
                 top: 0,
                 right,
                 bottom: 0,
+                auto: false,
             },
             BufferType::TopBottomLeftRight(top, bottom, left, right) => CanvasBuffer {
                 left,
                 top,
                 right,
                 bottom,
+                auto: false,
+            },
+            BufferType::Auto(padding) => CanvasBuffer {
+                auto: true,
+                ..CanvasBuffer::new(BufferType::Uniform(padding))
             },
         }
     }
@@ -73,35 +90,153 @@ impl Canvas {
         }
     }
 
+    /// Blends a color over the existing color of a point, weighted by an alpha from 0
+    /// (transparent) to 255 (opaque).
+    pub fn blend_pixel(&mut self, point: &Point<u32>, color: &RGB8, alpha: u8) {
+        if self.limits.contains(point) {
+            let x = point.x;
+            let y = self.limits.max().y - point.y;
+            let pixel = &mut self.pixels[y as usize][x as usize];
+            let alpha = alpha as u32;
+            let mix =
+                |fg: u8, bg: u8| ((fg as u32 * alpha + bg as u32 * (255 - alpha)) / 255) as u8;
+            *pixel = RGB8::new(
+                mix(color.r, pixel.r),
+                mix(color.g, pixel.g),
+                mix(color.b, pixel.b),
+            );
+        }
+    }
+
+    /// Draws pixels drawn at `factor` times the resolution of the canvas. Each pixel is blended
+    /// with the average color of its block of `factor` x `factor` fine pixels, where block pixels
+    /// that were not drawn keep the existing color.
+    fn draw_supersampled(&mut self, fine_pixels: &FinePixels, factor: u32) {
+        let Some(drawn) = &fine_pixels.drawn else {
+            return;
+        };
+
+        // only the blocks around the drawn pixels need to be visited
+        let block_size = factor * factor;
+        for block_y in drawn.min().y / factor..=drawn.max().y / factor {
+            for block_x in drawn.min().x / factor..=drawn.max().x / factor {
+                // the summed color and the number of covered pixels of the block
+                let (mut sum, mut count) = ([0u32; 3], 0);
+                for y in block_y * factor..(block_y + 1) * factor {
+                    for x in block_x * factor..(block_x + 1) * factor {
+                        if let Some(color) = fine_pixels.get(x, y) {
+                            sum[0] += color.r as u32;
+                            sum[1] += color.g as u32;
+                            sum[2] += color.b as u32;
+                            count += 1;
+                        }
+                    }
+                }
+                if count == 0 {
+                    continue;
+                }
+
+                let average = |sum: u32| ((sum + count / 2) / count) as u8;
+                let color = RGB8::new(average(sum[0]), average(sum[1]), average(sum[2]));
+                let alpha = (count * 255 / block_size) as u8;
+                self.blend_pixel(&Point::new(block_x, block_y), &color, alpha);
+            }
+        }
+    }
+
+    pub fn get_bytes(&self) -> Vec<u8> {
+        let width = self.pixels.first().map_or(0, |row| row.len());
+        let mut bytes = Vec::with_capacity(self.pixels.len() * width * 3);
+        for rgb in self.pixels.iter().flatten() {
+            bytes.extend_from_slice(&[rgb.r, rgb.g, rgb.b]);
+        }
+        bytes
+    }
+}
+
+impl PixelSink for Canvas {
     /// Sets the color for a point in the canvas. The provided point should be zero-indexed with
     /// the lower-left corner as (0, 0) and the upper-right cornder as (width - 1, height - 1).
-    pub fn set_pixel(&mut self, point: &Point<u32>, color: &RGB8) {
+    fn set_pixel(&mut self, point: &Point<u32>, color: &RGB8) {
         if self.limits.contains(point) {
             // reverse y since higher values means closer to
             // the top of the canvas
             let x = point.x;
             let y = self.limits.max().y - point.y;
-            self.pixels[y as usize][x as usize] = color.clone();
+            self.pixels[y as usize][x as usize] = *color;
         }
     }
 
-    /// Sets the color for multiple points in the canvas. The provided points should be zero-indexed
-    /// with the lower-left corner as (0, 0) and the upper-right cornder as (width - 1, height - 1).
-    pub fn set_pixels(&mut self, points: &[Point<u32>], color: &RGB8) {
-        for point in points {
-            self.set_pixel(point, color);
+    /// Fills the part of the span inside the canvas as a single slice of its row.
+    fn set_span(&mut self, y: u32, x_min: u32, x_max: u32, color: &RGB8) {
+        let max = self.limits.max();
+        if y > max.y || x_min > max.x || x_min > x_max {
+            return;
         }
+        let row = &mut self.pixels[(max.y - y) as usize];
+        row[x_min as usize..=x_max.min(max.x) as usize].fill(*color);
     }
 
-    pub fn get_bytes(&self) -> Vec<u8> {
-        self.pixels
-            .iter()
-            .flat_map(|row| {
-                row.iter()
-                    .flat_map(|&rgb| [rgb.r, rgb.g, rgb.b])
-                    .collect::<Vec<u8>>()
-            })
-            .collect()
+    fn set_rect(&mut self, min: &Point<u32>, max: &Point<u32>, color: &RGB8) {
+        for y in min.y..=max.y.min(self.limits.max().y) {
+            self.set_span(y, min.x, max.x, color);
+        }
+    }
+}
+
+/// Pixels drawn at a multiple of the canvas resolution, which are averaged down to the canvas
+/// when supersampling. Pixels are stored row by row from the bottom-left corner of the
+/// magnified canvas, and pixels outside it are ignored.
+#[derive(Debug)]
+struct FinePixels {
+    width: u32,
+    height: u32,
+    pixels: Vec<Option<RGB8>>,
+    // bounds of the pixels drawn so far
+    drawn: Option<Limits<u32>>,
+}
+
+impl FinePixels {
+    fn new(width: u32, height: u32) -> FinePixels {
+        FinePixels {
+            width,
+            height,
+            pixels: vec![None; width as usize * height as usize],
+            drawn: None,
+        }
+    }
+
+    fn get(&self, x: u32, y: u32) -> Option<RGB8> {
+        match x < self.width && y < self.height {
+            true => self.pixels[y as usize * self.width as usize + x as usize],
+            false => None,
+        }
+    }
+
+    fn mark_drawn(&mut self, min: Point<u32>, max: Point<u32>) {
+        self.drawn = Some(match &self.drawn {
+            Some(drawn) => Limits::new(
+                Point::new(drawn.min().x.min(min.x), drawn.min().y.min(min.y)),
+                Point::new(drawn.max().x.max(max.x), drawn.max().y.max(max.y)),
+            ),
+            None => Limits::new(min, max),
+        });
+    }
+}
+
+impl PixelSink for FinePixels {
+    fn set_pixel(&mut self, point: &Point<u32>, color: &RGB8) {
+        self.set_span(point.y, point.x, point.x, color);
+    }
+
+    fn set_span(&mut self, y: u32, x_min: u32, x_max: u32, color: &RGB8) {
+        if y >= self.height || x_min >= self.width || x_min > x_max {
+            return;
+        }
+        let x_max = x_max.min(self.width - 1);
+        let row = y as usize * self.width as usize;
+        self.pixels[row + x_min as usize..=row + x_max as usize].fill(Some(*color));
+        self.mark_drawn(Point::new(x_min, y), Point::new(x_max, y));
     }
 }
 
@@ -110,8 +245,12 @@ pub struct TerminalCanvas<T: Graphable> {
     canvas: Canvas,
     buffer: CanvasBuffer,
     graph: Option<Graph<T>>,
+    pie_chart: Option<PieChart>,
+    polar_graph: Option<PolarGraph>,
+    subplots: Option<Subplots<T>>,
     labels: Vec<Label>,
     limits: Limits<u32>,
+    supersampling: u32,
 }
 
 impl<T> TerminalCanvas<T>
@@ -123,8 +262,12 @@ where
             canvas: Canvas::new(width, height, background),
             buffer: CanvasBuffer::new(BufferType::None),
             graph: None,
+            pie_chart: None,
+            polar_graph: None,
+            subplots: None,
             labels: Vec::new(),
             limits: Limits::new(Point::new(0, 0), Point::new(width - 1, height - 1)),
+            supersampling: 1,
         }
     }
 
@@ -133,14 +276,66 @@ where
         self
     }
 
+    /// Draws plots at `factor` times the resolution of the canvas and averages each block of
+    /// `factor` x `factor` pixels down to one, smoothing the edges of markers, diagonal lines,
+    /// curves and pie wedges. Axes, grid lines and text are still drawn at the resolution of the
+    /// canvas. Defaults to 1, which draws plots directly.
+    pub fn with_supersampling(mut self, factor: u32) -> Self {
+        if factor == 0 {
+            panic!("Supersampling factor must be at least 1");
+        }
+        self.supersampling = factor;
+        self
+    }
+
     pub fn with_graph(mut self, graph: Graph<T>) -> Self {
-        if graph.data().is_empty() {
+        if graph.is_empty() {
             panic!("Cannot add empty graph");
         }
+        self.assert_no_plot("graph");
         self.graph = Some(graph);
         self
     }
 
+    /// Adds a pie chart, which takes the place of a graph on the canvas.
+    pub fn with_pie_chart(mut self, pie_chart: PieChart) -> Self {
+        self.assert_no_plot("pie chart");
+        self.pie_chart = Some(pie_chart);
+        self
+    }
+
+    /// Adds a polar graph, which takes the place of a graph on the canvas.
+    pub fn with_polar_graph(mut self, polar_graph: PolarGraph) -> Self {
+        if polar_graph.is_empty() {
+            panic!("Cannot add empty polar graph");
+        }
+        self.assert_no_plot("polar graph");
+        self.polar_graph = Some(polar_graph);
+        self
+    }
+
+    /// Adds a grid of graphs, which takes the place of a single graph on the canvas. The grid
+    /// fills the area inside the canvas buffer.
+    pub fn with_subplots(mut self, subplots: Subplots<T>) -> Self {
+        if subplots.is_empty() {
+            panic!("Cannot add empty subplots");
+        }
+        self.assert_no_plot("subplots");
+        self.subplots = Some(subplots);
+        self
+    }
+
+    // a canvas holds a single graph, pie chart, polar graph or subplot grid
+    fn assert_no_plot(&self, kind: &str) {
+        if self.graph.is_some()
+            || self.pie_chart.is_some()
+            || self.polar_graph.is_some()
+            || self.subplots.is_some()
+        {
+            panic!("Cannot add {kind} to a canvas that already has a plot");
+        }
+    }
+
     pub fn with_label(mut self, label: Label) -> Self {
         self.labels.push(label);
         self
@@ -152,105 +347,392 @@ where
 
     /// Consumes all drawable assets and draws them on the canvas.
     pub fn draw(mut self) -> Result<Self> {
-        let canvas_limits = self.get_drawable_limits()?.convert_to_f64();
+        let canvas_limits = self.get_drawable_limits()?;
 
         if let Some(graph) = self.graph.take() {
-            let unscaled_limits = graph
-                .limits()
-                .ok_or("Graph has no data; cannot compute limits")?
-                .convert_to_f64();
-            let scaled_graph = graph.scale(canvas_limits);
+            self.draw_graph(graph, &canvas_limits)?;
+        }
+
+        if let Some(subplots) = self.subplots.take() {
+            let panel_limits = subplots.panel_limits(&canvas_limits)?;
+            for (graph, area) in subplots.graphs().into_iter().zip(panel_limits) {
+                let graph_limits = match subplots.panel_buffer().auto {
+                    true => Self::layout_graph(&graph, &area)?,
+                    false => Self::fit_graph(&graph, *area.min(), *area.max())?,
+                };
+                self.draw_graph(graph, &graph_limits)?;
+            }
+        }
+
+        if let Some(pie_chart) = self.pie_chart.take() {
+            self.draw_plot(|factor, sink| {
+                pie_chart.draw(&Self::magnify_limits(&canvas_limits, factor), sink)
+            })?;
+            self.labels.extend(pie_chart.get_labels(&canvas_limits)?);
+
+            if let Some(legend) = pie_chart.legend() {
+                legend.draw(&canvas_limits, &mut self.canvas)?;
+                self.labels.extend(legend.get_labels(&canvas_limits)?);
+            }
+        }
 
-            scaled_graph
-                .get_axes_labels(&unscaled_limits)?
-                .into_iter()
-                .for_each(|label| self.labels.push(label));
+        if let Some(polar_graph) = self.polar_graph.take() {
+            self.draw_plot(|factor, sink| {
+                polar_graph
+                    .magnified(factor)
+                    .draw(&Self::magnify_limits(&canvas_limits, factor), sink)
+            })?;
+            self.labels.extend(polar_graph.get_labels(&canvas_limits)?);
 
-            scaled_graph
-                .get_mask()?
-                .iter()
-                .for_each(|mask| self.canvas.set_pixels(&mask.points, &mask.color));
+            if let Some(legend) = polar_graph.legend() {
+                legend.draw(&canvas_limits, &mut self.canvas)?;
+                self.labels.extend(legend.get_labels(&canvas_limits)?);
+            }
         }
 
         // labels must be drawn after graph since axes labels are added to the canvas
-        let label_masks: Vec<Vec<MaskPoints>> = self
+        let label_masks: Vec<Vec<AlphaMaskPoints>> = self
             .labels
             .iter()
-            .map(|txt| txt.get_mask())
+            .map(|txt| txt.get_alpha_mask())
             .collect::<Result<_>>()?;
-        label_masks
-            .iter()
-            .flatten()
-            .for_each(|mask| self.canvas.set_pixels(&mask.points, &mask.color));
+        for mask in label_masks.iter().flatten() {
+            for (point, alpha) in &mask.points {
+                self.canvas.blend_pixel(point, &mask.color, *alpha);
+            }
+        }
 
         Ok(self)
     }
 
+    /// Draws a graph scaled to fill the given limits, along with its colorbar and legend.
+    fn draw_graph(&mut self, graph: Graph<T>, graph_limits: &Limits<u32>) -> Result<()> {
+        let canvas_limits = graph_limits.convert_to_f64();
+        let unscaled_limits = graph
+            .limits()
+            .ok_or("Graph has no data; cannot compute limits")?
+            .convert_to_f64();
+        let secondary_limits = graph.secondary_limits().map(|l| l.convert_to_f64());
+        let scaled_graph = graph.scale(canvas_limits);
+        let (colorbar_limits, legend_limits) =
+            Self::side_limits(&scaled_graph, graph_limits, &secondary_limits);
+
+        scaled_graph
+            .get_axes_labels(&unscaled_limits)?
+            .into_iter()
+            .for_each(|label| self.labels.push(label));
+
+        scaled_graph.draw_frame(&mut self.canvas)?;
+        self.draw_plot(|factor, sink| match factor {
+            1 => scaled_graph.draw_data(sink),
+            factor => scaled_graph.draw_magnified_data(factor, sink),
+        })?;
+        for contour in scaled_graph.contours() {
+            self.labels.extend(contour.get_labels()?);
+        }
+        for annotation in scaled_graph.annotations() {
+            self.labels.push(annotation.get_label());
+        }
+
+        if let Some(secondary_limits) = &secondary_limits {
+            self.labels
+                .extend(scaled_graph.get_secondary_axis_labels(secondary_limits)?);
+        }
+
+        if let Some(colorbar) = scaled_graph.colorbar() {
+            colorbar.draw(&colorbar_limits, &mut self.canvas)?;
+            self.labels.extend(colorbar.get_labels(&colorbar_limits)?);
+        }
+
+        if let Some(legend) = scaled_graph.legend() {
+            legend.draw(&legend_limits, &mut self.canvas)?;
+            self.labels.extend(legend.get_labels(&legend_limits)?);
+        }
+
+        Ok(())
+    }
+
+    /// Draws a plot at the supersampling factor, straight onto the canvas or, when
+    /// supersampling, onto fine pixels that are then averaged down to the canvas.
+    fn draw_plot(
+        &mut self,
+        draw: impl FnOnce(u32, &mut dyn PixelSink) -> Result<()>,
+    ) -> Result<()> {
+        match self.supersampling {
+            1 => draw(1, &mut self.canvas),
+            factor => {
+                let canvas_max = *Self::magnify_limits(&self.canvas.limits, factor).max();
+                let mut fine_pixels = FinePixels::new(canvas_max.x + 1, canvas_max.y + 1);
+                draw(factor, &mut fine_pixels)?;
+                self.canvas.draw_supersampled(&fine_pixels, factor);
+                Ok(())
+            }
+        }
+    }
+
+    /// Gets the limits covering the same pixels at `factor` times the resolution.
+    fn magnify_limits(limits: &Limits<u32>, factor: u32) -> Limits<u32> {
+        let (min, max) = (*limits.min(), *limits.max());
+        Limits::new(
+            Point::new(min.x * factor, min.y * factor),
+            Point::new((max.x + 1) * factor - 1, (max.y + 1) * factor - 1),
+        )
+    }
+
+    /// Gets the limits that the colorbar and the legend are placed beside. Elements to the right
+    /// of the graph are placed in order: the secondary y axis labels, the colorbar, then the
+    /// legend.
+    fn side_limits(
+        scaled_graph: &Graph<f64>,
+        graph_limits: &Limits<u32>,
+        secondary_limits: &Option<Limits<f64>>,
+    ) -> (Limits<u32>, Limits<u32>) {
+        let mut colorbar_limits = graph_limits.clone();
+        if let (Some(axis), Some(secondary_limits)) =
+            (scaled_graph.secondary_axis(), secondary_limits)
+        {
+            let shift = Point::new(axis.reserved_width(secondary_limits), 0);
+            colorbar_limits.update_max(*colorbar_limits.max() + shift);
+        }
+
+        let mut legend_limits = colorbar_limits.clone();
+        if let Some(colorbar) = scaled_graph.colorbar() {
+            legend_limits
+                .update_max(*colorbar_limits.max() + Point::new(colorbar.reserved_width(), 0));
+        }
+        (colorbar_limits, legend_limits)
+    }
+
+    /// Gets the labels around a graph drawn within the given limits: the axes tick labels and
+    /// titles, the secondary axis labels, and the colorbar and legend labels.
+    fn margin_labels(graph: &Graph<T>, graph_limits: &Limits<u32>) -> Result<Vec<Label>> {
+        let unscaled_limits = graph
+            .limits()
+            .ok_or("Graph has no data; cannot compute limits")?
+            .convert_to_f64();
+        let secondary_limits = graph.secondary_limits().map(|l| l.convert_to_f64());
+        let scaled_graph = graph.clone().scale(graph_limits.convert_to_f64());
+        let (colorbar_limits, legend_limits) =
+            Self::side_limits(&scaled_graph, graph_limits, &secondary_limits);
+
+        let mut labels = scaled_graph.get_axes_labels(&unscaled_limits)?;
+        if let Some(secondary_limits) = &secondary_limits {
+            labels.extend(scaled_graph.get_secondary_axis_labels(secondary_limits)?);
+        }
+        if let Some(colorbar) = scaled_graph.colorbar() {
+            labels.extend(colorbar.get_labels(&colorbar_limits)?);
+        }
+        if let Some(legend) = scaled_graph.legend() {
+            labels.extend(legend.get_labels(&legend_limits)?);
+        }
+        Ok(labels)
+    }
+
+    /// Fits a graph within the given area with margins just large enough for its labels.
+    fn layout_graph(graph: &Graph<T>, area: &Limits<u32>) -> Result<Limits<u32>> {
+        layout::fit_labels(
+            area,
+            |min, max| Self::fit_graph(graph, min, max),
+            |graph_limits| Self::margin_labels(graph, graph_limits),
+        )
+    }
+
     pub fn get_drawable_limits(&self) -> Result<Limits<u32>> {
         // set initial point from the buffer; use saturating_sub to avoid u32 overflow
         // when the canvas is smaller than the buffer
-        let mut min = Point::new(self.buffer.left, self.buffer.bottom);
-        let mut max = Point::new(
+        let min = Point::new(self.buffer.left, self.buffer.bottom);
+        let max = Point::new(
             self.limits.max().x.saturating_sub(self.buffer.right),
             self.limits.max().y.saturating_sub(self.buffer.top),
         );
 
         if let Some(graph) = &self.graph {
-            let largest_marker_sz = graph
-                .data()
-                .iter()
-                .map(|s| s.marker_style().size())
-                .max()
-                .ok_or("Graph has no series data; cannot compute drawable limits")?;
-
-            // axes thickness in x/y pixels
-            let axes_thickness = match graph.axes() {
-                Some(axes) => match axes.positioning() {
-                    AxesPositioning::XOnly(line_style) => (0, 2 * line_style.thickness()),
-                    AxesPositioning::YOnly(line_style) => (2 * line_style.thickness(), 0),
-                    AxesPositioning::XY(line_style) => {
-                        (2 * line_style.thickness(), 2 * line_style.thickness())
-                    }
-                },
-                None => (0, 0),
+            return match self.buffer.auto {
+                true => Self::layout_graph(graph, &checked_limits(min, max)?),
+                false => Self::fit_graph(graph, min, max),
             };
+        }
 
-            // note that axes and markers can overlap; so use the larger of marker/axes as bounds
-            let inset_x = u32::max(largest_marker_sz, axes_thickness.0);
-            let inset_y = u32::max(largest_marker_sz, axes_thickness.1);
-
-            let min_x = min.x + inset_x;
-            let min_y = min.y + inset_y;
-            let max_x = max.x.saturating_sub(inset_x);
-            let max_y = max.y.saturating_sub(inset_y);
+        // pie chart and polar graph legends are drawn to the right of the chart
+        let reserved_width = match (&self.pie_chart, &self.polar_graph) {
+            (Some(pie_chart), _) => pie_chart.reserved_width(),
+            (_, Some(polar_graph)) => polar_graph.reserved_width(),
+            _ => 0,
+        };
+        let fit = |min: Point<u32>, mut max: Point<u32>| {
+            max.x = max.x.saturating_sub(reserved_width);
+            checked_limits(min, max)
+        };
+        if !self.buffer.auto {
+            return fit(min, max);
+        }
 
-            // include axes text
+        let chart_labels = |area: &Limits<u32>| -> Result<Vec<Label>> {
+            let (mut labels, legend) = match (&self.pie_chart, &self.polar_graph) {
+                (Some(pie_chart), _) => (pie_chart.get_labels(area)?, pie_chart.legend()),
+                (_, Some(polar_graph)) => (polar_graph.get_labels(area)?, polar_graph.legend()),
+                _ => (Vec::new(), None),
+            };
+            if let Some(legend) = legend {
+                labels.extend(legend.get_labels(area)?);
+            }
+            Ok(labels)
+        };
+        layout::fit_labels(&checked_limits(min, max)?, fit, chart_labels)
+    }
 
-            min = Point::new(min_x, min_y);
-            max = Point::new(max_x, max_y);
+    /// Gets the limits of the graph area within the given bounds, leaving room for the axes,
+    /// markers, colorbar and legend.
+    fn fit_graph(graph: &Graph<T>, min: Point<u32>, max: Point<u32>) -> Result<Limits<u32>> {
+        if graph.is_empty() {
+            return Err("Graph has no data; cannot compute drawable limits".into());
         }
+        // only markers are drawn outside of the data limits
+        let largest_marker_sz = graph
+            .data()
+            .iter()
+            .map(|s| s.max_marker_size())
+            .chain(graph.box_plots().iter().map(|b| b.max_marker_size()))
+            .max()
+            .unwrap_or(0);
 
-        if min.x >= max.x || min.y >= max.y {
-            return Err(format!(
-                "Canvas too small for the configured buffer and graph elements. \
-                 Drawable area would be {}x{} pixels (min={:?}, max={:?}). \
-                 Try a larger terminal window or smaller buffer/marker sizes.",
-                max.x.saturating_sub(min.x),
-                max.y.saturating_sub(min.y),
-                min,
-                max,
-            )
-            .into());
+        // axes thickness in x/y pixels
+        let axes_thickness = match graph.axes() {
+            Some(axes) => match axes.positioning() {
+                AxesPositioning::XOnly(line_style) => (0, 2 * line_style.thickness()),
+                AxesPositioning::YOnly(line_style) => (2 * line_style.thickness(), 0),
+                AxesPositioning::XY(line_style) => {
+                    (2 * line_style.thickness(), 2 * line_style.thickness())
+                }
+            },
+            None => (0, 0),
+        };
+
+        // note that axes and markers can overlap; so use the larger of marker/axes as bounds
+        let inset_x = u32::max(largest_marker_sz, axes_thickness.0);
+        let inset_y = u32::max(largest_marker_sz, axes_thickness.1);
+
+        let min_x = min.x + inset_x;
+        let min_y = min.y + inset_y;
+        let mut max_x = max.x.saturating_sub(inset_x);
+        let max_y = max.y.saturating_sub(inset_y);
+
+        // secondary axes, colorbars and legends are drawn to the right of the graph area
+        if let (Some(axis), Some(secondary_limits)) =
+            (graph.secondary_axis(), graph.secondary_limits())
+        {
+            max_x = max_x.saturating_sub(axis.reserved_width(&secondary_limits));
+        }
+        if let Some(colorbar) = graph.colorbar() {
+            max_x = max_x.saturating_sub(colorbar.reserved_width());
+        }
+        if let Some(legend) = graph.legend() {
+            max_x = max_x.saturating_sub(legend.reserved_width());
         }
 
-        Ok(Limits::new(min, max))
+        checked_limits(Point::new(min_x, min_y), Point::new(max_x, max_y))
     }
 }
 
+/// Builds the limits of a drawable area, or an error if the area has no room left.
+pub(crate) fn checked_limits(min: Point<u32>, max: Point<u32>) -> Result<Limits<u32>> {
+    if min.x >= max.x || min.y >= max.y {
+        return Err(format!(
+            "Canvas too small for the configured buffer and graph elements. \
+             Drawable area would be {}x{} pixels (min={:?}, max={:?}). \
+             Try a larger terminal window or smaller buffer/marker sizes.",
+            max.x.saturating_sub(min.x),
+            max.y.saturating_sub(min.y),
+            min,
+            max,
+        )
+        .into());
+    }
+
+    Ok(Limits::new(min, max))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plotting::{colors, series::Series};
+    use crate::plotting::{colors, common::MaskPoints, series::Series};
+
+    #[test]
+    fn blend_pixel_mixes_with_existing_color() {
+        let mut canvas = Canvas::new(2, 2, colors::BLACK);
+        let point = Point::new(0, 0);
+        canvas.blend_pixel(&point, &RGB8::new(200, 100, 0), 128);
+        assert_eq!(&canvas.get_bytes()[6..9], &[100, 50, 0]);
+        canvas.blend_pixel(&point, &colors::WHITE, 255);
+        assert_eq!(&canvas.get_bytes()[6..9], &[255, 255, 255]);
+    }
+
+    #[test]
+    fn rects_are_clipped_to_canvas() {
+        let mut canvas = Canvas::new(3, 2, colors::BLACK);
+        canvas.set_rect(&Point::new(1, 1), &Point::new(10, 10), &colors::WHITE);
+        // nothing is drawn for an empty span
+        canvas.set_span(0, 2, 1, &colors::WHITE);
+        let white = [255, 255, 255];
+        let black = [0, 0, 0];
+        let expected = [black, white, white, black, black, black].concat();
+        assert_eq!(canvas.get_bytes(), expected);
+    }
+
+    #[test]
+    fn supersampled_masks_blend_by_coverage() {
+        let mut canvas = Canvas::new(2, 1, colors::BLACK);
+        let masks = [
+            MaskPoints {
+                points: vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)],
+                color: colors::WHITE,
+            },
+            // later masks cover earlier ones
+            MaskPoints {
+                points: vec![
+                    Point::new(2, 0),
+                    Point::new(2, 1),
+                    Point::new(3, 0),
+                    Point::new(3, 1),
+                ],
+                color: RGB8::new(200, 0, 0),
+            },
+        ];
+        let mut fine_pixels = FinePixels::new(4, 2);
+        for mask in &masks {
+            fine_pixels.set_pixels(&mask.points, &mask.color);
+        }
+        canvas.draw_supersampled(&fine_pixels, 2);
+        assert_eq!(&canvas.get_bytes(), &[127, 127, 127, 200, 0, 0]);
+    }
+
+    #[test]
+    fn supersampling_smooths_diagonal_lines() {
+        use crate::plotting::line::LineStyle;
+        let points = [Point::new(0., 0.), Point::new(10., 3.)];
+        let graph =
+            Graph::new().with_series(Series::new(&points).with_line_style(LineStyle::Solid {
+                color: colors::WHITE,
+                thickness: 0,
+            }));
+        let partial_pixels = |factor: u32| {
+            let bytes = TerminalCanvas::new(60, 40, colors::BLACK)
+                .with_supersampling(factor)
+                .with_graph(graph.clone())
+                .draw()
+                .unwrap()
+                .get_bytes();
+            bytes.iter().filter(|&&b| b != 0 && b != 255).count()
+        };
+        assert_eq!(partial_pixels(1), 0);
+        assert!(partial_pixels(4) > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Supersampling factor must be at least 1")]
+    fn zero_supersampling_factor_panics() {
+        TerminalCanvas::<f64>::new(10, 10, colors::BLACK).with_supersampling(0);
+    }
 
     #[test]
     #[should_panic]
@@ -315,6 +797,378 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn colorbar_reduces_drawable_width() {
+        use crate::plotting::{colorbar::Colorbar, colormaps::Colormap, text::TextStyle};
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let graph = Graph::new().with_series(Series::new(&points));
+        let colorbar = Colorbar::new(Colormap::Viridis, 0., 5., TextStyle::default());
+
+        let without = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(graph.clone())
+            .get_drawable_limits()
+            .unwrap();
+        let with = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(graph.with_colorbar(colorbar.clone()))
+            .get_drawable_limits()
+            .unwrap();
+        assert_eq!(with.max().x, without.max().x - colorbar.reserved_width());
+        assert_eq!(with.max().y, without.max().y);
+    }
+
+    #[test]
+    fn series_with_colorbar_draws() {
+        use crate::plotting::{colorbar::Colorbar, colormaps::Colormap, text::TextStyle};
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let values = (0..=5).map(|x| x as f64).collect::<Vec<_>>();
+        TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_buffer(BufferType::Uniform(5))
+            .with_graph(
+                Graph::new()
+                    .with_series(Series::new(&points).with_color_values(
+                        &values,
+                        Colormap::Viridis,
+                        0.,
+                        5.,
+                    ))
+                    .with_colorbar(Colorbar::new(
+                        Colormap::Viridis,
+                        0.,
+                        5.,
+                        TextStyle::default(),
+                    )),
+            )
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    fn box_plot_only_graph_draws() {
+        use crate::plotting::{
+            axes::Axes,
+            boxplot::{BoxPlot, BoxPlotKind},
+            line::LineStyle,
+            text::TextStyle,
+        };
+        let groups = [
+            ("fast", vec![1., 2., 2.5, 3., 9.]),
+            ("slow", vec![4., 5., 6., 7.]),
+        ];
+        let axes = Axes::new(
+            AxesPositioning::XY(LineStyle::default_with_thickness(1)),
+            TextStyle::with_color(colors::WHITE),
+        );
+        TerminalCanvas::new(300, 300, colors::BLACK)
+            .with_buffer(BufferType::Uniform(40))
+            .with_graph(
+                Graph::new()
+                    .with_box_plot(BoxPlot::new(&groups, BoxPlotKind::Violin))
+                    .with_axes(axes),
+            )
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    fn candlestick_only_graph_draws() {
+        use crate::plotting::candlestick::{Candlestick, CandlestickKind, Ohlc};
+        let rows = [
+            Ohlc::new(0., 10., 12., 9., 11.).with_volume(100.),
+            Ohlc::new(1., 11., 11.5, 7., 8.).with_volume(300.),
+        ];
+        let subplots = Subplots::new(4, 1)
+            .with_spanning_graph(
+                0,
+                0,
+                3,
+                1,
+                Graph::new().with_candlestick(Candlestick::new(&rows, CandlestickKind::Candle)),
+            )
+            .with_graph(
+                3,
+                0,
+                Graph::new().with_candlestick(Candlestick::volume(&rows)),
+            )
+            .with_shared_x();
+        TerminalCanvas::<f64>::new(300, 300, colors::BLACK)
+            .with_subplots(subplots)
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    fn contour_level_labels_are_added_to_canvas() {
+        use crate::plotting::{contour::Contour, text::TextStyle};
+        let axis = [-2., -1., 0., 1., 2.];
+        let z = axis
+            .iter()
+            .map(|y: &f64| axis.iter().map(|x: &f64| x.abs() + y.abs()).collect())
+            .collect::<Vec<_>>();
+        let contour = Contour::new(&axis, &axis, &z)
+            .with_levels(&[1., 2.])
+            .with_labels(TextStyle::with_color(colors::WHITE));
+        let canvas = TerminalCanvas::<f64>::new(200, 200, colors::BLACK)
+            .with_graph(Graph::new().with_contour(contour))
+            .draw()
+            .unwrap();
+        assert_eq!(canvas.labels.len(), 2);
+    }
+
+    #[test]
+    fn annotation_text_follows_data_point() {
+        use crate::plotting::{
+            annotation::Annotation,
+            text::{TextPositioning, TextStyle},
+        };
+        let points = [Point::new(0., 0.), Point::new(10., 10.)];
+        let annotation = Annotation::new(Point::new(10., 10.), "max", TextStyle::default())
+            .with_alignment(TextPositioning::RightAligned);
+        let canvas = TerminalCanvas::<f64>::new(100, 100, colors::BLACK)
+            .with_graph(
+                Graph::new()
+                    .with_series(Series::new(&points))
+                    .with_annotation(annotation),
+            )
+            .draw()
+            .unwrap();
+        assert_eq!(canvas.labels.len(), 1);
+        // the point is scaled to the top right corner of the canvas
+        assert_eq!(canvas.labels[0].pos().point(), &Point::new(99, 99));
+    }
+
+    #[test]
+    fn pie_chart_legend_reduces_drawable_width() {
+        use crate::plotting::text::TextStyle;
+        let pie =
+            PieChart::new(&[("home", 3.), ("var", 1.)]).with_percentages(TextStyle::default());
+        let without = TerminalCanvas::<f64>::new(200, 100, colors::BLACK)
+            .with_pie_chart(pie.clone())
+            .get_drawable_limits()
+            .unwrap();
+        let pie = pie.with_legend(TextStyle::with_color(colors::WHITE));
+        let with = TerminalCanvas::<f64>::new(200, 100, colors::BLACK)
+            .with_pie_chart(pie.clone())
+            .get_drawable_limits()
+            .unwrap();
+        assert_eq!(with.max().x, without.max().x - pie.reserved_width());
+
+        TerminalCanvas::<f64>::new(200, 100, colors::BLACK)
+            .with_buffer(BufferType::Uniform(5))
+            .with_pie_chart(pie.with_hole(0.4).with_exploded("var", 0.1))
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn pie_chart_and_graph_panics() {
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        TerminalCanvas::new(100, 100, colors::BLACK)
+            .with_graph(Graph::new().with_series(Series::new(&points)))
+            .with_pie_chart(PieChart::new(&[("a", 1.)]));
+    }
+
+    #[test]
+    fn polar_graph_draws_inside_drawable_area() {
+        use crate::plotting::{line::LineStyle, text::TextStyle};
+        let points = (0..36)
+            .map(|i| Point::new(i as f64 * 10., 1. + i as f64 / 10.))
+            .collect::<Vec<_>>();
+        let polar = PolarGraph::new()
+            .with_angle_unit(crate::plotting::polar::AngleUnit::Degrees)
+            .with_series(Series::new(&points).with_label("spiral"))
+            .with_grid(LineStyle::default())
+            .with_labels(TextStyle::with_color(colors::WHITE))
+            .with_legend(TextStyle::with_color(colors::WHITE));
+        let limits = TerminalCanvas::<f64>::new(200, 150, colors::BLACK)
+            .with_polar_graph(polar.clone())
+            .get_drawable_limits()
+            .unwrap();
+        assert_eq!(limits.max().x, 199 - polar.reserved_width());
+
+        TerminalCanvas::<f64>::new(200, 150, colors::BLACK)
+            .with_buffer(BufferType::Uniform(5))
+            .with_polar_graph(polar)
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn polar_graph_and_pie_chart_panics() {
+        TerminalCanvas::<f64>::new(100, 100, colors::BLACK)
+            .with_pie_chart(PieChart::new(&[("a", 1.)]))
+            .with_polar_graph(PolarGraph::new().with_series(Series::new(&[Point::new(0., 1.)])));
+    }
+
+    #[test]
+    fn legend_reduces_drawable_width() {
+        use crate::plotting::text::TextStyle;
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let graph = Graph::new()
+            .with_series(Series::new(&points).with_label("load"))
+            .with_legend(TextStyle::with_color(colors::WHITE));
+        let legend_width = graph.legend().unwrap().reserved_width();
+
+        let without = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(Graph::new().with_series(Series::new(&points)))
+            .get_drawable_limits()
+            .unwrap();
+        let with = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(graph.clone())
+            .get_drawable_limits()
+            .unwrap();
+        assert_eq!(with.max().x, without.max().x - legend_width);
+
+        TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_buffer(BufferType::Uniform(5))
+            .with_graph(graph)
+            .draw()
+            .unwrap();
+    }
+
+    fn axes_graph(scale: i32) -> Graph<i32> {
+        use crate::plotting::{
+            axes::{Axes, AxesPositioning},
+            line::LineStyle,
+            text::TextStyle,
+        };
+        let points = (0..=5)
+            .map(|x| Point::new(x, scale * x))
+            .collect::<Vec<Point<_>>>();
+        let axes = Axes::new(
+            AxesPositioning::XY(LineStyle::default_with_thickness(1)),
+            TextStyle::with_color(colors::WHITE),
+        )
+        .with_x_title("time")
+        .with_y_title("load");
+        Graph::new()
+            .with_series(Series::new(&points).with_label("load"))
+            .with_axes(axes)
+            .with_legend(TextStyle::with_color(colors::WHITE))
+    }
+
+    #[test]
+    fn auto_buffer_keeps_labels_on_canvas() {
+        let graph = axes_graph(1);
+        let canvas = TerminalCanvas::new(300, 200, colors::BLACK)
+            .with_buffer(BufferType::Auto(2))
+            .with_graph(graph.clone());
+        let limits = canvas.get_drawable_limits().unwrap();
+        let labels = TerminalCanvas::margin_labels(&graph, &limits).unwrap();
+        for (lower_left, upper_right) in labels.iter().filter_map(|l| l.extent()) {
+            assert!(lower_left.x >= 2 && lower_left.y >= 2, "{lower_left:?}");
+            assert!(
+                upper_right.x <= 297 && upper_right.y <= 197,
+                "{upper_right:?}"
+            );
+        }
+        // the tick labels and titles need more room than the padding
+        assert!(limits.min().x > 20 && limits.min().y > 20);
+        canvas.draw().unwrap();
+    }
+
+    #[test]
+    fn auto_buffer_grows_with_label_width() {
+        let left = |scale| {
+            TerminalCanvas::new(300, 200, colors::BLACK)
+                .with_buffer(BufferType::Auto(0))
+                .with_graph(axes_graph(scale))
+                .get_drawable_limits()
+                .unwrap()
+                .min()
+                .x
+        };
+        assert!(left(100_000) > left(1));
+    }
+
+    #[test]
+    fn auto_panel_buffer_draws_subplots() {
+        let subplots = Subplots::new(1, 2)
+            .with_panel_buffer(BufferType::Auto(2))
+            .with_graph(0, 0, axes_graph(1))
+            .with_graph(0, 1, axes_graph(1_000));
+        TerminalCanvas::new(400, 200, colors::BLACK)
+            .with_subplots(subplots)
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    fn secondary_axis_reduces_drawable_width() {
+        use crate::plotting::{
+            axes::SecondaryYAxis, line::LineStyle, series::YAxis, text::TextStyle,
+        };
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let latency = (0..=5)
+            .map(|x| Point::new(x, 100 * x))
+            .collect::<Vec<Point<_>>>();
+        let graph = Graph::new()
+            .with_series(Series::new(&points))
+            .with_series(Series::new(&latency).with_y_axis(YAxis::Secondary));
+        let axis = SecondaryYAxis::new(
+            LineStyle::default_with_thickness(1),
+            TextStyle::with_color(colors::WHITE),
+        );
+
+        let without = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(graph.clone())
+            .get_drawable_limits()
+            .unwrap();
+        let graph = graph.with_secondary_y_axis(axis.clone());
+        let with = TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_graph(graph.clone())
+            .get_drawable_limits()
+            .unwrap();
+        let secondary_limits = graph.secondary_limits().unwrap();
+        assert_eq!(
+            with.max().x,
+            without.max().x - axis.reserved_width(&secondary_limits)
+        );
+
+        TerminalCanvas::new(200, 200, colors::BLACK)
+            .with_buffer(BufferType::Uniform(30))
+            .with_graph(graph)
+            .draw()
+            .unwrap();
+    }
+
+    #[test]
+    fn subplots_draw_each_panel() {
+        use crate::plotting::subplots::Subplots;
+        let rising = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let falling = (0..=5)
+            .map(|x| Point::new(x, 5 - x))
+            .collect::<Vec<Point<_>>>();
+        let subplots = Subplots::new(2, 2)
+            .with_panel_buffer(BufferType::Uniform(5))
+            .with_spanning_graph(0, 0, 1, 2, Graph::new().with_series(Series::new(&rising)))
+            .with_graph(1, 1, Graph::new().with_series(Series::new(&falling)))
+            .with_shared_x();
+        let canvas = TerminalCanvas::new(200, 100, colors::BLACK)
+            .with_subplots(subplots)
+            .draw()
+            .unwrap();
+
+        // rows of bytes are stored from the top down; the bottom left cell is left empty
+        let bytes = canvas.get_bytes();
+        let is_lit = |x: usize, y: usize| bytes[3 * (y * 200 + x)..3 * (y * 200 + x + 1)] != [0; 3];
+        assert!((0..100).any(|x| (0..50).any(|y| is_lit(x, y))));
+        assert!((100..200).any(|x| (50..100).any(|y| is_lit(x, y))));
+        assert!(!(0..100).any(|x| (50..100).any(|y| is_lit(x, y))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn subplots_and_graph_panics() {
+        use crate::plotting::subplots::Subplots;
+        let points = (0..=5).map(|x| Point::new(x, x)).collect::<Vec<Point<_>>>();
+        let graph = Graph::new().with_series(Series::new(&points));
+        TerminalCanvas::new(100, 100, colors::BLACK)
+            .with_graph(graph.clone())
+            .with_subplots(Subplots::new(1, 1).with_graph(0, 0, graph));
+    }
+
     #[test]
     fn buffer_type_top_bottom_left_right_fields_are_correct() {
         let buf = CanvasBuffer::new(BufferType::TopBottomLeftRight(10, 5, 8, 3));
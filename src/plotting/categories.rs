@@ -0,0 +1,89 @@
+use super::point::Point;
+
+/// Space left on either side of the outer categories, in units of category spacing.
+pub const CATEGORY_PADDING: f64 = 0.5;
+
+/// Maps category names (e.g. service names or days of the week) to evenly spaced x positions.
+/// Categories are assigned positions 0, 1, 2, ... in the order they are first seen, so several
+/// series can share one set of categories.
+#[derive(Debug, Clone, Default)]
+pub struct Categories {
+    names: Vec<String>,
+}
+
+impl Categories {
+    pub fn new() -> Categories {
+        Categories { names: Vec::new() }
+    }
+
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Categories {
+        let mut categories = Categories::new();
+        names.iter().for_each(|name| {
+            categories.position(name.as_ref());
+        });
+        categories
+    }
+
+    /// Gets the position of a category, adding it after the existing categories if it has not
+    /// been seen before.
+    pub fn position(&mut self, name: &str) -> f64 {
+        let index = match self.names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name.to_string());
+                self.names.len() - 1
+            }
+        };
+        index as f64
+    }
+
+    /// Converts (category, value) pairs to points, with the category position as the x value.
+    pub fn points<S: AsRef<str>>(&mut self, data: &[(S, f64)]) -> Vec<Point<f64>> {
+        data.iter()
+            .map(|(name, y)| Point::new(self.position(name.as_ref()), *y))
+            .collect()
+    }
+
+    /// Gets the x range that fits all categories with padding on either side.
+    pub fn x_range(&self) -> (f64, f64) {
+        let last = self.names.len().saturating_sub(1) as f64;
+        (-CATEGORY_PADDING, last + CATEGORY_PADDING)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_follow_first_appearance() {
+        let mut categories = Categories::new();
+        assert_eq!(categories.position("tue"), 0.);
+        assert_eq!(categories.position("mon"), 1.);
+        assert_eq!(categories.position("tue"), 0.);
+        assert_eq!(categories.names(), &["tue", "mon"]);
+    }
+
+    #[test]
+    fn points_share_positions_across_series() {
+        let mut categories = Categories::from_names(&["mon", "tue", "wed"]);
+        let a = categories.points(&[("wed", 1.), ("mon", 2.)]);
+        let b = categories.points(&[("thu", 3.)]);
+        assert_eq!(a, vec![Point::new(2., 1.), Point::new(0., 2.)]);
+        assert_eq!(b, vec![Point::new(3., 3.)]);
+        assert_eq!(categories.len(), 4);
+        assert_eq!(categories.x_range(), (-0.5, 3.5));
+    }
+}
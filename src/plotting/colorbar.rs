@@ -0,0 +1,207 @@
+use super::{
+    colormaps::Colormap,
+    common::{MaskPoints, PixelSink},
+    limits::Limits,
+    point::Point,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+
+const NUM_COLORBAR_TICKS: u32 = 4;
+const DEFAULT_BAR_WIDTH: u32 = 10;
+const BAR_GAP: u32 = 10;
+const LABEL_GAP: u32 = 4;
+
+/// Legend that shows how values map to colors for series colored through a colormap. It is
+/// drawn as a vertical gradient to the right of the graph area with numeric tick labels.
+#[derive(Debug, Clone)]
+pub struct Colorbar {
+    colormap: Colormap,
+    min: f64,
+    max: f64,
+    width: u32,
+    style: TextStyle,
+}
+
+impl Colorbar {
+    pub fn new(colormap: Colormap, min: f64, max: f64, style: TextStyle) -> Colorbar {
+        if min > max {
+            panic!("Colorbar min ({min}) must not be greater than max ({max})");
+        }
+        Colorbar {
+            colormap,
+            min,
+            max,
+            width: DEFAULT_BAR_WIDTH,
+            style,
+        }
+    }
+
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn colormap(&self) -> Colormap {
+        self.colormap
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    fn tick_values(&self) -> Vec<f64> {
+        let interval = (self.max - self.min) / NUM_COLORBAR_TICKS as f64;
+        (0..=NUM_COLORBAR_TICKS)
+            .map(|i| self.min + interval * i as f64)
+            .collect()
+    }
+
+    fn tick_texts(&self) -> Vec<Text> {
+        self.tick_values()
+            .into_iter()
+            .map(|v| Text::from_number(v, 3, self.style.clone()))
+            .collect()
+    }
+
+    /// Gets the total horizontal space (in pixels) needed to the right of the graph area to draw
+    /// the bar and its labels.
+    pub fn reserved_width(&self) -> u32 {
+        let max_label_width = self
+            .tick_texts()
+            .iter()
+            .map(|txt| txt.width() as u32)
+            .max()
+            .unwrap_or(0);
+        BAR_GAP + self.width + LABEL_GAP + max_label_width
+    }
+
+    /// Gets the limits of the bar itself, given the limits of the graph area it is placed next
+    /// to.
+    pub fn bar_limits(&self, graph_limits: &Limits<u32>) -> Limits<u32> {
+        let min_x = graph_limits.max().x + BAR_GAP;
+        let min = Point::new(min_x, graph_limits.min().y);
+        let max = Point::new(min_x + self.width, graph_limits.max().y);
+        Limits::new(min, max)
+    }
+
+    pub fn draw<S: PixelSink + ?Sized>(
+        &self,
+        graph_limits: &Limits<u32>,
+        sink: &mut S,
+    ) -> Result<()> {
+        let bar_limits = self.bar_limits(graph_limits);
+        let (_, span_y) = bar_limits.span();
+
+        // one horizontal row of pixels per color; the bottom of the bar is the low end of the map
+        for y in bar_limits.min().y..=bar_limits.max().y {
+            let t = if span_y == 0 {
+                0.5
+            } else {
+                (y - bar_limits.min().y) as f64 / span_y as f64
+            };
+            let color = self.colormap.color_at(t);
+            sink.set_span(y, bar_limits.min().x, bar_limits.max().x, &color);
+        }
+        Ok(())
+    }
+
+    pub fn get_mask(&self, graph_limits: &Limits<u32>) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(graph_limits, &mut masks)?;
+        Ok(masks)
+    }
+
+    pub fn get_labels(&self, graph_limits: &Limits<u32>) -> Result<Vec<Label>> {
+        let bar_limits = self.bar_limits(graph_limits);
+        let (_, span_y) = bar_limits.span();
+        let interval_y = span_y as f64 / NUM_COLORBAR_TICKS as f64;
+
+        let labels = self
+            .tick_texts()
+            .into_iter()
+            .enumerate()
+            .map(|(i, txt)| {
+                let x = bar_limits.max().x + LABEL_GAP + (txt.width() as u32).div_ceil(2);
+                let y = bar_limits.min().y + (interval_y * i as f64).round() as u32;
+                Label::new(txt, TextPositioning::Centered(Point::new(x, y)))
+            })
+            .collect();
+        Ok(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn colorbar() -> Colorbar {
+        Colorbar::new(
+            Colormap::Viridis,
+            0.,
+            100.,
+            TextStyle::with_color(colors::WHITE),
+        )
+    }
+
+    fn graph_limits() -> Limits<u32> {
+        Limits::new(Point::new(10, 10), Point::new(110, 90))
+    }
+
+    #[test]
+    #[should_panic]
+    fn create_with_inverted_range_panics() {
+        Colorbar::new(Colormap::Viridis, 1., 0., TextStyle::default());
+    }
+
+    #[test]
+    fn bar_is_placed_right_of_graph() {
+        let bar = colorbar().bar_limits(&graph_limits());
+        assert!(bar.min().x > graph_limits().max().x);
+        assert_eq!(bar.min().y, graph_limits().min().y);
+        assert_eq!(bar.max().y, graph_limits().max().y);
+    }
+
+    #[test]
+    fn reserved_width_covers_bar_and_labels() {
+        let cb = colorbar();
+        let bar = cb.bar_limits(&graph_limits());
+        let right_edge = graph_limits().max().x + cb.reserved_width();
+        for label in cb.get_labels(&graph_limits()).unwrap() {
+            assert!(label.limits().min().x > bar.max().x);
+            assert!(label.limits().max().x <= right_edge + 1);
+        }
+    }
+
+    #[test]
+    fn gradient_runs_from_low_to_high() {
+        let cb = colorbar();
+        let mask = cb.get_mask(&graph_limits()).unwrap();
+        assert_eq!(mask.first().unwrap().color, Colormap::Viridis.color_at(0.));
+        assert_eq!(mask.last().unwrap().color, Colormap::Viridis.color_at(1.));
+        assert_eq!(mask.first().unwrap().points[0].y, graph_limits().min().y);
+    }
+
+    #[test]
+    fn labels_span_value_range() {
+        let labels = colorbar().get_labels(&graph_limits()).unwrap();
+        assert_eq!(labels.len(), NUM_COLORBAR_TICKS as usize + 1);
+        assert_eq!(
+            labels.first().unwrap().pos().point().y,
+            graph_limits().min().y
+        );
+        assert_eq!(
+            labels.last().unwrap().pos().point().y,
+            graph_limits().max().y
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use super::common::Graphable;
+use rgb::RGB8;
+
+/// Continuous colormaps that map a normalized value in [0, 1] to a color. The sequential maps
+/// (viridis, magma, inferno, plasma, cividis) are perceptually uniform; turbo is a rainbow-style
+/// map with improved uniformity; coolwarm and RdBu are diverging maps centered on 0.5.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    Cividis,
+    Turbo,
+    Coolwarm,
+    RdBu,
+}
+
+// control points are evenly spaced over [0, 1]; values in between are linearly interpolated
+const VIRIDIS: &[RGB8] = &[
+    RGB8::new(68, 1, 84),
+    RGB8::new(71, 45, 123),
+    RGB8::new(59, 82, 139),
+    RGB8::new(44, 114, 142),
+    RGB8::new(33, 145, 140),
+    RGB8::new(40, 174, 128),
+    RGB8::new(94, 201, 98),
+    RGB8::new(173, 220, 48),
+    RGB8::new(253, 231, 37),
+];
+
+const MAGMA: &[RGB8] = &[
+    RGB8::new(0, 0, 4),
+    RGB8::new(28, 16, 68),
+    RGB8::new(79, 18, 123),
+    RGB8::new(129, 37, 129),
+    RGB8::new(181, 54, 122),
+    RGB8::new(229, 80, 100),
+    RGB8::new(251, 135, 97),
+    RGB8::new(254, 194, 135),
+    RGB8::new(252, 253, 191),
+];
+
+const INFERNO: &[RGB8] = &[
+    RGB8::new(0, 0, 4),
+    RGB8::new(31, 12, 72),
+    RGB8::new(85, 15, 109),
+    RGB8::new(136, 34, 106),
+    RGB8::new(186, 54, 85),
+    RGB8::new(227, 89, 51),
+    RGB8::new(249, 142, 9),
+    RGB8::new(249, 203, 53),
+    RGB8::new(252, 255, 164),
+];
+
+const PLASMA: &[RGB8] = &[
+    RGB8::new(13, 8, 135),
+    RGB8::new(75, 3, 161),
+    RGB8::new(125, 3, 168),
+    RGB8::new(168, 34, 150),
+    RGB8::new(203, 70, 121),
+    RGB8::new(229, 107, 93),
+    RGB8::new(248, 148, 65),
+    RGB8::new(253, 195, 40),
+    RGB8::new(240, 249, 33),
+];
+
+const CIVIDIS: &[RGB8] = &[
+    RGB8::new(0, 34, 78),
+    RGB8::new(18, 53, 112),
+    RGB8::new(59, 73, 108),
+    RGB8::new(87, 93, 109),
+    RGB8::new(112, 113, 115),
+    RGB8::new(138, 134, 120),
+    RGB8::new(165, 156, 116),
+    RGB8::new(195, 179, 105),
+    RGB8::new(254, 232, 56),
+];
+
+const TURBO: &[RGB8] = &[
+    RGB8::new(48, 18, 59),
+    RGB8::new(70, 107, 227),
+    RGB8::new(41, 187, 236),
+    RGB8::new(49, 242, 153),
+    RGB8::new(162, 252, 60),
+    RGB8::new(237, 208, 58),
+    RGB8::new(251, 128, 34),
+    RGB8::new(203, 42, 4),
+    RGB8::new(122, 4, 3),
+];
+
+const COOLWARM: &[RGB8] = &[
+    RGB8::new(59, 76, 192),
+    RGB8::new(98, 130, 234),
+    RGB8::new(141, 176, 254),
+    RGB8::new(184, 208, 249),
+    RGB8::new(221, 221, 221),
+    RGB8::new(245, 196, 173),
+    RGB8::new(244, 154, 123),
+    RGB8::new(222, 96, 77),
+    RGB8::new(180, 4, 38),
+];
+
+const RD_BU: &[RGB8] = &[
+    RGB8::new(103, 0, 31),
+    RGB8::new(178, 24, 43),
+    RGB8::new(214, 96, 77),
+    RGB8::new(244, 165, 130),
+    RGB8::new(253, 219, 199),
+    RGB8::new(247, 247, 247),
+    RGB8::new(209, 229, 240),
+    RGB8::new(146, 197, 222),
+    RGB8::new(67, 147, 195),
+    RGB8::new(33, 102, 172),
+    RGB8::new(5, 48, 97),
+];
+
+const COLORMAP_TABLE: &[(&str, Colormap)] = &[
+    ("VIRIDIS", Colormap::Viridis),
+    ("MAGMA", Colormap::Magma),
+    ("INFERNO", Colormap::Inferno),
+    ("PLASMA", Colormap::Plasma),
+    ("CIVIDIS", Colormap::Cividis),
+    ("TURBO", Colormap::Turbo),
+    ("COOLWARM", Colormap::Coolwarm),
+    ("RDBU", Colormap::RdBu),
+];
+
+impl Colormap {
+    fn control_points(&self) -> &'static [RGB8] {
+        match self {
+            Colormap::Viridis => VIRIDIS,
+            Colormap::Magma => MAGMA,
+            Colormap::Inferno => INFERNO,
+            Colormap::Plasma => PLASMA,
+            Colormap::Cividis => CIVIDIS,
+            Colormap::Turbo => TURBO,
+            Colormap::Coolwarm => COOLWARM,
+            Colormap::RdBu => RD_BU,
+        }
+    }
+
+    /// Gets the color for a normalized value. Values outside [0, 1] are clamped to the ends of
+    /// the map; NaN maps to the low end.
+    pub fn color_at(&self, t: f64) -> RGB8 {
+        let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) };
+        let points = self.control_points();
+
+        let pos = t * (points.len() - 1) as f64;
+        let lower = pos.floor() as usize;
+        let upper = usize::min(lower + 1, points.len() - 1);
+        let frac = pos - lower as f64;
+
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+        let (a, b) = (points[lower], points[upper]);
+        RGB8::new(lerp(a.r, b.r), lerp(a.g, b.g), lerp(a.b, b.b))
+    }
+
+    /// Gets the color for a value within the range [min, max]. If the range is empty, the center
+    /// of the map is used.
+    pub fn color_for<T: Graphable>(&self, value: T, min: T, max: T) -> RGB8 {
+        let value: f64 = value.into();
+        let min: f64 = min.into();
+        let max: f64 = max.into();
+
+        let span = max - min;
+        if span == 0. {
+            return self.color_at(0.5);
+        }
+        self.color_at((value - min) / span)
+    }
+
+    /// Samples the map at evenly spaced points (including both ends) to produce a palette of
+    /// distinct colors, e.g. for styling many series.
+    pub fn palette(&self, num_colors: usize) -> Vec<RGB8> {
+        match num_colors {
+            0 => Vec::new(),
+            1 => vec![self.color_at(0.5)],
+            n => (0..n)
+                .map(|i| self.color_at(i as f64 / (n - 1) as f64))
+                .collect(),
+        }
+    }
+
+    /// Case-insensitive colormap name lookup. Accepts names like "viridis", "RdBu", etc.
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        let upper = name.to_ascii_uppercase();
+        COLORMAP_TABLE
+            .iter()
+            .find(|(n, _)| *n == upper)
+            .map(|(_, c)| *c)
+    }
+
+    /// Returns all available colormap names (uppercase).
+    pub fn all_names() -> &'static [(&'static str, Colormap)] {
+        COLORMAP_TABLE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_at_endpoints_match_control_points() {
+        for (_, cmap) in Colormap::all_names() {
+            let points = cmap.control_points();
+            assert_eq!(cmap.color_at(0.), points[0]);
+            assert_eq!(cmap.color_at(1.), points[points.len() - 1]);
+        }
+    }
+
+    #[test]
+    fn color_at_interpolates_between_control_points() {
+        // halfway between the first two viridis control points
+        let t = 0.5 / (VIRIDIS.len() - 1) as f64;
+        assert_eq!(Colormap::Viridis.color_at(t), RGB8::new(70, 23, 104));
+    }
+
+    #[test]
+    fn color_at_clamps_out_of_range_values() {
+        assert_eq!(Colormap::Magma.color_at(-5.), Colormap::Magma.color_at(0.));
+        assert_eq!(Colormap::Magma.color_at(5.), Colormap::Magma.color_at(1.));
+        assert_eq!(
+            Colormap::Magma.color_at(f64::NAN),
+            Colormap::Magma.color_at(0.)
+        );
+    }
+
+    #[test]
+    fn diverging_maps_are_neutral_at_center() {
+        assert_eq!(Colormap::RdBu.color_at(0.5), RGB8::new(247, 247, 247));
+        assert_eq!(Colormap::Coolwarm.color_at(0.5), RGB8::new(221, 221, 221));
+    }
+
+    #[test]
+    fn color_for_normalizes_value() {
+        let cmap = Colormap::Plasma;
+        assert_eq!(cmap.color_for(15, 10, 20), cmap.color_at(0.5));
+        assert_eq!(cmap.color_for(10., 10., 20.), cmap.color_at(0.));
+        assert_eq!(cmap.color_for(3, 3, 3), cmap.color_at(0.5));
+    }
+
+    #[test]
+    fn palette_has_requested_size_and_spans_map() {
+        let palette = Colormap::Turbo.palette(5);
+        assert_eq!(palette.len(), 5);
+        assert_eq!(palette[0], Colormap::Turbo.color_at(0.));
+        assert_eq!(palette[4], Colormap::Turbo.color_at(1.));
+        assert!(Colormap::Turbo.palette(0).is_empty());
+        assert_eq!(
+            Colormap::Turbo.palette(1),
+            vec![Colormap::Turbo.color_at(0.5)]
+        );
+    }
+
+    #[test]
+    fn from_name_case_insensitive() {
+        assert_eq!(Colormap::from_name("viridis"), Some(Colormap::Viridis));
+        assert_eq!(Colormap::from_name("RdBu"), Some(Colormap::RdBu));
+        assert_eq!(Colormap::from_name("COOLWARM"), Some(Colormap::Coolwarm));
+        assert_eq!(Colormap::from_name("jet"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::common::Result;
 use rgb::RGB8;
 
 pub const MAROON: RGB8 = RGB8::new(128, 0, 0);
@@ -296,6 +297,165 @@ pub fn all_names() -> &'static [(&'static str, RGB8)] {
     COLOR_TABLE
 }
 
+// the first 16 colors of the 256-color palette, as xterm draws them by default
+const ANSI_COLORS: [RGB8; 16] = [
+    RGB8::new(0, 0, 0),
+    RGB8::new(205, 0, 0),
+    RGB8::new(0, 205, 0),
+    RGB8::new(205, 205, 0),
+    RGB8::new(0, 0, 238),
+    RGB8::new(205, 0, 205),
+    RGB8::new(0, 205, 205),
+    RGB8::new(229, 229, 229),
+    RGB8::new(127, 127, 127),
+    RGB8::new(255, 0, 0),
+    RGB8::new(0, 255, 0),
+    RGB8::new(255, 255, 0),
+    RGB8::new(92, 92, 255),
+    RGB8::new(255, 0, 255),
+    RGB8::new(0, 255, 255),
+    RGB8::new(255, 255, 255),
+];
+
+// channel levels of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Gets a color of the xterm 256-color palette: the 16 ANSI colors, a 6x6x6 color cube, then 24
+/// shades of gray.
+pub fn from_ansi256(index: u8) -> RGB8 {
+    match index {
+        0..=15 => ANSI_COLORS[index as usize],
+        16..=231 => {
+            let i = (index - 16) as usize;
+            RGB8::new(
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        232..=255 => {
+            let level = 8 + 10 * (index - 232);
+            RGB8::new(level, level, level)
+        }
+    }
+}
+
+/// Parses a color specification, which is one of:
+///
+/// - a name, e.g. "Blue" or "dark_red" (see [`all_names`])
+/// - a hex code, "#rrggbb" or the short form "#rgb"
+/// - "rgb(r, g, b)", with channels from 0 to 255
+/// - "hsl(h, s%, l%)", with the hue in degrees and the saturation and lightness from 0% to 100%
+/// - an index into the 256-color palette from 0 to 255, e.g. "208" (see [`from_ansi256`])
+///
+/// Errors describe what is wrong with the specification.
+pub fn parse(spec: &str) -> Result<RGB8> {
+    let spec = spec.trim();
+    let lower = spec.to_ascii_lowercase();
+
+    if let Some(hex) = spec.strip_prefix('#') {
+        return parse_hex(hex)
+            .ok_or_else(|| format!("Invalid hex color '{spec}'; expected #rrggbb or #rgb").into());
+    }
+    if let Some(args) = function_args(&lower, "rgb") {
+        let channels = args
+            .iter()
+            .map(|c| c.parse::<u8>().ok())
+            .collect::<Option<Vec<_>>>();
+        return match channels.as_deref() {
+            Some(&[r, g, b]) => Ok(RGB8::new(r, g, b)),
+            _ => Err(format!(
+                "Invalid rgb color '{spec}'; expected rgb(r, g, b) with channels from 0 to 255"
+            )
+            .into()),
+        };
+    }
+    if let Some(args) = function_args(&lower, "hsl") {
+        return parse_hsl(&args).ok_or_else(|| {
+            format!(
+                "Invalid hsl color '{spec}'; expected hsl(h, s%, l%) with the hue in degrees and \
+                 the saturation and lightness from 0% to 100%"
+            )
+            .into()
+        });
+    }
+    if !spec.is_empty() && spec.chars().all(|c| c.is_ascii_digit()) {
+        return match spec.parse::<u8>() {
+            Ok(index) => Ok(from_ansi256(index)),
+            Err(_) => {
+                Err(format!("Color index '{spec}' is out of range; expected 0 to 255").into())
+            }
+        };
+    }
+    from_name(spec).ok_or_else(|| {
+        format!(
+            "Unknown color '{spec}'; expected a color name, #rrggbb, #rgb, rgb(r, g, b), \
+             hsl(h, s%, l%) or a 256-color index"
+        )
+        .into()
+    })
+}
+
+fn parse_hex(hex: &str) -> Option<RGB8> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        6 => Some(RGB8::new(
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        // each digit is repeated, so #f80 is #ff8800
+        3 => {
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|c| c * 17);
+            Some(RGB8::new(short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+/// Gets the comma-separated arguments of a function-style color, e.g. "rgb(1, 2, 3)".
+fn function_args<'a>(spec: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let args = spec
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(args.split(',').map(str::trim).collect())
+}
+
+fn parse_hsl(args: &[&str]) -> Option<RGB8> {
+    let [hue, saturation, lightness] = args else {
+        return None;
+    };
+    let hue = hue.trim_end_matches("deg").parse::<f64>().ok()?;
+    let percent = |value: &str| {
+        let value = value.trim_end_matches('%').trim().parse::<f64>().ok()?;
+        (0. ..=100.).contains(&value).then_some(value / 100.)
+    };
+    let (saturation, lightness) = (percent(saturation)?, percent(lightness)?);
+    if !hue.is_finite() {
+        return None;
+    }
+
+    let chroma = (1. - (2. * lightness - 1.).abs()) * saturation;
+    let sector = hue.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (sector % 2. - 1.).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+    let m = lightness - chroma / 2.;
+    let channel = |c: f64| ((c + m) * 255.).round() as u8;
+    Some(RGB8::new(channel(r), channel(g), channel(b)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +481,44 @@ mod tests {
         assert_eq!(from_name(""), None);
     }
 
+    #[test]
+    fn parse_accepts_every_format() {
+        assert_eq!(parse("Dark_Red").unwrap(), DARK_RED);
+        assert_eq!(parse("#1E90ff").unwrap(), DODGER_BLUE);
+        assert_eq!(parse("#f80").unwrap(), RGB8::new(255, 136, 0));
+        assert_eq!(parse("rgb(30, 144, 255)").unwrap(), DODGER_BLUE);
+        assert_eq!(parse(" RGB(0,0,0) ").unwrap(), BLACK);
+        assert_eq!(parse("hsl(0, 100%, 50%)").unwrap(), RED);
+        assert_eq!(parse("hsl(120, 100%, 25%)").unwrap(), RGB8::new(0, 128, 0));
+        assert_eq!(parse("hsl(-120deg, 100, 50)").unwrap(), BLUE);
+        assert_eq!(parse("hsl(0, 0%, 100%)").unwrap(), WHITE);
+        assert_eq!(parse("9").unwrap(), RED);
+        assert_eq!(parse("208").unwrap(), RGB8::new(255, 135, 0));
+    }
+
+    #[test]
+    fn parse_errors_describe_the_problem() {
+        let err = |spec: &str| parse(spec).unwrap_err().to_string();
+        assert!(err("#12345").contains("expected #rrggbb or #rgb"));
+        assert!(err("#ggg").contains("Invalid hex color"));
+        assert!(err("rgb(1, 2)").contains("channels from 0 to 255"));
+        assert!(err("rgb(1, 2, 256)").contains("Invalid rgb color"));
+        assert!(err("hsl(0, 120%, 50%)").contains("Invalid hsl color"));
+        assert!(err("256").contains("out of range"));
+        assert!(err("Gren").contains("Unknown color 'Gren'"));
+        assert!(err("").contains("Unknown color"));
+    }
+
+    #[test]
+    fn ansi256_palette_has_cube_and_grays() {
+        assert_eq!(from_ansi256(0), BLACK);
+        assert_eq!(from_ansi256(16), BLACK);
+        assert_eq!(from_ansi256(196), RED);
+        assert_eq!(from_ansi256(231), WHITE);
+        assert_eq!(from_ansi256(232), RGB8::new(8, 8, 8));
+        assert_eq!(from_ansi256(255), RGB8::new(238, 238, 238));
+    }
+
     #[test]
     fn all_names_is_nonempty() {
         assert!(all_names().len() > 100);
@@ -42,8 +42,64 @@ pub struct MaskPoints {
     pub color: RGB8,
 }
 
+/// Pixels drawn with partial coverage, e.g. anti-aliased text, which are blended with whatever
+/// has already been drawn. An alpha of 255 replaces the existing color.
+#[derive(Debug)]
+pub struct AlphaMaskPoints {
+    pub points: Vec<(Point<u32>, u8)>,
+    pub color: RGB8,
+}
+
+/// Destination for drawn pixels, e.g. a canvas. Drawables write their pixels straight into a
+/// sink as they rasterize them, so that large plots never hold all of their pixels at once.
+/// Pixels outside of the sink are ignored.
+pub trait PixelSink {
+    fn set_pixel(&mut self, point: &Point<u32>, color: &RGB8);
+
+    /// Sets the pixels of row `y` from `x_min` to `x_max` (inclusive).
+    fn set_span(&mut self, y: u32, x_min: u32, x_max: u32, color: &RGB8) {
+        for x in x_min..=x_max {
+            self.set_pixel(&Point::new(x, y), color);
+        }
+    }
+
+    /// Sets the pixels of the rectangle between two corners (inclusive).
+    fn set_rect(&mut self, min: &Point<u32>, max: &Point<u32>, color: &RGB8) {
+        for y in min.y..=max.y {
+            self.set_span(y, min.x, max.x, color);
+        }
+    }
+
+    fn set_pixels(&mut self, points: &[Point<u32>], color: &RGB8) {
+        for point in points {
+            self.set_pixel(point, color);
+        }
+    }
+}
+
+/// Collects drawn pixels into masks, starting a new mask whenever the color changes.
+impl PixelSink for Vec<MaskPoints> {
+    fn set_pixel(&mut self, point: &Point<u32>, color: &RGB8) {
+        match self.last_mut() {
+            Some(mask) if mask.color == *color => mask.points.push(*point),
+            _ => self.push(MaskPoints {
+                points: vec![*point],
+                color: *color,
+            }),
+        }
+    }
+}
+
 pub trait Drawable {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>>;
+    /// Draws the pixels into the sink, with later pixels drawn over earlier ones.
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()>;
+
+    /// Gets the drawn pixels as masks, e.g. to inspect them before drawing.
+    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(&mut masks)?;
+        Ok(masks)
+    }
 }
 
 pub trait Convertable<U> {
@@ -112,3 +168,10 @@ where
 {
     fn shift_by(self, amount: Point<T>) -> Self;
 }
+
+/// Scales a size measured out from a center pixel (i.e. spanning `2 * size + 1` pixels, like line
+/// thicknesses and marker sizes) for drawing at `factor` times the resolution, so that it spans
+/// about the same distance.
+pub(crate) fn magnify(size: u32, factor: u32) -> u32 {
+    (2 * size + 1) * factor / 2
+}
@@ -0,0 +1,478 @@
+use super::{
+    colormaps::Colormap,
+    common::{
+        Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable, magnify,
+    },
+    limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    point::Point,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+const DEFAULT_NUM_LEVELS: usize = 8;
+// pixels of line left out around each level label
+const LABEL_GAP: u32 = 1;
+
+/// Iso-line of a contour plot: every segment where the grid crosses one value.
+#[derive(Debug, Clone)]
+pub struct ContourLevel<T: Graphable> {
+    value: f64,
+    segments: Vec<(Point<T>, Point<T>)>,
+}
+
+impl<T: Graphable> ContourLevel<T> {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn segments(&self) -> &[(Point<T>, Point<T>)] {
+        &self.segments
+    }
+
+    fn map_points<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> ContourLevel<U> {
+        ContourLevel {
+            value: self.value,
+            segments: self
+                .segments
+                .iter()
+                .map(|&(a, b)| (map_fn(a), map_fn(b)))
+                .collect(),
+        }
+    }
+}
+
+/// Scalar values sampled on a rectangular grid: `z[row][col]` is the value at `(x[col], y[row])`.
+#[derive(Debug, Clone)]
+struct Grid {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<Vec<f64>>,
+}
+
+impl Grid {
+    fn value_range(&self) -> Option<(f64, f64)> {
+        let values = self.z.iter().flatten().filter(|v| v.is_finite());
+        let min = values.clone().copied().reduce(f64::min)?;
+        let max = values.copied().reduce(f64::max)?;
+        Some((min, max))
+    }
+
+    /// Traces the iso-line at one level with marching squares. Cells with a non-finite corner
+    /// are skipped, and saddle cells are resolved using the average of their corners.
+    fn trace(&self, level: f64) -> Vec<(Point<f64>, Point<f64>)> {
+        let mut segments = Vec::new();
+        for row in 0..self.y.len() - 1 {
+            for col in 0..self.x.len() - 1 {
+                // corners counter-clockwise from the bottom left
+                let corners = [
+                    (col, row),
+                    (col + 1, row),
+                    (col + 1, row + 1),
+                    (col, row + 1),
+                ]
+                .map(|(c, r)| (Point::new(self.x[c], self.y[r]), self.z[r][c]));
+                if corners.iter().any(|(_, z)| !z.is_finite()) {
+                    continue;
+                }
+
+                // point where the level crosses the edge from corner a to corner b
+                let crossing = |a: usize, b: usize| {
+                    let ((pa, za), (pb, zb)) = (corners[a], corners[b]);
+                    let t = (level - za) / (zb - za);
+                    pa + (pb - pa) * t
+                };
+                let above = corners.map(|(_, z)| z >= level);
+                let case = above
+                    .iter()
+                    .enumerate()
+                    .fold(0, |case, (i, &a)| case | ((a as usize) << i));
+
+                // edges are numbered by their first corner: 0 bottom, 1 right, 2 top, 3 left
+                let edge = |e: usize| crossing(e, (e + 1) % 4);
+                let center_above = corners.iter().map(|(_, z)| z).sum::<f64>() / 4. >= level;
+                let pairs: &[(usize, usize)] = match case {
+                    0 | 15 => &[],
+                    1 | 14 => &[(3, 0)],
+                    2 | 13 => &[(0, 1)],
+                    3 | 12 => &[(3, 1)],
+                    4 | 11 => &[(1, 2)],
+                    6 | 9 => &[(0, 2)],
+                    7 | 8 => &[(2, 3)],
+                    // saddles: the center decides which pairs of corners are connected
+                    5 if center_above => &[(3, 2), (0, 1)],
+                    5 => &[(3, 0), (1, 2)],
+                    10 if center_above => &[(3, 0), (1, 2)],
+                    10 => &[(3, 2), (0, 1)],
+                    _ => unreachable!(),
+                };
+                // a level running exactly through a corner gives zero-length segments
+                segments.extend(
+                    pairs
+                        .iter()
+                        .map(|&(a, b)| (edge(a), edge(b)))
+                        .filter(|(a, b)| a != b),
+                );
+            }
+        }
+        segments
+    }
+}
+
+/// Contour plot of a scalar grid, drawn as iso-lines colored by their level. Levels are either
+/// chosen automatically, evenly spaced inside the range of the data, or set explicitly.
+#[derive(Debug, Clone)]
+pub struct Contour<T: Graphable> {
+    grid: Grid,
+    levels: Vec<ContourLevel<T>>,
+    colormap: Colormap,
+    thickness: u32,
+    labels: Option<TextStyle>,
+    limits: Limits<T>,
+    // labels keep their size when the lines are magnified, so the gaps around them must grow
+    label_scale: u32,
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for Contour<T> {
+    type ConvertTo = Contour<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        Contour {
+            grid: self.grid.clone(),
+            levels: self
+                .levels
+                .iter()
+                .map(|level| level.map_points(|p| p.convert_to(convert_fn)))
+                .collect(),
+            colormap: self.colormap,
+            thickness: self.thickness,
+            labels: self.labels.clone(),
+            limits: self.limits.convert_to(convert_fn),
+            label_scale: self.label_scale,
+        }
+    }
+}
+
+impl Contour<f64> {
+    /// Creates a contour plot of `z`, where `z[row][col]` is the value at `(x[col], y[row])`.
+    /// Non-finite values leave a gap in the lines. Panics if the grid is smaller than 2x2, the
+    /// shapes do not match, the coordinates are not strictly increasing, or no value is finite.
+    pub fn new(x: &[f64], y: &[f64], z: &[Vec<f64>]) -> Contour<f64> {
+        if x.len() < 2 || y.len() < 2 {
+            panic!(
+                "Contour grid must be at least 2x2, got {}x{}",
+                x.len(),
+                y.len()
+            );
+        }
+        if z.len() != y.len() || z.iter().any(|row| row.len() != x.len()) {
+            panic!(
+                "Contour values must have {} rows of {} columns",
+                y.len(),
+                x.len()
+            );
+        }
+        for (name, coords) in [("x", x), ("y", y)] {
+            let increasing = coords.windows(2).all(|pair| pair[0] < pair[1]);
+            if !increasing || coords.iter().any(|v| !v.is_finite()) {
+                panic!("Contour {name} coordinates must be finite and strictly increasing");
+            }
+        }
+
+        let grid = Grid {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            z: z.to_vec(),
+        };
+        if grid.value_range().is_none() {
+            panic!("Contour values must contain at least one finite value");
+        }
+        let limits = Limits::new(
+            Point::new(x[0], y[0]),
+            Point::new(x[x.len() - 1], y[y.len() - 1]),
+        );
+
+        Contour {
+            grid,
+            levels: Vec::new(),
+            colormap: Colormap::Viridis,
+            thickness: 0,
+            labels: None,
+            limits,
+            label_scale: 1,
+        }
+        .with_num_levels(DEFAULT_NUM_LEVELS)
+    }
+
+    /// Draws iso-lines at the given values.
+    pub fn with_levels(mut self, values: &[f64]) -> Self {
+        if values.iter().any(|v| !v.is_finite()) {
+            panic!("Contour levels must be finite, got {values:?}");
+        }
+        let mut values = values.to_vec();
+        values.sort_by(f64::total_cmp);
+        values.dedup();
+
+        self.levels = values
+            .into_iter()
+            .map(|value| ContourLevel {
+                value,
+                segments: self.grid.trace(value),
+            })
+            .collect();
+        self
+    }
+
+    /// Draws iso-lines at the given number of values, evenly spaced strictly between the smallest
+    /// and largest value of the grid.
+    pub fn with_num_levels(self, num_levels: usize) -> Self {
+        if num_levels == 0 {
+            panic!("Contour plot must have at least one level");
+        }
+        let (min, max) = self.grid.value_range().unwrap();
+        let step = (max - min) / (num_levels + 1) as f64;
+        let values = (1..=num_levels)
+            .map(|i| min + step * i as f64)
+            .collect::<Vec<_>>();
+        self.with_levels(&values)
+    }
+}
+
+impl<T: Graphable> Contour<T> {
+    /// Colors each level by its position between the lowest and highest level.
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Gets the plot for drawing at `factor` times the resolution, with the line thickness
+    /// scaled to cover about the same width.
+    pub(crate) fn magnified(&self, factor: u32) -> Contour<T> {
+        let mut contour = self.clone();
+        contour.thickness = magnify(self.thickness, factor);
+        contour.label_scale = self.label_scale * factor;
+        contour
+    }
+
+    /// Labels each level with its value, leaving a gap in the line under the label.
+    pub fn with_labels(mut self, style: TextStyle) -> Self {
+        self.labels = Some(style);
+        self
+    }
+
+    pub fn levels(&self) -> &[ContourLevel<T>] {
+        &self.levels
+    }
+
+    pub fn colormap(&self) -> Colormap {
+        self.colormap
+    }
+
+    /// Gets the limits of the grid.
+    pub fn limits(&self) -> &Limits<T> {
+        &self.limits
+    }
+
+    /// Gets the lowest and highest level, e.g. for a matching [`Colorbar`].
+    ///
+    /// [`Colorbar`]: super::colorbar::Colorbar
+    pub fn level_range(&self) -> Option<(f64, f64)> {
+        let first = self.levels.first()?.value;
+        let last = self.levels.last()?.value;
+        Some((first, last))
+    }
+
+    pub fn level_color(&self, value: f64) -> RGB8 {
+        match self.level_range() {
+            Some((min, max)) if min < max => self.colormap.color_for(value, min, max),
+            _ => self.colormap.color_at(0.5),
+        }
+    }
+}
+
+impl Contour<f64> {
+    /// Gets a label for each level that crosses the grid, placed on the segment in the middle
+    /// of the level from left to right. Only meaningful once the plot is scaled to pixels.
+    pub fn get_labels(&self) -> Result<Vec<Label>> {
+        let Some(style) = &self.labels else {
+            return Ok(Vec::new());
+        };
+        let labels = self
+            .levels
+            .iter()
+            .filter_map(|level| {
+                let mut midpoints = level
+                    .segments
+                    .iter()
+                    .map(|&(a, b)| (a + b) / 2.)
+                    .collect::<Vec<_>>();
+                midpoints.sort_by(|a, b| a.x.total_cmp(&b.x));
+                let pos = midpoints.get(midpoints.len() / 2)?.round();
+                let txt = Text::from_number(level.value, 3, style.clone());
+                Some(Label::new(txt, TextPositioning::Centered(pos)))
+            })
+            .collect();
+        Ok(labels)
+    }
+}
+
+impl<T: Graphable> Drawable for Contour<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let contour = self.convert_to_f64();
+        // the center and half size of the area kept clear around each label
+        let scale = contour.label_scale as f64;
+        let label_areas = contour
+            .get_labels()?
+            .iter()
+            .map(|label| {
+                let limits = label.limits().convert_to_f64();
+                let center = (*limits.min() + *limits.max()) / 2.;
+                let half_size = ((*limits.max() - *limits.min()) / 2. + LABEL_GAP as f64) * scale;
+                (center, half_size)
+            })
+            .collect::<Vec<_>>();
+        // leave out segments that would run through a label
+        let under_label = |p: &Point<u32>| {
+            label_areas.iter().any(|(center, half_size)| {
+                (p.x as f64 - center.x).abs() <= half_size.x
+                    && (p.y as f64 - center.y).abs() <= half_size.y
+            })
+        };
+
+        for level in &contour.levels {
+            let style = LineStyle::Solid {
+                color: contour.level_color(level.value),
+                thickness: contour.thickness,
+            };
+            for &(a, b) in &level.segments {
+                let (start, end) = (a.round(), b.round());
+                if under_label(&start) || under_label(&end) {
+                    continue;
+                }
+                let pos = LinePositioning::BetweenPoints { start, end };
+                Line::new(pos, style).draw(sink)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, U> Scalable<T, U> for Contour<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = Contour<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        Contour {
+            grid: self.grid.clone(),
+            levels: self
+                .levels
+                .iter()
+                .map(|level| level.map_points(|p| p.scale_to(old_limits, new_limits)))
+                .collect(),
+            colormap: self.colormap,
+            thickness: self.thickness,
+            labels: self.labels.clone(),
+            limits: self.limits.scale_to(old_limits, new_limits),
+            label_scale: self.label_scale,
+        }
+    }
+}
+
+impl<T> Shiftable<T> for Contour<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(mut self, amount: Point<T>) -> Self {
+        self.levels = self
+            .levels
+            .iter()
+            .map(|level| level.map_points(|p| p + amount))
+            .collect();
+        self.limits = self.limits.shift_by(amount);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a cone peaking at the center of a 5x5 grid
+    fn cone() -> Contour<f64> {
+        let coords = [-2., -1., 0., 1., 2.];
+        let z = coords
+            .iter()
+            .map(|y: &f64| {
+                coords
+                    .iter()
+                    .map(|x: &f64| 4. - x.abs() - y.abs())
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        Contour::new(&coords, &coords, &z)
+    }
+
+    #[test]
+    fn automatic_levels_are_inside_the_data_range() {
+        let values = cone()
+            .with_num_levels(3)
+            .levels()
+            .iter()
+            .map(|l| l.value())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1., 2., 3.]);
+    }
+
+    #[test]
+    fn segments_lie_on_the_level() {
+        let contour = cone().with_levels(&[3.]);
+        let segments = contour.levels()[0].segments();
+        // the level 3 line is the diamond |x| + |y| = 1, crossing each of the 4 cells at the
+        // center once
+        assert_eq!(segments.len(), 4);
+        for &(a, b) in segments {
+            for p in [a, b] {
+                assert_eq!(p.x.abs() + p.y.abs(), 1.);
+            }
+        }
+    }
+
+    #[test]
+    fn saddle_cells_use_the_center_value() {
+        let z = vec![vec![1., 0.], vec![0., 1.]];
+        let contour = Contour::new(&[0., 1.], &[0., 1.], &z).with_levels(&[0.5]);
+        let segments = contour.levels()[0].segments();
+        assert_eq!(segments.len(), 2);
+
+        // the center (0.5) counts as above the level, so the high corners are joined and the
+        // lines cut off the low corners
+        let cuts_corner = |corner: Point<f64>| {
+            segments
+                .iter()
+                .any(|&(a, b)| a.dist(&corner) == 0.5 && b.dist(&corner) == 0.5)
+        };
+        assert!(cuts_corner(Point::new(1., 0.)));
+        assert!(cuts_corner(Point::new(0., 1.)));
+    }
+
+    #[test]
+    fn non_finite_cells_are_skipped() {
+        let z = vec![vec![0., 1., 2.], vec![0., f64::NAN, 2.]];
+        let contour = Contour::new(&[0., 1., 2.], &[0., 1.], &z).with_levels(&[0.5, 1.5]);
+        assert!(contour.levels().iter().all(|l| l.segments().is_empty()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_shape_panics() {
+        Contour::new(&[0., 1.], &[0., 1.], &[vec![0., 1.]]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::common::Result;
+use std::{fmt, path::Path, sync::Arc};
+
+const BUNDLED_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono.ttf");
+const DEFAULT_PX_SIZE: f32 = 14.;
+
+/// TrueType/OpenType font used to draw text with anti-aliasing at any pixel size, in place of the
+/// built-in bitmap font. The pixel size is the height of the font's em square; lines of text are
+/// a little taller, to fit accents and descenders.
+#[derive(Clone)]
+pub struct Font {
+    font: Arc<fontdue::Font>,
+    px_size: f32,
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("name", &self.font.name())
+            .field("px_size", &self.px_size)
+            .finish()
+    }
+}
+
+impl Font {
+    /// Gets the font bundled with termplt (DejaVu Sans Mono).
+    pub fn bundled() -> Font {
+        Font::from_bytes(BUNDLED_FONT).expect("Bundled font must be valid")
+    }
+
+    /// Parses a TTF or OTF font from its file contents.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Font> {
+        let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())?;
+        if font.horizontal_line_metrics(1.).is_none() {
+            return Err("Font has no horizontal line metrics".into());
+        }
+        Ok(Font {
+            font: Arc::new(font),
+            px_size: DEFAULT_PX_SIZE,
+        })
+    }
+
+    /// Loads a TTF or OTF font from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Font> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("Could not read font '{}': {e}", path.display()))?;
+        Font::from_bytes(&bytes)
+    }
+
+    /// Sets the pixel size of the font. Panics unless the size is positive.
+    pub fn with_px_size(mut self, px_size: f32) -> Self {
+        if px_size.is_nan() || px_size <= 0. {
+            panic!("Font size must be positive, got {px_size}");
+        }
+        self.px_size = px_size;
+        self
+    }
+
+    /// Sizes the font so that a line of text is as tall as a row of the terminal, e.g. the
+    /// `pix_per_row` reported by [`get_window_size`], matching the terminal's own font.
+    ///
+    /// [`get_window_size`]: crate::get_window_size
+    pub fn with_row_height(self, pix_per_row: u32) -> Self {
+        let line_height = self.line_height(1.);
+        self.with_px_size(pix_per_row as f32 / line_height)
+    }
+
+    pub fn px_size(&self) -> f32 {
+        self.px_size
+    }
+
+    /// Checks whether the font has a glyph for a character.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.font.has_glyph(c)
+    }
+
+    fn line_height(&self, px_size: f32) -> f32 {
+        self.font
+            .horizontal_line_metrics(px_size)
+            .map(|metrics| metrics.new_line_size)
+            .expect("Font line metrics are checked when the font is loaded")
+    }
+
+    /// Gets the coverage of each pixel of a character, from 0 (empty) to 255 (fully covered),
+    /// with rows from the bottom up. Every character is as tall as a line of text, with the
+    /// baseline at the same height, and as wide as its advance, so that characters can be placed
+    /// side by side. The font size is multiplied by `scale`.
+    pub fn rasterize(&self, c: char, scale: usize) -> Vec<Vec<u8>> {
+        let px_size = self.px_size * scale as f32;
+        let line = self
+            .font
+            .horizontal_line_metrics(px_size)
+            .expect("Font line metrics are checked when the font is loaded");
+        let (metrics, glyph) = self.font.rasterize(c, px_size);
+
+        let height = line.new_line_size.ceil() as usize;
+        let width = usize::max(
+            metrics.advance_width.round() as usize,
+            (metrics.xmin.max(0) as usize) + metrics.width,
+        );
+        // the line gap is split evenly above and below the text
+        let baseline = (line.line_gap / 2. - line.descent).round() as i32;
+
+        let mut coverage = vec![vec![0; width]; height];
+        for (row, glyph_row) in glyph.chunks(metrics.width.max(1)).enumerate() {
+            // glyph rows are from the top down
+            let y = baseline + metrics.ymin + (metrics.height - 1 - row) as i32;
+            for (col, &value) in glyph_row.iter().enumerate() {
+                let x = metrics.xmin + col as i32;
+                if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y))
+                    && x < width
+                    && y < height
+                {
+                    coverage[y][x] = value;
+                }
+            }
+        }
+        coverage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_share_height_and_baseline() {
+        let font = Font::bundled().with_px_size(20.);
+        let underscore = font.rasterize('_', 1);
+        let letter = font.rasterize('x', 1);
+        assert_eq!(underscore.len(), letter.len());
+
+        // the underscore sits at or below the bottom of the 'x'
+        let lowest = |c: &Vec<Vec<u8>>| c.iter().position(|row| row.iter().any(|&v| v > 0));
+        assert!(lowest(&underscore).unwrap() <= lowest(&letter).unwrap());
+    }
+
+    #[test]
+    fn has_glyph_for_characters_beyond_the_bitmap_font() {
+        let font = Font::bundled();
+        assert!(font.has_glyph('é'));
+        assert!(font.has_glyph('µ'));
+        assert!(!font.has_glyph('\u{e000}'));
+    }
+
+    #[test]
+    fn edges_are_anti_aliased() {
+        let coverage = Font::bundled().with_px_size(20.).rasterize('o', 1);
+        let values = coverage.iter().flatten().copied().collect::<Vec<_>>();
+        assert!(values.iter().any(|&v| v > 200));
+        assert!(values.iter().any(|&v| v > 0 && v < 200));
+    }
+
+    #[test]
+    fn scale_multiplies_size() {
+        let font = Font::bundled().with_px_size(10.);
+        let small = font.rasterize('8', 1);
+        let large = font.rasterize('8', 2);
+        assert!(large.len() >= 2 * small.len() - 1);
+        assert!(large[0].len() >= 2 * small[0].len() - 1);
+    }
+
+    #[test]
+    fn row_height_sets_line_height() {
+        let font = Font::bundled().with_row_height(30);
+        assert_eq!(font.rasterize('a', 1).len(), 30);
+    }
+
+    #[test]
+    fn invalid_font_errors() {
+        assert!(Font::from_bytes(&[0, 1, 2, 3]).is_err());
+        assert!(Font::from_file("/nonexistent/font.ttf").is_err());
+    }
+}
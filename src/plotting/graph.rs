@@ -1,25 +1,70 @@
 use super::{
-    axes::Axes,
+    annotation::Annotation,
+    arrow::Arrow,
+    axes::{Axes, SecondaryYAxis},
+    boxplot::BoxPlot,
+    candlestick::Candlestick,
+    categories::Categories,
+    colorbar::Colorbar,
     common::{
-        Convertable, Drawable, FloatConvertable, Graphable, IntConvertable, MaskPoints, Scalable,
+        Convertable, Drawable, FloatConvertable, Graphable, IntConvertable, PixelSink, Scalable,
         Shiftable,
     },
+    contour::Contour,
     graph_limits::GraphLimits,
     grid_lines::GridLines,
+    legend::Legend,
     limits::Limits,
-    point::{Point, PointCollection},
-    series::Series,
-    text::Label,
+    line::LineStyle,
+    point::Point,
+    quiver::Quiver,
+    reference::Reference,
+    series::{Series, YAxis},
+    text::{Label, TextStyle},
 };
 use crate::common::Result;
+use rgb::RGB8;
+
+/// Gets the limits of the points in a single pass, without collecting them, since graphs can
+/// hold millions of points.
+fn points_limits<T: Graphable>(mut points: impl Iterator<Item = Point<T>>) -> Option<Limits<T>> {
+    let first = points.next()?;
+    let (min, max) = points.fold((first, first), |(mut min, mut max), p| {
+        if p.x < min.x {
+            min.x = p.x;
+        }
+        if p.y < min.y {
+            min.y = p.y;
+        }
+        if p.x > max.x {
+            max.x = p.x;
+        }
+        if p.y > max.y {
+            max.y = p.y;
+        }
+        (min, max)
+    });
+    Some(Limits::new(min, max))
+}
 
 // TODO: implement items like: grid lines, legends, etc.
 #[derive(Debug, Clone)]
 pub struct Graph<T: Graphable + FloatConvertable> {
     data: Vec<Series<T>>,
+    box_plots: Vec<BoxPlot<T>>,
+    candlesticks: Vec<Candlestick<T>>,
+    quivers: Vec<Quiver<T>>,
+    contours: Vec<Contour<T>>,
+    references: Vec<Reference<T>>,
+    annotations: Vec<Annotation<T>>,
     graph_limits: Option<GraphLimits<T>>,
     axes: Option<Axes>,
     grid_lines: Option<GridLines>,
+    colorbar: Option<Colorbar>,
+    x_categories: Option<Vec<String>>,
+    legend: Option<TextStyle>,
+    secondary_axis: Option<SecondaryYAxis>,
+    secondary_y_limits: Option<(T, T)>,
 }
 
 impl<T: Graphable, U: Graphable> Convertable<U> for Graph<T> {
@@ -31,6 +76,42 @@ impl<T: Graphable, U: Graphable> Convertable<U> for Graph<T> {
             .map(|series| series.convert_to(convert_fn))
             .collect::<Vec<_>>();
 
+        let box_plots = self
+            .box_plots
+            .iter()
+            .map(|box_plot| box_plot.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
+        let candlesticks = self
+            .candlesticks
+            .iter()
+            .map(|candlestick| candlestick.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
+        let quivers = self
+            .quivers
+            .iter()
+            .map(|quiver| quiver.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
+        let contours = self
+            .contours
+            .iter()
+            .map(|contour| contour.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
+        let references = self
+            .references
+            .iter()
+            .map(|reference| reference.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
+        let annotations = self
+            .annotations
+            .iter()
+            .map(|annotation| annotation.convert_to(convert_fn))
+            .collect::<Vec<_>>();
+
         let graph_limits = if let Some(value) = &self.graph_limits {
             Some(value.convert_to(convert_fn))
         } else {
@@ -39,12 +120,30 @@ impl<T: Graphable, U: Graphable> Convertable<U> for Graph<T> {
 
         let axes = self.axes.clone();
         let grid_lines = self.grid_lines.clone();
+        let colorbar = self.colorbar.clone();
+        let x_categories = self.x_categories.clone();
+        let legend = self.legend.clone();
+        let secondary_axis = self.secondary_axis.clone();
+        let secondary_y_limits = self
+            .secondary_y_limits
+            .map(|(min, max)| (min.convert_to(convert_fn), max.convert_to(convert_fn)));
 
         Graph {
             data,
+            box_plots,
+            candlesticks,
+            quivers,
+            contours,
+            references,
+            annotations,
             graph_limits,
             axes,
             grid_lines,
+            colorbar,
+            x_categories,
+            legend,
+            secondary_axis,
+            secondary_y_limits,
         }
     }
 }
@@ -53,9 +152,20 @@ impl<T: Graphable> Graph<T> {
     pub fn new() -> Graph<T> {
         Graph {
             data: vec![],
+            box_plots: vec![],
+            candlesticks: vec![],
+            quivers: vec![],
+            contours: vec![],
+            references: vec![],
+            annotations: vec![],
             graph_limits: None,
             axes: None,
             grid_lines: None,
+            colorbar: None,
+            x_categories: None,
+            legend: None,
+            secondary_axis: None,
+            secondary_y_limits: None,
         }
     }
 
@@ -64,6 +174,65 @@ impl<T: Graphable> Graph<T> {
         self
     }
 
+    pub fn with_box_plot(mut self, box_plot: BoxPlot<T>) -> Self {
+        self.box_plots.push(box_plot);
+        self
+    }
+
+    pub fn with_candlestick(mut self, candlestick: Candlestick<T>) -> Self {
+        self.candlesticks.push(candlestick);
+        self
+    }
+
+    pub fn with_quiver(mut self, quiver: Quiver<T>) -> Self {
+        self.quivers.push(quiver);
+        self
+    }
+
+    pub fn with_contour(mut self, contour: Contour<T>) -> Self {
+        self.contours.push(contour);
+        self
+    }
+
+    /// Adds a horizontal reference line at a y value, e.g. a threshold.
+    pub fn with_hline(mut self, y: T, style: LineStyle) -> Self {
+        self.references.push(Reference::HLine { y, style });
+        self
+    }
+
+    /// Adds a vertical reference line at an x value, e.g. a deployment time.
+    pub fn with_vline(mut self, x: T, style: LineStyle) -> Self {
+        self.references.push(Reference::VLine { x, style });
+        self
+    }
+
+    /// Shades the band between two y values across the graph. Panics if `min` is greater than
+    /// `max`.
+    pub fn with_hspan(mut self, min: T, max: T, fill: RGB8) -> Self {
+        if min > max {
+            panic!("Span min {min:?} must not be greater than max {max:?}");
+        }
+        self.references.push(Reference::HSpan { min, max, fill });
+        self
+    }
+
+    /// Shades the band between two x values up the graph, e.g. an incident window. Panics if
+    /// `min` is greater than `max`.
+    pub fn with_vspan(mut self, min: T, max: T, fill: RGB8) -> Self {
+        if min > max {
+            panic!("Span min {min:?} must not be greater than max {max:?}");
+        }
+        self.references.push(Reference::VSpan { min, max, fill });
+        self
+    }
+
+    /// Adds text anchored to a point in data coordinates. Annotations do not affect the limits of
+    /// the graph.
+    pub fn with_annotation(mut self, annotation: Annotation<T>) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
     pub fn with_axes(mut self, axes: Axes) -> Self {
         self.axes = Some(axes);
         self
@@ -74,6 +243,30 @@ impl<T: Graphable> Graph<T> {
         self
     }
 
+    pub fn with_colorbar(mut self, colorbar: Colorbar) -> Self {
+        self.colorbar = Some(colorbar);
+        self
+    }
+
+    /// Adds a legend listing the labeled series to the right of the graph.
+    pub fn with_legend(mut self, style: TextStyle) -> Self {
+        self.legend = Some(style);
+        self
+    }
+
+    /// Adds a right-hand y axis for series plotted against [`YAxis::Secondary`].
+    pub fn with_secondary_y_axis(mut self, axis: SecondaryYAxis) -> Self {
+        self.secondary_axis = Some(axis);
+        self
+    }
+
+    /// Sets explicit limits for the secondary y axis; secondary series points outside of these
+    /// limits are not drawn.
+    pub fn with_secondary_y_limits(mut self, min: T, max: T) -> Self {
+        self.secondary_y_limits = Some((min, max));
+        self
+    }
+
     pub fn with_x_limits(mut self, min: T, max: T) -> Self {
         let graph_limits = match self.graph_limits {
             None => GraphLimits::XOnly { min, max },
@@ -132,18 +325,158 @@ impl<T: Graphable> Graph<T> {
         &self.data
     }
 
+    pub fn box_plots(&self) -> &[BoxPlot<T>] {
+        &self.box_plots
+    }
+
+    pub fn candlesticks(&self) -> &[Candlestick<T>] {
+        &self.candlesticks
+    }
+
+    pub fn quivers(&self) -> &[Quiver<T>] {
+        &self.quivers
+    }
+
+    pub fn contours(&self) -> &[Contour<T>] {
+        &self.contours
+    }
+
+    pub fn references(&self) -> &[Reference<T>] {
+        &self.references
+    }
+
+    pub fn annotations(&self) -> &[Annotation<T>] {
+        &self.annotations
+    }
+
+    /// Checks whether the graph has anything to plot (i.e. any series or box plots).
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+            && self.box_plots.is_empty()
+            && self.candlesticks.is_empty()
+            && self.quivers.is_empty()
+            && self.contours.is_empty()
+    }
+
+    /// Gets the names of the categories placed along the x axis, if any of the plotted elements
+    /// are categorical. Explicitly set categories take precedence over box plot groups.
+    pub fn x_categories(&self) -> Option<Vec<String>> {
+        self.x_categories
+            .clone()
+            .or_else(|| self.box_plots.first().map(|box_plot| box_plot.categories()))
+    }
+
     pub fn axes(&self) -> Option<Axes> {
         self.axes.clone()
     }
 
+    pub fn colorbar(&self) -> Option<&Colorbar> {
+        self.colorbar.as_ref()
+    }
+
+    pub fn secondary_axis(&self) -> Option<&SecondaryYAxis> {
+        self.secondary_axis.as_ref()
+    }
+
+    fn has_secondary_series(&self) -> bool {
+        self.data
+            .iter()
+            .any(|series| series.y_axis() == YAxis::Secondary)
+    }
+
+    /// Gets the limits for series plotted against the secondary y axis. The x range is shared
+    /// with the primary axis; the y range covers the secondary series unless explicit secondary
+    /// limits are set.
+    pub fn secondary_limits(&self) -> Option<Limits<T>> {
+        if !self.has_secondary_series() {
+            return None;
+        }
+        let limits = self.limits()?;
+        let (min_y, max_y) = match self.secondary_y_limits {
+            Some(y_limits) => y_limits,
+            None => {
+                let secondary_points = self
+                    .data
+                    .iter()
+                    .filter(|series| series.y_axis() == YAxis::Secondary)
+                    .flat_map(|series| series.data().iter().copied());
+                let data_limits = points_limits(secondary_points)?;
+                (data_limits.min().y, data_limits.max().y)
+            }
+        };
+        Some(Limits::new(
+            Point::new(limits.min().x, min_y),
+            Point::new(limits.max().x, max_y),
+        ))
+    }
+
+    /// Gets the legend for the labeled series, if a legend was requested and any series has a
+    /// label.
+    pub fn legend(&self) -> Option<Legend> {
+        let style = self.legend.as_ref()?;
+        let legend = self
+            .data
+            .iter()
+            .filter_map(|series| series.label().map(|label| (label, series.legend_color())))
+            .fold(Legend::new(style.clone()), |legend, (label, color)| {
+                legend.with_entry(label, color)
+            });
+        (!legend.is_empty()).then_some(legend)
+    }
+
+    /// Gets the corners of every box plot, candlestick chart, quiver and contour plot; these are
+    /// always plotted against the primary y axis.
+    fn element_corners(&self) -> Vec<Point<T>> {
+        let box_plot_limits = self
+            .box_plots
+            .iter()
+            .map(|box_plot| box_plot.limits().clone());
+        let candlestick_limits = self
+            .candlesticks
+            .iter()
+            .map(|candlestick| candlestick.limits().clone());
+        let quiver_limits = self.quivers.iter().map(|quiver| quiver.limits());
+        let contour_limits = self.contours.iter().map(|contour| contour.limits().clone());
+        box_plot_limits
+            .chain(candlestick_limits)
+            .chain(quiver_limits)
+            .chain(contour_limits)
+            .flat_map(|limits| [*limits.min(), *limits.max()])
+            .collect()
+    }
+
+    /// Gets the limits of the primary axes. The x range covers every element of the graph, while
+    /// the y range only covers elements plotted against the primary y axis (unless there are
+    /// none). Reference lines and spans widen the limits but cannot define them on their own.
     pub fn limits(&self) -> Option<Limits<T>> {
-        let mut limits = self
+        let corners = self.element_corners();
+        let all_points = self.data.iter().flat_map(|series| series.data().iter());
+        let mut limits = points_limits(all_points.chain(&corners).copied())?;
+
+        let primary_points = self
             .data
             .iter()
-            .flat_map(|series| series.data().to_vec())
-            .collect::<Vec<Point<_>>>()
-            .as_slice()
-            .limits()?;
+            .filter(|series| series.y_axis() == YAxis::Primary)
+            .flat_map(|series| series.data().iter());
+        let primary_limits = points_limits(primary_points.chain(&corners).copied());
+        if let Some(primary_limits) = primary_limits {
+            limits = Limits::new(
+                Point::new(limits.min().x, primary_limits.min().y),
+                Point::new(limits.max().x, primary_limits.max().y),
+            );
+        }
+        limits = self.references.iter().fold(limits, |limits, reference| {
+            let (mut min, mut max) = (*limits.min(), *limits.max());
+            if let Some((x_min, x_max)) = reference.x_range() {
+                min.x = if x_min < min.x { x_min } else { min.x };
+                max.x = if x_max > max.x { x_max } else { max.x };
+            }
+            if let Some((y_min, y_max)) = reference.y_range() {
+                min.y = if y_min < min.y { y_min } else { min.y };
+                max.y = if y_max > max.y { y_max } else { max.y };
+            }
+            Limits::new(min, max)
+        });
 
         // explicit limits override data limits
         if let Some(graph_limits) = &self.graph_limits {
@@ -177,19 +510,15 @@ impl<T: Graphable> Graph<T> {
         let mut scaled_graph = self.clone();
 
         let mut old_limits = self.limits().expect("Cannot scale an empty graph");
+        let mut secondary_limits = self.secondary_limits();
         // if there are explicit limits set; remove any points that don't lie within those limits
-        if let Some(_) = self.graph_limits {
+        if self.graph_limits.is_some() || self.secondary_y_limits.is_some() {
             scaled_graph.data = scaled_graph
                 .data
                 .iter()
-                .map(|series| {
-                    let filtered_data: Vec<_> = series
-                        .data()
-                        .iter()
-                        .filter(|p| old_limits.contains(p))
-                        .copied()
-                        .collect();
-                    series.clone_with(&filtered_data)
+                .map(|series| match (series.y_axis(), &secondary_limits) {
+                    (YAxis::Secondary, Some(limits)) => series.filter(|p| limits.contains(p)),
+                    _ => series.filter(|p| old_limits.contains(p)),
                 })
                 .collect::<Vec<_>>();
 
@@ -198,7 +527,44 @@ impl<T: Graphable> Graph<T> {
             old_limits = scaled_graph
                 .limits()
                 .expect("No valid points lie in specified graph limits");
+            secondary_limits = scaled_graph.secondary_limits();
         }
+
+        let mut scaled_graph = scaled_graph.convert_to_f64();
+        let old_limits = old_limits.convert_to_f64();
+
+        // map secondary series onto the primary y range, so that the whole graph can be scaled
+        // to the canvas together
+        if let Some(secondary_limits) = secondary_limits {
+            let secondary_limits = secondary_limits.convert_to_f64();
+            let (_, primary_span) = old_limits.span();
+            let (_, secondary_span) = secondary_limits.span();
+            let to_primary = |y: f64| {
+                if secondary_span == 0. {
+                    old_limits.min().y + primary_span / 2.
+                } else {
+                    old_limits.min().y
+                        + (y - secondary_limits.min().y) / secondary_span * primary_span
+                }
+            };
+            scaled_graph.data = scaled_graph
+                .data
+                .into_iter()
+                .map(|series| match series.y_axis() {
+                    YAxis::Secondary => {
+                        let data = series
+                            .data()
+                            .iter()
+                            .map(|p| Point::new(p.x, to_primary(p.y)))
+                            .collect::<Vec<_>>();
+                        series.clone_with(&data)
+                    }
+                    YAxis::Primary => series,
+                })
+                .collect();
+            scaled_graph.secondary_y_limits = None;
+        }
+
         scaled_graph.scale_to(&old_limits, &new_limits)
     }
 
@@ -210,39 +576,170 @@ impl<T: Graphable> Graph<T> {
                 let limits = self
                     .limits()
                     .ok_or("Graph has no data; cannot compute limits for axes labels")?;
-                axes.get_labels(&limits, graph_limits)
+                // categorical elements label the x axis by name, unless categories were set
+                // explicitly on the axes
+                match (axes.x_categories(), self.x_categories()) {
+                    (None, Some(categories)) => axes
+                        .clone()
+                        .with_x_categories(&categories)
+                        .get_labels(&limits, graph_limits),
+                    _ => axes.get_labels(&limits, graph_limits),
+                }
             }
             None => Ok(Vec::new()),
         }
     }
 }
 
-impl<T: IntConvertable + Graphable> Drawable for Graph<T> {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
-        let mut mask_points = Vec::new();
+impl Graph<f64> {
+    /// Generates the tick labels for the secondary y axis of a scaled graph. The secondary limits
+    /// define the expected numerical values for the labels.
+    pub fn get_secondary_axis_labels(&self, secondary_limits: &Limits<f64>) -> Result<Vec<Label>> {
+        match &self.secondary_axis {
+            Some(axis) => {
+                let limits = self
+                    .limits()
+                    .ok_or("Graph has no data; cannot compute limits for axes labels")?;
+                axis.get_labels(&limits, secondary_limits)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Labels the x axis with category names. Series x values are expected to be category
+    /// positions (see [`Categories::points`]); the x limits are set so that every category fits
+    /// on the graph.
+    pub fn with_x_categories(self, categories: &Categories) -> Self {
+        let (min, max) = categories.x_range();
+        let mut graph = self.with_x_limits(min, max);
+        graph.x_categories = Some(categories.names().to_vec());
+        graph
+    }
+}
+
+impl<T: IntConvertable + Graphable> Graph<T> {
+    /// Draws the elements that the data is drawn over: spans, axes, grid lines and reference
+    /// lines.
+    pub(crate) fn draw_frame<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
         let limits = self
             .limits()
             .ok_or("Graph has no data; cannot compute limits for mask")?;
+        // spans are drawn first so that everything else shows on top of them
+        let (spans, lines): (Vec<_>, Vec<_>) = self
+            .references
+            .iter()
+            .partition(|reference| reference.is_span());
+        for span in spans {
+            span.draw(&limits, sink)?;
+        }
 
         // add axes if they are defined
         if let Some(axes) = &self.axes {
-            mask_points.extend(axes.get_mask(&limits)?);
+            axes.draw(&limits, sink)?;
+        }
+
+        if let Some(secondary_axis) = &self.secondary_axis {
+            secondary_axis.draw(&limits, sink)?;
         }
 
         // add grid lines if they are defined
         if let Some(grid_lines) = &self.grid_lines {
-            mask_points.extend(grid_lines.get_mask(&limits)?);
+            grid_lines.draw(&limits, sink)?;
+        }
+
+        // add reference lines
+        for line in lines {
+            line.draw(&limits, sink)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the data: box plots, candlesticks, quivers, contours, series and annotation arrows.
+    pub(crate) fn draw_data<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let arrows = self
+            .annotations
+            .iter()
+            .filter_map(|annotation| annotation.get_arrow())
+            .collect::<Vec<_>>();
+        self.draw_plot(&arrows, sink)
+    }
+
+    fn draw_plot<S: PixelSink + ?Sized>(&self, arrows: &[Arrow], sink: &mut S) -> Result<()> {
+        for box_plot in &self.box_plots {
+            box_plot.draw(sink)?;
+        }
+        for candlestick in &self.candlesticks {
+            candlestick.draw(sink)?;
         }
+        for quiver in &self.quivers {
+            quiver.draw(sink)?;
+        }
+        for contour in &self.contours {
+            contour.draw(sink)?;
+        }
+        for series in self.data() {
+            series.draw(sink)?;
+        }
+        // annotation text is drawn as labels by the canvas
+        for arrow in arrows {
+            arrow.draw(sink)?;
+        }
+        Ok(())
+    }
+}
+
+impl Graph<f64> {
+    /// Draws the data of a graph already scaled to the canvas at `factor` times the resolution:
+    /// each pixel becomes a block of `factor` x `factor` pixels, and lines and markers are
+    /// magnified to cover about the same area.
+    pub(crate) fn draw_magnified_data<S: PixelSink + ?Sized>(
+        &self,
+        factor: u32,
+        sink: &mut S,
+    ) -> Result<()> {
+        // pixel centers map to the centers of their blocks
+        let offset = (factor - 1) as f64 / 2.;
+        let magnify = |p: Point<f64>| p * factor as f64 + Point::new(offset, offset);
+
+        // arrows are fitted around their text, which stays at the canvas resolution
+        let arrows = self
+            .annotations
+            .iter()
+            .filter_map(|annotation| annotation.get_arrow())
+            .map(|arrow| {
+                Arrow::new(
+                    magnify(*arrow.tail()),
+                    magnify(*arrow.tip()),
+                    arrow.style().magnified(factor),
+                )
+                .with_head_length(arrow.head_length() * factor as f64)
+            })
+            .collect::<Vec<_>>();
 
-        // add series data
-        let series_masks: Vec<Vec<MaskPoints>> = self
-            .data()
+        let mut graph = Graph::new();
+        graph.data = self.data.iter().map(|s| s.magnified(factor)).collect();
+        graph.box_plots = self.box_plots.iter().map(|b| b.magnified(factor)).collect();
+        graph.candlesticks = self
+            .candlesticks
             .iter()
-            .map(|series| series.get_mask())
-            .collect::<Result<_>>()?;
-        mask_points.extend(series_masks.into_iter().flatten());
+            .map(|candlestick| candlestick.magnified(factor))
+            .collect();
+        graph.quivers = self.quivers.iter().map(|q| q.magnified(factor)).collect();
+        graph.contours = self.contours.iter().map(|c| c.magnified(factor)).collect();
+
+        let unit = Limits::new(Point::new(0., 0.), Point::new(1., 1.));
+        let magnified_unit = Limits::new(magnify(*unit.min()), magnify(*unit.max()));
+        graph
+            .scale_to(&unit, &magnified_unit)
+            .draw_plot(&arrows, sink)
+    }
+}
 
-        Ok(mask_points)
+impl<T: IntConvertable + Graphable> Drawable for Graph<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        self.draw_frame(sink)?;
+        self.draw_data(sink)
     }
 }
 
@@ -267,6 +764,36 @@ where
             .into_iter()
             .map(|series| series.scale_to(&old_limits_f64, &new_limits_f64))
             .collect::<Vec<_>>();
+        scaled_graph.box_plots = scaled_graph
+            .box_plots
+            .into_iter()
+            .map(|box_plot| box_plot.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
+        scaled_graph.candlesticks = scaled_graph
+            .candlesticks
+            .into_iter()
+            .map(|candlestick| candlestick.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
+        scaled_graph.quivers = scaled_graph
+            .quivers
+            .into_iter()
+            .map(|quiver| quiver.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
+        scaled_graph.contours = scaled_graph
+            .contours
+            .into_iter()
+            .map(|contour| contour.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
+        scaled_graph.references = scaled_graph
+            .references
+            .into_iter()
+            .map(|reference| reference.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
+        scaled_graph.annotations = scaled_graph
+            .annotations
+            .into_iter()
+            .map(|annotation| annotation.scale_to(&old_limits_f64, &new_limits_f64))
+            .collect::<Vec<_>>();
         scaled_graph = scaled_graph.shift_by(new_limit_shift);
 
         scaled_graph.graph_limits = match self.graph_limits {
@@ -296,6 +823,36 @@ where
             .into_iter()
             .map(|series| series.shift_by(amount))
             .collect::<Vec<_>>();
+        self.box_plots = self
+            .box_plots
+            .into_iter()
+            .map(|box_plot| box_plot.shift_by(amount))
+            .collect::<Vec<_>>();
+        self.candlesticks = self
+            .candlesticks
+            .into_iter()
+            .map(|candlestick| candlestick.shift_by(amount))
+            .collect::<Vec<_>>();
+        self.quivers = self
+            .quivers
+            .into_iter()
+            .map(|quiver| quiver.shift_by(amount))
+            .collect::<Vec<_>>();
+        self.contours = self
+            .contours
+            .into_iter()
+            .map(|contour| contour.shift_by(amount))
+            .collect::<Vec<_>>();
+        self.references = self
+            .references
+            .into_iter()
+            .map(|reference| reference.shift_by(amount))
+            .collect::<Vec<_>>();
+        self.annotations = self
+            .annotations
+            .into_iter()
+            .map(|annotation| annotation.shift_by(amount))
+            .collect::<Vec<_>>();
 
         self.graph_limits = match self.graph_limits {
             Some(graph_limits) => Some(graph_limits.shift_by(amount)),
@@ -309,6 +866,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plotting::common::MaskPoints;
 
     #[test]
     fn empty_graph() {
@@ -383,6 +941,165 @@ mod tests {
         );
     }
 
+    #[test]
+    fn box_plots_contribute_to_limits() {
+        use crate::plotting::boxplot::{BoxPlot, BoxPlotKind};
+        let g = Graph::new()
+            .with_series(Series::new(&[Point::new(0., 50.)]))
+            .with_box_plot(BoxPlot::new(
+                &[("a", vec![1., 2., 3.]), ("b", vec![-4., 5., 6.])],
+                BoxPlotKind::Box,
+            ));
+
+        assert!(!g.is_empty());
+        assert_eq!(
+            g.x_categories(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(
+            g.limits().unwrap(),
+            Limits::new(Point::new(-0.5, -4.), Point::new(1.5, 50.))
+        );
+    }
+
+    #[test]
+    fn quiver_tips_contribute_to_limits() {
+        use crate::plotting::quiver::Quiver;
+        let g = Graph::new()
+            .with_quiver(Quiver::new(&[(0., 0., 2., 0.), (1., 0., 0., -3.)]).with_scale(1.));
+
+        assert!(!g.is_empty());
+        assert_eq!(
+            g.limits().unwrap(),
+            Limits::new(Point::new(0., -3.), Point::new(2., 0.))
+        );
+    }
+
+    #[test]
+    fn references_widen_limits() {
+        use crate::plotting::{colors, line::LineStyle};
+        let g = Graph::new()
+            .with_series(Series::new(&[Point::new(0., 0.), Point::new(10., 5.)]))
+            .with_hline(8., LineStyle::default())
+            .with_vspan(-2., 3., colors::GRAY)
+            .with_vline(4., LineStyle::default());
+
+        assert_eq!(
+            g.limits().unwrap(),
+            Limits::new(Point::new(-2., 0.), Point::new(10., 8.))
+        );
+        // references alone have nothing to plot
+        assert!(Graph::new().with_hline(1., LineStyle::default()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_span_panics() {
+        use crate::plotting::colors;
+        Graph::<f64>::new().with_hspan(2., 1., colors::GRAY);
+    }
+
+    #[test]
+    fn box_plot_categories_label_x_axis() {
+        use crate::plotting::axes::AxesPositioning;
+        use crate::plotting::boxplot::{BoxPlot, BoxPlotKind};
+        use crate::plotting::line::LineStyle;
+        use crate::plotting::text::TextStyle;
+        let axes = Axes::new(
+            AxesPositioning::XOnly(LineStyle::default()),
+            TextStyle::default(),
+        );
+        let g = Graph::new().with_axes(axes).with_box_plot(BoxPlot::new(
+            &[("ab", vec![1., 2.]), ("cd", vec![3., 4.])],
+            BoxPlotKind::Box,
+        ));
+        let limits = g.limits().unwrap();
+        let labels = g.get_axes_labels(&limits).unwrap();
+        let texts = labels
+            .iter()
+            .map(|l| {
+                l.txt()
+                    .chars()
+                    .iter()
+                    .map(|c| c.value())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["ab", "cd"]);
+    }
+
+    #[test]
+    fn x_categories_set_padded_limits() {
+        let mut categories = Categories::new();
+        let points = categories.points(&[("mon", 1.), ("tue", 3.), ("wed", 2.)]);
+        let g = Graph::new()
+            .with_series(Series::new(&points))
+            .with_x_categories(&categories);
+
+        assert_eq!(g.x_categories().unwrap(), vec!["mon", "tue", "wed"]);
+        assert_eq!(
+            g.limits().unwrap(),
+            Limits::new(Point::new(-0.5, 1.), Point::new(2.5, 3.))
+        );
+    }
+
+    #[test]
+    fn legend_lists_only_labeled_series() {
+        use crate::plotting::colors;
+        let g = Graph::new()
+            .with_series(Series::new(&[Point::new(0, 0)]).with_label("cpu"))
+            .with_series(Series::new(&[Point::new(1, 1)]));
+        assert!(g.legend().is_none());
+
+        let legend = g.with_legend(TextStyle::with_color(colors::WHITE)).legend();
+        let legend = legend.unwrap();
+        assert_eq!(legend.entries().len(), 1);
+        assert_eq!(legend.entries()[0].label(), "cpu");
+    }
+
+    fn dual_axis_graph() -> Graph<f64> {
+        let requests = Series::new(&[Point::new(0., 0.), Point::new(10., 10.)]);
+        let latency = Series::new(&[Point::new(2., 200.), Point::new(8., 1000.)])
+            .with_y_axis(YAxis::Secondary);
+        Graph::new().with_series(requests).with_series(latency)
+    }
+
+    #[test]
+    fn secondary_series_have_independent_y_limits() {
+        let g = dual_axis_graph();
+        assert_eq!(
+            g.limits().unwrap(),
+            Limits::new(Point::new(0., 0.), Point::new(10., 10.))
+        );
+        assert_eq!(
+            g.secondary_limits().unwrap(),
+            Limits::new(Point::new(0., 200.), Point::new(10., 1000.))
+        );
+        assert!(
+            Graph::new()
+                .with_series(Series::new(&[Point::new(0, 0)]))
+                .secondary_limits()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn scale_maps_secondary_series_to_full_height() {
+        let canvas = Limits::new(Point::new(0., 0.), Point::new(100., 100.));
+        let scaled = dual_axis_graph().scale(canvas.clone());
+        assert_eq!(
+            scaled.data()[1].data(),
+            &[Point::new(20., 0.), Point::new(80., 100.)]
+        );
+
+        // explicit secondary limits rescale and clip the secondary series only
+        let scaled = dual_axis_graph()
+            .with_secondary_y_limits(0., 500.)
+            .scale(canvas);
+        assert_eq!(scaled.data()[0].data().len(), 2);
+        assert_eq!(scaled.data()[1].data(), &[Point::new(20., 40.)]);
+    }
+
     // --- with_x_limits state machine ---
 
     fn graph_with_data() -> Graph<i32> {
@@ -510,6 +1227,28 @@ mod tests {
             .contains("no data"));
     }
 
+    #[test]
+    fn magnified_data_mask_maps_pixels_to_blocks() {
+        use crate::plotting::{colors, marker::MarkerStyle};
+        let graph = Graph::new().with_series(
+            Series::new(&[Point::new(3., 5.)])
+                .with_marker_style(MarkerStyle::Pixel { color: colors::RED }),
+        );
+        let magnified_mask = |factor: u32| {
+            let mut masks: Vec<MaskPoints> = Vec::new();
+            graph.draw_magnified_data(factor, &mut masks).unwrap();
+            masks
+        };
+        let mut points = magnified_mask(2)[0].points.clone();
+        points.sort_by_key(|p| (p.x, p.y));
+        // the pixel is magnified to a 3x3 square centered on its 2x2 block
+        assert_eq!(points.len(), 9);
+        assert!(points.contains(&Point::new(6, 10)) && points.contains(&Point::new(7, 11)));
+        let mut data_mask: Vec<MaskPoints> = Vec::new();
+        graph.draw_data(&mut data_mask).unwrap();
+        assert_eq!(magnified_mask(1)[0].points, data_mask[0].points);
+    }
+
     #[test]
     fn get_axes_labels_on_empty_graph_with_axes_returns_error() {
         use crate::plotting::axes::AxesPositioning;
@@ -1,5 +1,5 @@
 use super::{
-    common::{Drawable, FloatConvertable, Graphable, MaskPoints},
+    common::{Drawable, FloatConvertable, Graphable, MaskPoints, PixelSink},
     limits::Limits,
     line::{Line, LineStyle},
     line_positioning::LinePositioning,
@@ -16,37 +16,43 @@ pub enum GridLines {
 }
 
 impl GridLines {
-    pub fn get_mask<T: FloatConvertable + Graphable>(
+    pub fn draw<T: FloatConvertable + Graphable, S: PixelSink + ?Sized>(
         &self,
         limits: &Limits<T>,
-    ) -> Result<Vec<MaskPoints>> {
+        sink: &mut S,
+    ) -> Result<()> {
         let limits = limits.convert_to_f64();
         let (limit_span_x, limit_span_y) = limits.span();
         let (x_starts, y_starts) = limits.chunk(NUM_GRID_SECTIONS);
 
-        let horz_lines = |length: f64, line_style: &LineStyle| {
-            y_starts
-                .into_iter()
-                .map(|start| LinePositioning::Horizontal { start, length })
-                .flat_map(|pos| Line::new(pos, *line_style).get_mask().unwrap())
-                .collect::<Vec<_>>()
-        };
-        let vert_lines = |length: f64, line_style: &LineStyle| {
-            x_starts
-                .into_iter()
-                .map(|start| LinePositioning::Vertical { start, length })
-                .flat_map(|pos| Line::new(pos, *line_style).get_mask().unwrap())
-                .collect::<Vec<_>>()
+        let (horz_style, vert_style) = match self {
+            GridLines::XOnly(line_style) => (Some(line_style), None),
+            GridLines::YOnly(line_style) => (None, Some(line_style)),
+            GridLines::XY(line_style) => (Some(line_style), Some(line_style)),
         };
+        if let Some(line_style) = horz_style {
+            for start in y_starts {
+                let length = limit_span_x;
+                let pos = LinePositioning::Horizontal { start, length };
+                Line::new(pos, *line_style).draw(sink)?;
+            }
+        }
+        if let Some(line_style) = vert_style {
+            for start in x_starts {
+                let length = limit_span_y;
+                let pos = LinePositioning::Vertical { start, length };
+                Line::new(pos, *line_style).draw(sink)?;
+            }
+        }
+        Ok(())
+    }
 
-        let mask_points = match self {
-            GridLines::XOnly(line_style) => horz_lines(limit_span_x, line_style),
-            GridLines::YOnly(line_style) => vert_lines(limit_span_y, line_style),
-            GridLines::XY(line_style) => horz_lines(limit_span_x, line_style)
-                .into_iter()
-                .chain(vert_lines(limit_span_y, line_style))
-                .collect::<Vec<_>>(),
-        };
-        Ok(mask_points)
+    pub fn get_mask<T: FloatConvertable + Graphable>(
+        &self,
+        limits: &Limits<T>,
+    ) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(limits, &mut masks)?;
+        Ok(masks)
     }
 }
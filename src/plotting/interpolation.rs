@@ -0,0 +1,270 @@
+use super::point::Point;
+
+// pixels between samples along smooth curves
+const CURVE_SAMPLE_SPACING: f64 = 2.;
+
+/// How the line between consecutive points of a series is drawn.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum LineInterpolation {
+    /// Straight segments between points.
+    #[default]
+    Linear,
+    /// Steps to the next value at the start of each interval.
+    StepPre,
+    /// Holds each value until the next point, then steps.
+    StepPost,
+    /// Steps halfway between points.
+    StepMid,
+    /// Smooth curve through every point.
+    CatmullRom,
+    /// Smooth curve through every point that does not overshoot the data; requires increasing x
+    /// values and falls back to straight segments otherwise.
+    MonotoneCubic,
+}
+
+const INTERPOLATION_TABLE: &[(&str, LineInterpolation)] = &[
+    ("LINEAR", LineInterpolation::Linear),
+    ("STEPPRE", LineInterpolation::StepPre),
+    ("STEPPOST", LineInterpolation::StepPost),
+    ("STEPMID", LineInterpolation::StepMid),
+    ("CATMULLROM", LineInterpolation::CatmullRom),
+    ("MONOTONECUBIC", LineInterpolation::MonotoneCubic),
+];
+
+impl LineInterpolation {
+    /// Case-insensitive lookup that also ignores '-' and '_', so "step-pre" and "StepPre" are
+    /// equivalent.
+    pub fn from_name(name: &str) -> Option<LineInterpolation> {
+        let normalized = name
+            .chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_ascii_uppercase();
+        INTERPOLATION_TABLE
+            .iter()
+            .find(|(n, _)| *n == normalized)
+            .map(|(_, i)| *i)
+    }
+
+    pub fn all_names() -> &'static [(&'static str, LineInterpolation)] {
+        INTERPOLATION_TABLE
+    }
+
+    /// Gets the path to draw through the points, as a list of vertices to connect with straight
+    /// segments. Points are expected to already be in pixel space.
+    pub fn path(&self, points: &[Point<f64>]) -> Vec<Point<f64>> {
+        if points.len() < 2 {
+            return points.to_vec();
+        }
+
+        match self {
+            LineInterpolation::Linear => points.to_vec(),
+            LineInterpolation::StepPre => step_path(points, |a, b| vec![Point::new(a.x, b.y)]),
+            LineInterpolation::StepPost => step_path(points, |a, b| vec![Point::new(b.x, a.y)]),
+            LineInterpolation::StepMid => step_path(points, |a, b| {
+                let mid_x = (a.x + b.x) / 2.;
+                vec![Point::new(mid_x, a.y), Point::new(mid_x, b.y)]
+            }),
+            LineInterpolation::CatmullRom => catmull_rom_path(points),
+            LineInterpolation::MonotoneCubic => monotone_cubic_path(points),
+        }
+    }
+}
+
+/// Builds a path with extra corner vertices inserted between each pair of points.
+fn step_path<F>(points: &[Point<f64>], corners: F) -> Vec<Point<f64>>
+where
+    F: Fn(&Point<f64>, &Point<f64>) -> Vec<Point<f64>>,
+{
+    let mut path = vec![points[0]];
+    for pair in points.windows(2) {
+        path.extend(corners(&pair[0], &pair[1]));
+        path.push(pair[1]);
+    }
+    path
+}
+
+fn num_samples(a: &Point<f64>, b: &Point<f64>) -> usize {
+    usize::max(2, (a.dist(b) / CURVE_SAMPLE_SPACING).ceil() as usize)
+}
+
+/// Uniform Catmull-Rom spline; the end points are repeated so the curve passes through every
+/// point.
+fn catmull_rom_path(points: &[Point<f64>]) -> Vec<Point<f64>> {
+    let last = points.len() - 1;
+    let mut path = vec![points[0]];
+    for i in 0..last {
+        let p0 = points[i.saturating_sub(1)];
+        let (p1, p2) = (points[i], points[i + 1]);
+        let p3 = points[usize::min(i + 2, last)];
+
+        let samples = num_samples(&p1, &p2);
+        for s in 1..=samples {
+            let t = s as f64 / samples as f64;
+            let (t2, t3) = (t * t, t * t * t);
+            let blend = |v0: f64, v1: f64, v2: f64, v3: f64| {
+                0.5 * (2. * v1
+                    + (v2 - v0) * t
+                    + (2. * v0 - 5. * v1 + 4. * v2 - v3) * t2
+                    + (3. * v1 - v0 - 3. * v2 + v3) * t3)
+            };
+            path.push(Point::new(
+                blend(p0.x, p1.x, p2.x, p3.x),
+                blend(p0.y, p1.y, p2.y, p3.y),
+            ));
+        }
+    }
+    path
+}
+
+/// Monotone cubic Hermite spline (Fritsch-Carlson), which keeps the curve within the range of
+/// each pair of neighboring points.
+fn monotone_cubic_path(points: &[Point<f64>]) -> Vec<Point<f64>> {
+    if points.windows(2).any(|pair| pair[1].x <= pair[0].x) {
+        return points.to_vec();
+    }
+
+    let n = points.len();
+    let secants = points
+        .windows(2)
+        .map(|pair| (pair[1].y - pair[0].y) / (pair[1].x - pair[0].x))
+        .collect::<Vec<_>>();
+
+    let mut tangents = (0..n)
+        .map(|i| match i {
+            0 => secants[0],
+            i if i == n - 1 => secants[n - 2],
+            i if secants[i - 1] * secants[i] <= 0. => 0.,
+            i => (secants[i - 1] + secants[i]) / 2.,
+        })
+        .collect::<Vec<_>>();
+
+    // limit the tangents so that no segment overshoots
+    for i in 0..n - 1 {
+        if secants[i] == 0. {
+            tangents[i] = 0.;
+            tangents[i + 1] = 0.;
+            continue;
+        }
+        let alpha = tangents[i] / secants[i];
+        let beta = tangents[i + 1] / secants[i];
+        let magnitude = alpha.hypot(beta);
+        if magnitude > 3. {
+            let tau = 3. / magnitude;
+            tangents[i] = tau * alpha * secants[i];
+            tangents[i + 1] = tau * beta * secants[i];
+        }
+    }
+
+    let mut path = vec![points[0]];
+    for i in 0..n - 1 {
+        let (a, b) = (points[i], points[i + 1]);
+        let dx = b.x - a.x;
+        let samples = num_samples(&a, &b);
+        for s in 1..=samples {
+            let t = s as f64 / samples as f64;
+            let (t2, t3) = (t * t, t * t * t);
+            let h10 = t3 - 2. * t2 + t;
+            let h01 = -2. * t3 + 3. * t2;
+            let h11 = t3 - t2;
+            // hermite basis, written relative to the start point so flat segments stay exact
+            let y = a.y + h01 * (b.y - a.y) + h10 * dx * tangents[i] + h11 * dx * tangents[i + 1];
+            path.push(Point::new(a.x + t * dx, y));
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Point<f64>> {
+        vec![
+            Point::new(0., 0.),
+            Point::new(10., 10.),
+            Point::new(20., 5.),
+        ]
+    }
+
+    #[test]
+    fn from_name_ignores_case_and_separators() {
+        assert_eq!(
+            LineInterpolation::from_name("step-pre"),
+            Some(LineInterpolation::StepPre)
+        );
+        assert_eq!(
+            LineInterpolation::from_name("Step_Post"),
+            Some(LineInterpolation::StepPost)
+        );
+        assert_eq!(
+            LineInterpolation::from_name("catmullrom"),
+            Some(LineInterpolation::CatmullRom)
+        );
+        assert_eq!(LineInterpolation::from_name("spline"), None);
+    }
+
+    #[test]
+    fn linear_path_is_unchanged() {
+        assert_eq!(LineInterpolation::Linear.path(&points()), points());
+    }
+
+    #[test]
+    fn step_paths_insert_corners() {
+        assert_eq!(
+            LineInterpolation::StepPost.path(&points()),
+            vec![
+                Point::new(0., 0.),
+                Point::new(10., 0.),
+                Point::new(10., 10.),
+                Point::new(20., 10.),
+                Point::new(20., 5.),
+            ]
+        );
+        assert_eq!(
+            LineInterpolation::StepPre.path(&points())[1],
+            Point::new(0., 10.)
+        );
+        assert_eq!(
+            LineInterpolation::StepMid.path(&points())[1..3],
+            [Point::new(5., 0.), Point::new(5., 10.)]
+        );
+    }
+
+    #[test]
+    fn curves_pass_through_every_point() {
+        for mode in [
+            LineInterpolation::CatmullRom,
+            LineInterpolation::MonotoneCubic,
+        ] {
+            let path = mode.path(&points());
+            for p in points() {
+                assert!(
+                    path.iter().any(|q| q.dist(&p) < 1e-9),
+                    "{mode:?} misses {p:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn monotone_cubic_does_not_overshoot() {
+        let data = vec![
+            Point::new(0., 0.),
+            Point::new(10., 0.),
+            Point::new(20., 10.),
+            Point::new(30., 10.),
+        ];
+        let path = LineInterpolation::MonotoneCubic.path(&data);
+        assert!(path.iter().all(|p| (0. ..=10.).contains(&p.y)));
+        // catmull-rom overshoots on the same data
+        let path = LineInterpolation::CatmullRom.path(&data);
+        assert!(path.iter().any(|p| p.y < 0. || p.y > 10.));
+    }
+
+    #[test]
+    fn monotone_cubic_falls_back_to_linear_for_unsorted_x() {
+        let data = vec![Point::new(10., 0.), Point::new(0., 5.)];
+        assert_eq!(LineInterpolation::MonotoneCubic.path(&data), data);
+    }
+}
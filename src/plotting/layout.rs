@@ -0,0 +1,140 @@
+use super::{
+    canvas::{BufferType, CanvasBuffer},
+    common::IntConvertable,
+    limits::Limits,
+    point::Point,
+    text::Label,
+};
+use crate::common::Result;
+
+// margins only grow, so this is enough for labels that move or change as the area shrinks
+const LAYOUT_PASSES: usize = 5;
+
+/// Finds the largest plot area within `bounds` whose surrounding labels (tick labels, titles,
+/// colorbar and legend labels) are not clipped by `bounds`. Starting with no margins, the plot
+/// area is fitted with `fit`, its labels are measured with `labels` and each margin grows by
+/// however far the labels stick out past that side, until every label fits.
+pub(crate) fn fit_labels(
+    bounds: &Limits<u32>,
+    fit: impl Fn(Point<u32>, Point<u32>) -> Result<Limits<u32>>,
+    labels: impl Fn(&Limits<u32>) -> Result<Vec<Label>>,
+) -> Result<Limits<u32>> {
+    let mut margins = CanvasBuffer::new(BufferType::None);
+    for _ in 0..LAYOUT_PASSES {
+        let area = fit_within(bounds, &margins, &fit)?;
+        let overflow = overflow(&labels(&area)?, bounds);
+        if overflow.left == 0 && overflow.bottom == 0 && overflow.right == 0 && overflow.top == 0 {
+            return Ok(area);
+        }
+        margins.left += overflow.left;
+        margins.bottom += overflow.bottom;
+        margins.right += overflow.right;
+        margins.top += overflow.top;
+    }
+    fit_within(bounds, &margins, &fit)
+}
+
+fn fit_within(
+    bounds: &Limits<u32>,
+    margins: &CanvasBuffer,
+    fit: impl Fn(Point<u32>, Point<u32>) -> Result<Limits<u32>>,
+) -> Result<Limits<u32>> {
+    let min = Point::new(
+        bounds.min().x + margins.left,
+        bounds.min().y + margins.bottom,
+    );
+    let max = Point::new(
+        bounds.max().x.saturating_sub(margins.right),
+        bounds.max().y.saturating_sub(margins.top),
+    );
+    fit(min, max)
+}
+
+/// Gets how many pixels the labels extend past each side of the bounds.
+fn overflow(labels: &[Label], bounds: &Limits<u32>) -> CanvasBuffer {
+    let min = bounds.min().convert_to_i32();
+    let max = bounds.max().convert_to_i32();
+    let past = |by: i32| by.max(0) as u32;
+
+    let mut overflow = CanvasBuffer::new(BufferType::None);
+    for label in labels {
+        let Some((lower_left, upper_right)) = label.extent() else {
+            continue;
+        };
+        overflow.left = overflow.left.max(past(min.x - lower_left.x));
+        overflow.bottom = overflow.bottom.max(past(min.y - lower_left.y));
+        overflow.right = overflow.right.max(past(upper_right.x - max.x));
+        overflow.top = overflow.top.max(past(upper_right.y - max.y));
+    }
+    overflow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::text::{Text, TextPositioning, TextStyle};
+
+    fn bounds() -> Limits<u32> {
+        Limits::new(Point::new(0, 0), Point::new(199, 99))
+    }
+
+    // a label hanging below and right-aligned to the left of the lower left corner of the area
+    fn corner_labels(area: &Limits<u32>) -> Result<Vec<Label>> {
+        let text = || Text::new("123", TextStyle::default());
+        Ok(vec![
+            Label::new(text(), TextPositioning::Below(*area.min())),
+            Label::new(text(), TextPositioning::RightAligned(*area.min())),
+        ])
+    }
+
+    #[test]
+    fn labels_inside_bounds_need_no_margins() {
+        let area = fit_labels(
+            &bounds(),
+            |min, max| Ok(Limits::new(min, max)),
+            |_| Ok(vec![]),
+        );
+        assert_eq!(area.unwrap(), bounds());
+    }
+
+    #[test]
+    fn margins_grow_until_labels_fit() {
+        let area = fit_labels(
+            &bounds(),
+            |min, max| Ok(Limits::new(min, max)),
+            corner_labels,
+        )
+        .unwrap();
+        let labels = corner_labels(&area).unwrap();
+        let text = labels[0].txt();
+        assert_eq!(area.min().y, text.height() as u32);
+        assert_eq!(area.min().x, text.width() as u32);
+        // the other sides are left alone
+        assert_eq!(area.max(), bounds().max());
+        assert!(overflow(&labels, &bounds()).left == 0);
+    }
+
+    #[test]
+    fn overflow_measures_each_side() {
+        let text = || Text::new("1", TextStyle::default());
+        let (width, height) = (text().width() as u32, text().height() as u32);
+        let labels = [
+            Label::new(text(), TextPositioning::LeftAligned(Point::new(199, 50))),
+            Label::new(text(), TextPositioning::Above(Point::new(100, 99))),
+        ];
+        let overflow = overflow(&labels, &bounds());
+        assert_eq!((overflow.left, overflow.bottom), (0, 0));
+        assert_eq!(overflow.right, width - 1);
+        assert_eq!(overflow.top, height - 1);
+    }
+
+    #[test]
+    fn too_small_bounds_return_error() {
+        let fit = |min: Point<u32>, max: Point<u32>| match min.x < max.x {
+            true => Ok(Limits::new(min, max)),
+            false => Err("Canvas too small".into()),
+        };
+        let bounds = Limits::new(Point::new(0, 0), Point::new(20, 20));
+        assert!(fit_labels(&bounds, fit, corner_labels).is_err());
+    }
+}
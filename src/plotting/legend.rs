@@ -0,0 +1,194 @@
+use super::{
+    common::{MaskPoints, PixelSink},
+    limits::Limits,
+    point::Point,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+const LEGEND_GAP: u32 = 10;
+const SWATCH_LABEL_GAP: u32 = 4;
+const ROW_GAP: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct LegendEntry {
+    label: String,
+    color: RGB8,
+}
+
+impl LegendEntry {
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+}
+
+/// List of labeled color swatches drawn to the right of the plot area. Entries are stacked from
+/// the top down in the order they were added, and the whole list is centered vertically.
+#[derive(Debug, Clone)]
+pub struct Legend {
+    entries: Vec<LegendEntry>,
+    style: TextStyle,
+}
+
+impl Legend {
+    pub fn new(style: TextStyle) -> Legend {
+        Legend {
+            entries: Vec::new(),
+            style,
+        }
+    }
+
+    pub fn with_entry(mut self, label: &str, color: RGB8) -> Self {
+        self.entries.push(LegendEntry {
+            label: label.to_string(),
+            color,
+        });
+        self
+    }
+
+    pub fn entries(&self) -> &[LegendEntry] {
+        &self.entries
+    }
+
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn texts(&self) -> Vec<Text> {
+        self.entries
+            .iter()
+            .map(|entry| Text::new(&entry.label, self.style.clone()))
+            .collect()
+    }
+
+    fn text_height(&self) -> u32 {
+        self.texts()
+            .iter()
+            .map(|txt| txt.height() as u32)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn row_height(&self) -> u32 {
+        self.text_height() + ROW_GAP
+    }
+
+    // swatches are slightly shorter than the text so they read as separate boxes
+    fn swatch_size(&self) -> u32 {
+        self.text_height().saturating_sub(2).max(1)
+    }
+
+    /// Gets the total horizontal space (in pixels) needed to the right of the plot area to draw
+    /// the legend.
+    pub fn reserved_width(&self) -> u32 {
+        if self.is_empty() {
+            return 0;
+        }
+        let max_label_width = self
+            .texts()
+            .iter()
+            .map(|txt| txt.width() as u32)
+            .max()
+            .unwrap_or(0);
+        LEGEND_GAP + self.swatch_size() + SWATCH_LABEL_GAP + max_label_width
+    }
+
+    /// Gets the vertical center of each row, from the top row down.
+    fn row_centers(&self, plot_limits: &Limits<u32>) -> Vec<u32> {
+        let row_height = self.row_height();
+        let total_height = row_height * self.entries.len() as u32;
+        let center_y = (plot_limits.min().y + plot_limits.max().y) / 2;
+        let top = center_y + total_height / 2;
+        (0..self.entries.len() as u32)
+            .map(|i| top.saturating_sub(i * row_height + row_height / 2))
+            .collect()
+    }
+
+    pub fn draw<S: PixelSink + ?Sized>(
+        &self,
+        plot_limits: &Limits<u32>,
+        sink: &mut S,
+    ) -> Result<()> {
+        let size = self.swatch_size();
+        let min_x = plot_limits.max().x + LEGEND_GAP;
+        for (entry, y) in self.entries.iter().zip(self.row_centers(plot_limits)) {
+            let min = Point::new(min_x, y.saturating_sub(size / 2));
+            let max = Point::new(min_x + size - 1, min.y + size - 1);
+            sink.set_rect(&min, &max, &entry.color);
+        }
+        Ok(())
+    }
+
+    pub fn get_mask(&self, plot_limits: &Limits<u32>) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(plot_limits, &mut masks)?;
+        Ok(masks)
+    }
+
+    pub fn get_labels(&self, plot_limits: &Limits<u32>) -> Result<Vec<Label>> {
+        let min_x = plot_limits.max().x + LEGEND_GAP + self.swatch_size() + SWATCH_LABEL_GAP;
+        let labels = self
+            .texts()
+            .into_iter()
+            .zip(self.row_centers(plot_limits))
+            .map(|(txt, y)| {
+                let x = min_x + (txt.width() as u32).div_ceil(2);
+                Label::new(txt, TextPositioning::Centered(Point::new(x, y)))
+            })
+            .collect();
+        Ok(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn legend() -> Legend {
+        Legend::new(TextStyle::with_color(colors::WHITE))
+            .with_entry("home", colors::RED)
+            .with_entry("var", colors::BLUE)
+            .with_entry("tmp", colors::GREEN)
+    }
+
+    fn plot_limits() -> Limits<u32> {
+        Limits::new(Point::new(10, 10), Point::new(110, 110))
+    }
+
+    #[test]
+    fn empty_legend_reserves_no_space() {
+        assert_eq!(Legend::new(TextStyle::default()).reserved_width(), 0);
+    }
+
+    #[test]
+    fn entries_fit_in_reserved_width() {
+        let legend = legend();
+        let right_edge = plot_limits().max().x + legend.reserved_width();
+        for label in legend.get_labels(&plot_limits()).unwrap() {
+            assert!(label.limits().min().x > plot_limits().max().x);
+            assert!(label.limits().max().x <= right_edge + 1);
+        }
+        for mask in legend.get_mask(&plot_limits()).unwrap() {
+            assert!(mask.points.iter().all(|p| p.x > plot_limits().max().x));
+        }
+    }
+
+    #[test]
+    fn entries_stack_from_the_top_down() {
+        let masks = legend().get_mask(&plot_limits()).unwrap();
+        assert_eq!(masks[0].color, colors::RED);
+        let top_of = |mask: &MaskPoints| mask.points.iter().map(|p| p.y).max().unwrap();
+        assert!(top_of(&masks[0]) > top_of(&masks[1]));
+        assert!(top_of(&masks[1]) > top_of(&masks[2]));
+    }
+}
@@ -1,8 +1,8 @@
 use super::{
     colors,
     common::{
-        Convertable, Drawable, FloatConvertable, Graphable, IntConvertable, MaskPoints, Scalable,
-        Shiftable,
+        Convertable, Drawable, FloatConvertable, Graphable, IntConvertable, PixelSink, Scalable,
+        Shiftable, magnify,
     },
     limits::Limits,
     line_positioning::LinePositioning,
@@ -57,6 +57,21 @@ impl LineStyle {
             } => *color,
         }
     }
+
+    /// Gets the style for drawing at `factor` times the resolution, with the thickness scaled to
+    /// cover about the same width.
+    pub(crate) fn magnified(&self, factor: u32) -> LineStyle {
+        match *self {
+            LineStyle::Solid { color, thickness } => LineStyle::Solid {
+                color,
+                thickness: magnify(thickness, factor),
+            },
+            LineStyle::Dashed { color, thickness } => LineStyle::Dashed {
+                color,
+                thickness: magnify(thickness, factor),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -137,89 +152,91 @@ impl Line<i32> {
                 Point::limit_range(self.positioning.limits())
             }
             LinePositioning::BetweenPoints { start, end } => {
-                // Bresenham's line algorithm — handles all orientations including
-                // vertical, horizontal, steep, shallow, and any direction.
-                let mut x0 = start.x;
-                let mut y0 = start.y;
-                let x1 = end.x;
-                let y1 = end.y;
-
-                let dx = (x1 - x0).abs();
-                let dy = -(y1 - y0).abs();
-                let sx = if x0 < x1 { 1 } else { -1 };
-                let sy = if y0 < y1 { 1 } else { -1 };
-                let mut err = dx + dy;
-
                 let mut points = Vec::new();
-                loop {
-                    let point = Point::new(x0, y0).convert_to_u32();
+                Self::trace(start, end, |point| {
+                    let point = point.convert_to_u32();
                     if points.last() != Some(&point) {
                         points.push(point);
                     }
-
-                    if x0 == x1 && y0 == y1 {
-                        break;
-                    }
-
-                    let e2 = 2 * err;
-                    if e2 >= dy {
-                        err += dy;
-                        x0 += sx;
-                    }
-                    if e2 <= dx {
-                        err += dx;
-                        y0 += sy;
-                    }
-                }
+                });
                 points
             }
         }
     }
-}
 
-impl<T: IntConvertable + Graphable> Drawable for Line<T> {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
-        let flat_line_fn = |thickness: u32, pos: &LinePositioning<T>| -> Vec<Point<u32>> {
-            let mut points = Vec::new();
-            let shift_start = -1 * thickness as i32;
-            let shift_end = thickness as i32;
-            for shift in shift_start..=shift_end {
-                let shift_point = match pos {
-                    LinePositioning::Vertical { .. } => Point::new(shift, 0),
-                    LinePositioning::Horizontal { .. } => Point::new(0, shift),
-                    _ => panic!("Invalid line positioning type for flat line fn: {pos:?}"),
-                };
-                points.extend(
-                    self.convert_to_i32()
-                        .full_drawable_points()
-                        .iter()
-                        .map(|&p| (p.convert_to_i32() + shift_point).convert_to_u32()),
-                );
+    /// Visits each point from the start to the end of a straight line, using Bresenham's line
+    /// algorithm, which handles every orientation: vertical, horizontal, steep, shallow, and
+    /// any direction.
+    fn trace(start: Point<i32>, end: Point<i32>, mut visit: impl FnMut(Point<i32>)) {
+        let (mut x0, mut y0) = (start.x, start.y);
+        let (x1, y1) = (end.x, end.y);
+
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            visit(Point::new(x0, y0));
+
+            if x0 == x1 && y0 == y1 {
+                break;
             }
-            points
-        };
-        let mask_points = match self.style {
-            LineStyle::Solid { color, thickness } => {
-                let points = match self.positioning {
-                    LinePositioning::Vertical { .. } | LinePositioning::Horizontal { .. } => {
-                        flat_line_fn(thickness, &self.positioning)
-                    }
-                    LinePositioning::BetweenPoints { .. } => {
-                        self.convert_to_i32().full_drawable_points()
-                    }
-                };
 
-                vec![MaskPoints {
-                    points,
-                    color: color.clone(),
-                }]
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
             }
+        }
+    }
+}
+
+impl<T: IntConvertable + Graphable> Drawable for Line<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let (color, thickness) = match self.style {
+            LineStyle::Solid { color, thickness } => (color, thickness as i32),
             LineStyle::Dashed {
                 color: _,
                 thickness: _,
             } => todo!(),
         };
-        Ok(mask_points)
+
+        let line = self.convert_to_i32();
+        match line.positioning {
+            LinePositioning::Vertical { .. } | LinePositioning::Horizontal { .. } => {
+                // thick flat lines are a single rectangle, widened on both sides
+                let widen = match line.positioning {
+                    LinePositioning::Vertical { .. } => Point::new(thickness, 0),
+                    _ => Point::new(0, thickness),
+                };
+                let limits = line.positioning.limits();
+                let min = (*limits.min() - widen).convert_to_u32();
+                let max = (*limits.max() + widen).convert_to_u32();
+                sink.set_rect(&min, &max, &color);
+            }
+            LinePositioning::BetweenPoints { start, end } => {
+                // thick lines are widened across their minor axis
+                let delta = end - start;
+                let shallow = delta.x.abs() >= delta.y.abs();
+                Line::<i32>::trace(start, end, |p| match shallow {
+                    true => (-thickness..=thickness).for_each(|shift| {
+                        sink.set_pixel(&Point::new(p.x, p.y + shift).convert_to_u32(), &color)
+                    }),
+                    false => {
+                        let min = Point::new(p.x - thickness, p.y).convert_to_u32();
+                        let max = Point::new(p.x + thickness, p.y).convert_to_u32();
+                        sink.set_span(min.y, min.x, max.x, &color);
+                    }
+                });
+            }
+        }
+        Ok(())
     }
 }
 
@@ -393,4 +410,26 @@ mod tests {
             "Thick line ({thick_count} points) should have more points than thin ({thin_count})"
         );
     }
+
+    #[test]
+    fn thick_diagonal_line_widens_across_minor_axis() {
+        let pos = LinePositioning::BetweenPoints {
+            start: Point::new(10, 10),
+            end: Point::new(20, 14),
+        };
+        let line: Line<i32> = Line::new(pos, LineStyle::default_with_thickness(2));
+        let points = &line.get_mask().unwrap()[0].points;
+        // a shallow line is widened vertically, so each column has 2 * thickness + 1 pixels
+        for x in 10..=20 {
+            assert_eq!(points.iter().filter(|p| p.x == x).count(), 5);
+        }
+    }
+
+    #[test]
+    fn magnified_style_covers_same_width() {
+        let style = LineStyle::default_with_thickness(1).magnified(4);
+        // 3 pixels wide becomes 12 subpixels, rounded up to an odd width
+        assert_eq!(style.thickness(), 6);
+        assert_eq!(LineStyle::default().magnified(1), LineStyle::default());
+    }
 }
@@ -2,8 +2,10 @@ use core::f32;
 
 use super::{
     colors,
-    common::{Drawable, MaskPoints},
+    common::{Drawable, PixelSink, magnify},
     limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
     point::Point,
 };
 use crate::{
@@ -12,18 +14,42 @@ use crate::{
 };
 use rgb::RGB8;
 
+// radius of the inner corners of a star, relative to its points
+const STAR_INNER_RADIUS: f64 = 0.4;
+
+/// Shape, size and color of the marker drawn at each point. The size is the distance from the
+/// center of the marker to the edge of its bounding square, so every marker of the same size
+/// covers the same area. `Cross` is diagonal like an "x", `Point` is a filled circle of half the
+/// size and `Pixel` is a single pixel regardless of size.
 #[derive(Debug, Clone)]
 pub enum MarkerStyle {
     FilledSquare { size: u32, color: RGB8 },
     HollowSquare { size: u32, color: RGB8 },
     FilledCircle { size: u32, color: RGB8 },
     HollowCircle { size: u32, color: RGB8 },
+    FilledTriangleUp { size: u32, color: RGB8 },
+    HollowTriangleUp { size: u32, color: RGB8 },
+    FilledTriangleDown { size: u32, color: RGB8 },
+    HollowTriangleDown { size: u32, color: RGB8 },
+    FilledTriangleLeft { size: u32, color: RGB8 },
+    HollowTriangleLeft { size: u32, color: RGB8 },
+    FilledTriangleRight { size: u32, color: RGB8 },
+    HollowTriangleRight { size: u32, color: RGB8 },
+    FilledDiamond { size: u32, color: RGB8 },
+    HollowDiamond { size: u32, color: RGB8 },
+    FilledStar { size: u32, color: RGB8 },
+    HollowStar { size: u32, color: RGB8 },
+    Cross { size: u32, color: RGB8 },
+    Plus { size: u32, color: RGB8 },
+    Point { size: u32, color: RGB8 },
+    Pixel { color: RGB8 },
 }
 
 #[derive(Debug)]
 pub struct Marker {
     style: MarkerStyle,
     center: Point<u32>,
+    stroke: u32,
 }
 
 impl MarkerStyle {
@@ -35,22 +61,190 @@ impl MarkerStyle {
     }
 
     pub fn size(&self) -> u32 {
-        match self {
+        match *self {
             MarkerStyle::FilledSquare { size, .. }
             | MarkerStyle::HollowSquare { size, .. }
             | MarkerStyle::FilledCircle { size, .. }
-            | MarkerStyle::HollowCircle { size, .. } => *size,
+            | MarkerStyle::HollowCircle { size, .. }
+            | MarkerStyle::FilledTriangleUp { size, .. }
+            | MarkerStyle::HollowTriangleUp { size, .. }
+            | MarkerStyle::FilledTriangleDown { size, .. }
+            | MarkerStyle::HollowTriangleDown { size, .. }
+            | MarkerStyle::FilledTriangleLeft { size, .. }
+            | MarkerStyle::HollowTriangleLeft { size, .. }
+            | MarkerStyle::FilledTriangleRight { size, .. }
+            | MarkerStyle::HollowTriangleRight { size, .. }
+            | MarkerStyle::FilledDiamond { size, .. }
+            | MarkerStyle::HollowDiamond { size, .. }
+            | MarkerStyle::FilledStar { size, .. }
+            | MarkerStyle::HollowStar { size, .. }
+            | MarkerStyle::Cross { size, .. }
+            | MarkerStyle::Plus { size, .. }
+            | MarkerStyle::Point { size, .. } => size,
+            MarkerStyle::Pixel { .. } => 0,
         }
     }
+
+    pub fn color(&self) -> RGB8 {
+        match *self {
+            MarkerStyle::FilledSquare { color, .. }
+            | MarkerStyle::HollowSquare { color, .. }
+            | MarkerStyle::FilledCircle { color, .. }
+            | MarkerStyle::HollowCircle { color, .. }
+            | MarkerStyle::FilledTriangleUp { color, .. }
+            | MarkerStyle::HollowTriangleUp { color, .. }
+            | MarkerStyle::FilledTriangleDown { color, .. }
+            | MarkerStyle::HollowTriangleDown { color, .. }
+            | MarkerStyle::FilledTriangleLeft { color, .. }
+            | MarkerStyle::HollowTriangleLeft { color, .. }
+            | MarkerStyle::FilledTriangleRight { color, .. }
+            | MarkerStyle::HollowTriangleRight { color, .. }
+            | MarkerStyle::FilledDiamond { color, .. }
+            | MarkerStyle::HollowDiamond { color, .. }
+            | MarkerStyle::FilledStar { color, .. }
+            | MarkerStyle::HollowStar { color, .. }
+            | MarkerStyle::Cross { color, .. }
+            | MarkerStyle::Plus { color, .. }
+            | MarkerStyle::Point { color, .. }
+            | MarkerStyle::Pixel { color } => color,
+        }
+    }
+
+    /// Creates a copy of the style with the same shape and size but a different color.
+    pub fn with_color(&self, color: RGB8) -> MarkerStyle {
+        self.restyled(self.size(), color)
+    }
+
+    /// Creates a copy of the style with the same shape and color but a different size.
+    pub fn with_size(&self, size: u32) -> MarkerStyle {
+        self.restyled(size, self.color())
+    }
+
+    /// Gets the style for drawing at `factor` times the resolution, with the size scaled to cover
+    /// about the same area. A pixel becomes a square covering the magnified pixel.
+    pub(crate) fn magnified(&self, factor: u32) -> MarkerStyle {
+        match *self {
+            MarkerStyle::Pixel { color } => MarkerStyle::FilledSquare {
+                size: magnify(0, factor),
+                color,
+            },
+            _ => self.with_size(magnify(self.size(), factor)),
+        }
+    }
+
+    fn restyled(&self, size: u32, color: RGB8) -> MarkerStyle {
+        match self {
+            MarkerStyle::FilledSquare { .. } => MarkerStyle::FilledSquare { size, color },
+            MarkerStyle::HollowSquare { .. } => MarkerStyle::HollowSquare { size, color },
+            MarkerStyle::FilledCircle { .. } => MarkerStyle::FilledCircle { size, color },
+            MarkerStyle::HollowCircle { .. } => MarkerStyle::HollowCircle { size, color },
+            MarkerStyle::FilledTriangleUp { .. } => MarkerStyle::FilledTriangleUp { size, color },
+            MarkerStyle::HollowTriangleUp { .. } => MarkerStyle::HollowTriangleUp { size, color },
+            MarkerStyle::FilledTriangleDown { .. } => {
+                MarkerStyle::FilledTriangleDown { size, color }
+            }
+            MarkerStyle::HollowTriangleDown { .. } => {
+                MarkerStyle::HollowTriangleDown { size, color }
+            }
+            MarkerStyle::FilledTriangleLeft { .. } => {
+                MarkerStyle::FilledTriangleLeft { size, color }
+            }
+            MarkerStyle::HollowTriangleLeft { .. } => {
+                MarkerStyle::HollowTriangleLeft { size, color }
+            }
+            MarkerStyle::FilledTriangleRight { .. } => {
+                MarkerStyle::FilledTriangleRight { size, color }
+            }
+            MarkerStyle::HollowTriangleRight { .. } => {
+                MarkerStyle::HollowTriangleRight { size, color }
+            }
+            MarkerStyle::FilledDiamond { .. } => MarkerStyle::FilledDiamond { size, color },
+            MarkerStyle::HollowDiamond { .. } => MarkerStyle::HollowDiamond { size, color },
+            MarkerStyle::FilledStar { .. } => MarkerStyle::FilledStar { size, color },
+            MarkerStyle::HollowStar { .. } => MarkerStyle::HollowStar { size, color },
+            MarkerStyle::Cross { .. } => MarkerStyle::Cross { size, color },
+            MarkerStyle::Plus { .. } => MarkerStyle::Plus { size, color },
+            MarkerStyle::Point { .. } => MarkerStyle::Point { size, color },
+            MarkerStyle::Pixel { .. } => MarkerStyle::Pixel { color },
+        }
+    }
+
+    /// Gets the corners of polygonal markers relative to a center at the origin, for a marker of
+    /// size 1.
+    fn unit_polygon(&self) -> Option<Vec<Point<f64>>> {
+        let corners = |corners: &[(f64, f64)]| {
+            corners
+                .iter()
+                .map(|&(x, y)| Point::new(x, y))
+                .collect::<Vec<_>>()
+        };
+        let polygon = match self {
+            MarkerStyle::FilledTriangleUp { .. } | MarkerStyle::HollowTriangleUp { .. } => {
+                corners(&[(0., 1.), (-1., -1.), (1., -1.)])
+            }
+            MarkerStyle::FilledTriangleDown { .. } | MarkerStyle::HollowTriangleDown { .. } => {
+                corners(&[(0., -1.), (1., 1.), (-1., 1.)])
+            }
+            MarkerStyle::FilledTriangleLeft { .. } | MarkerStyle::HollowTriangleLeft { .. } => {
+                corners(&[(-1., 0.), (1., -1.), (1., 1.)])
+            }
+            MarkerStyle::FilledTriangleRight { .. } | MarkerStyle::HollowTriangleRight { .. } => {
+                corners(&[(1., 0.), (-1., 1.), (-1., -1.)])
+            }
+            MarkerStyle::FilledDiamond { .. } | MarkerStyle::HollowDiamond { .. } => {
+                corners(&[(0., 1.), (1., 0.), (0., -1.), (-1., 0.)])
+            }
+            // five points, alternating with inner corners, starting from the top
+            MarkerStyle::FilledStar { .. } | MarkerStyle::HollowStar { .. } => (0..10)
+                .map(|i| {
+                    let radius = if i % 2 == 0 { 1. } else { STAR_INNER_RADIUS };
+                    let angle = std::f64::consts::FRAC_PI_2 + i as f64 * std::f64::consts::PI / 5.;
+                    Point::new(radius * angle.cos(), radius * angle.sin())
+                })
+                .collect(),
+            _ => return None,
+        };
+        Some(polygon)
+    }
+
+    fn is_filled(&self) -> bool {
+        matches!(
+            self,
+            MarkerStyle::FilledSquare { .. }
+                | MarkerStyle::FilledCircle { .. }
+                | MarkerStyle::FilledTriangleUp { .. }
+                | MarkerStyle::FilledTriangleDown { .. }
+                | MarkerStyle::FilledTriangleLeft { .. }
+                | MarkerStyle::FilledTriangleRight { .. }
+                | MarkerStyle::FilledDiamond { .. }
+                | MarkerStyle::FilledStar { .. }
+                | MarkerStyle::Point { .. }
+                | MarkerStyle::Pixel { .. }
+        )
+    }
 }
 
 impl Marker {
     pub fn new(center: Point<u32>, style: MarkerStyle) -> Marker {
-        Marker { center, style }
+        Marker {
+            center,
+            style,
+            stroke: 0,
+        }
+    }
+
+    /// Sets the thickness of the outline of hollow markers, crosses and pluses, which is measured
+    /// like the thickness of a [`LineStyle`].
+    pub fn with_stroke(mut self, thickness: u32) -> Self {
+        self.stroke = thickness;
+        self
     }
 
     pub fn limits(&self) -> Limits<u32> {
-        let size = self.style.size();
+        let size = match self.style.is_filled() {
+            true => self.style.size(),
+            false => self.style.size() + self.stroke,
+        };
         let min = Point::new(
             self.center.x.saturating_sub(size),
             self.center.y.saturating_sub(size),
@@ -66,138 +260,340 @@ impl Marker {
     pub fn center(&self) -> &Point<u32> {
         &self.center
     }
+
+    fn center_f64(&self) -> Point<f64> {
+        Point::new(self.center.x as f64, self.center.y as f64)
+    }
+
+    /// Gets the pixel at an offset from the center, rounded and clamped to the marker size.
+    fn offset_point(&self, size: u32, x_adj: f32, y_adj: f32) -> Point<u32> {
+        let size = size as i32;
+        let x_adj = if x_adj > 0. {
+            i32::min(x_adj.round().convert_to_i32(), size)
+        } else {
+            i32::max(x_adj.round().convert_to_i32(), -size)
+        };
+        let y_adj = if y_adj > 0. {
+            i32::min(y_adj.round().convert_to_i32(), size)
+        } else {
+            i32::max(y_adj.round().convert_to_i32(), -size)
+        };
+
+        let x = (self.center.x.convert_to_i32() + x_adj).convert_to_u32();
+        let y = (self.center.y.convert_to_i32() + y_adj).convert_to_u32();
+        Point::new(x, y)
+    }
+
+    /// Gets offsets around the first quadrant of a circle, about a pixel apart.
+    fn circle_offsets(size: u32) -> impl Iterator<Item = (f32, f32)> {
+        let radius = size as f32;
+        let step = f32::atan(1.0 / radius);
+        std::iter::successors(Some(0.), move |angle| Some(angle + step))
+            .take_while(move |angle| *angle < f32::consts::FRAC_PI_2 + step)
+            .map(move |angle| (radius * f32::cos(angle), radius * f32::sin(angle)))
+    }
+
+    /// Draws straight segments of the given thickness between consecutive points.
+    fn draw_path<S: PixelSink + ?Sized>(
+        points: &[Point<f64>],
+        thickness: u32,
+        color: RGB8,
+        sink: &mut S,
+    ) -> Result<()> {
+        let style = LineStyle::Solid { color, thickness };
+        for pair in points.windows(2) {
+            let positioning = LinePositioning::BetweenPoints {
+                start: pair[0].round(),
+                end: pair[1].round(),
+            };
+            Line::new(positioning, style).draw(sink)?;
+        }
+        Ok(())
+    }
+
+    /// Draws a polygonal marker: its outline, plus the pixels whose centers are inside it (by the
+    /// even-odd rule) when filled.
+    fn draw_polygon<S: PixelSink + ?Sized>(
+        &self,
+        unit_polygon: &[Point<f64>],
+        sink: &mut S,
+    ) -> Result<()> {
+        let center = self.center_f64();
+        let (size, color) = (self.style.size() as f64, self.style.color());
+        let corners = unit_polygon
+            .iter()
+            .map(|&corner| center + corner * size)
+            .collect::<Vec<_>>();
+
+        let mut outline = corners.clone();
+        outline.push(corners[0]);
+        if !self.style.is_filled() {
+            return Self::draw_path(&outline, self.stroke, color, sink);
+        }
+        Self::draw_path(&outline, 0, color, sink)?;
+
+        // fill each row between pairs of edge crossings; a pixel is inside when an odd number of
+        // crossings lie at or left of its center
+        let limits = self.limits();
+        let (x_min, x_max) = (limits.min().x as f64, limits.max().x as f64);
+        let mut crossings = Vec::new();
+        for y in limits.min().y..=limits.max().y {
+            let row = y as f64;
+            crossings.clear();
+            crossings.extend(
+                outline
+                    .windows(2)
+                    .filter(|edge| (edge[0].y > row) != (edge[1].y > row))
+                    .map(|edge| {
+                        let (a, b) = (edge[0], edge[1]);
+                        a.x + (row - a.y) * (b.x - a.x) / (b.y - a.y)
+                    }),
+            );
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks(2) {
+                let start = pair[0].ceil().max(x_min);
+                let end = pair.get(1).map_or(x_max, |end| end.ceil() - 1.).min(x_max);
+                if start <= end {
+                    sink.set_span(y, start as u32, end as u32, &color);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Drawable for Marker {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
-        let mask_points = match self.style {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        // thick outlines of squares and circles are drawn as nested outlines
+        if self.stroke > 0
+            && matches!(
+                self.style,
+                MarkerStyle::HollowSquare { .. } | MarkerStyle::HollowCircle { .. }
+            )
+        {
+            let size = self.style.size();
+            for size in size.saturating_sub(self.stroke)..=size + self.stroke {
+                Marker::new(self.center, self.style.with_size(size)).draw(sink)?;
+            }
+            return Ok(());
+        }
+
+        match self.style {
             MarkerStyle::FilledSquare { color, size: _ } => {
                 let limits = self.limits();
-                let points = Point::<u32>::limit_range(limits);
-                vec![MaskPoints {
-                    points,
-                    color: color.clone(),
-                }]
+                sink.set_rect(limits.min(), limits.max(), &color);
             }
             MarkerStyle::HollowSquare { color, size } => {
-                let x_lo = self.center.x.saturating_sub(size);
-                let y_lo = self.center.y.saturating_sub(size);
-                let x_hi = self.center.x + size;
-                let y_hi = self.center.y + size;
-                let top = Point::<u32>::range(
-                    &Point::new(x_lo, y_hi),
-                    &Point::new(x_hi, y_hi),
-                );
-                let bottom = Point::<u32>::range(
-                    &Point::new(x_lo, y_lo),
-                    &Point::new(x_hi, y_lo),
-                );
-                let right = Point::<u32>::range(
-                    &Point::new(x_hi, y_lo),
-                    &Point::new(x_hi, y_hi),
-                );
-                let left = Point::<u32>::range(
-                    &Point::new(x_lo, y_lo),
-                    &Point::new(x_lo, y_hi),
+                let lo = Point::new(
+                    self.center.x.saturating_sub(size),
+                    self.center.y.saturating_sub(size),
                 );
-                vec![
-                    MaskPoints {
-                        points: top,
-                        color: color.clone(),
-                    },
-                    MaskPoints {
-                        points: bottom,
-                        color: color.clone(),
-                    },
-                    MaskPoints {
-                        points: right,
-                        color: color.clone(),
-                    },
-                    MaskPoints {
-                        points: left,
-                        color: color.clone(),
-                    },
-                ]
+                let hi = self.center + size;
+                sink.set_rect(&Point::new(lo.x, hi.y), &hi, &color);
+                sink.set_rect(&lo, &Point::new(hi.x, lo.y), &color);
+                sink.set_rect(&Point::new(hi.x, lo.y), &hi, &color);
+                sink.set_rect(&lo, &Point::new(lo.x, hi.y), &color);
             }
             MarkerStyle::FilledCircle { size, color } => {
-                let get_point_fn = |x_adj: f32, y_adj: f32| -> Point<u32> {
-                    let size = size as i32;
-                    let x_adj = if x_adj > 0. {
-                        i32::min(x_adj.round().convert_to_i32(), size)
-                    } else {
-                        i32::max(x_adj.round().convert_to_i32(), -size)
-                    };
-                    let y_adj = if y_adj > 0. {
-                        i32::min(y_adj.round().convert_to_i32(), size)
-                    } else {
-                        i32::max(y_adj.round().convert_to_i32(), -size)
-                    };
-
-                    let x = (self.center.x.convert_to_i32() + x_adj).convert_to_u32();
-                    let y = (self.center.y.convert_to_i32() + y_adj).convert_to_u32();
-
-                    Point::new(x, y)
-                };
-
-                let mut points = Vec::new();
-                let radius = size as f32;
-                let step = f32::atan(1.0 / radius);
-                let mut angle = 0.;
-                while angle < f32::consts::FRAC_PI_2 + step {
-                    let x_adj = radius * f32::cos(angle);
-                    let y_adj = radius * f32::sin(angle);
-
-                    let iter_points = Point::<u32>::range(
-                        &get_point_fn(-x_adj, -y_adj),
-                        &get_point_fn(x_adj, y_adj),
-                    );
-
-                    points.extend(iter_points);
-                    angle += step;
+                for (x_adj, y_adj) in Self::circle_offsets(size) {
+                    let min = self.offset_point(size, -x_adj, -y_adj);
+                    let max = self.offset_point(size, x_adj, y_adj);
+                    sink.set_rect(&min, &max, &color);
                 }
-                vec![MaskPoints { points, color }]
             }
             MarkerStyle::HollowCircle { size, color } => {
-                let get_point_fn = |x_adj: f32, y_adj: f32| -> Point<u32> {
-                    let size = size as i32;
-                    let x_adj = if x_adj > 0. {
-                        i32::min(x_adj.round().convert_to_i32(), size)
-                    } else {
-                        i32::max(x_adj.round().convert_to_i32(), -size)
-                    };
-                    let y_adj = if y_adj > 0. {
-                        i32::min(y_adj.round().convert_to_i32(), size)
-                    } else {
-                        i32::max(y_adj.round().convert_to_i32(), -size)
-                    };
-
-                    let x = (self.center.x.convert_to_i32() + x_adj).convert_to_u32();
-                    let y = (self.center.y.convert_to_i32() + y_adj).convert_to_u32();
-
-                    Point::new(x, y)
-                };
-
-                let mut points = Vec::new();
-                let radius = size as f32;
-                let step = f32::atan(1.0 / radius);
-                let mut angle = 0.;
-                while angle < f32::consts::FRAC_PI_2 + step {
-                    let x_adj = radius * f32::cos(angle);
-                    let y_adj = radius * f32::sin(angle);
-
-                    let iter_points = vec![
-                        get_point_fn(x_adj, y_adj),
-                        get_point_fn(-x_adj, y_adj),
-                        get_point_fn(x_adj, -y_adj),
-                        get_point_fn(-x_adj, -y_adj),
-                    ];
-
-                    points.extend(iter_points);
-                    angle += step;
+                for (x_adj, y_adj) in Self::circle_offsets(size) {
+                    sink.set_pixel(&self.offset_point(size, x_adj, y_adj), &color);
+                    sink.set_pixel(&self.offset_point(size, -x_adj, y_adj), &color);
+                    sink.set_pixel(&self.offset_point(size, x_adj, -y_adj), &color);
+                    sink.set_pixel(&self.offset_point(size, -x_adj, -y_adj), &color);
                 }
-                vec![MaskPoints { points, color }]
             }
-        };
-        Ok(mask_points)
+            MarkerStyle::Cross { size, color } => {
+                let (center, size) = (self.center_f64(), size as f64);
+                Self::draw_path(&[center - size, center + size], self.stroke, color, sink)?;
+                Self::draw_path(
+                    &[
+                        center + Point::new(-size, size),
+                        center + Point::new(size, -size),
+                    ],
+                    self.stroke,
+                    color,
+                    sink,
+                )?;
+            }
+            MarkerStyle::Plus { size, color } => {
+                let (center, size) = (self.center_f64(), size as f64);
+                Self::draw_path(
+                    &[center - Point::new(size, 0.), center + Point::new(size, 0.)],
+                    self.stroke,
+                    color,
+                    sink,
+                )?;
+                Self::draw_path(
+                    &[center - Point::new(0., size), center + Point::new(0., size)],
+                    self.stroke,
+                    color,
+                    sink,
+                )?;
+            }
+            MarkerStyle::Point { size, color } => {
+                let style = MarkerStyle::FilledCircle {
+                    size: size / 2,
+                    color,
+                };
+                Marker::new(self.center, style).draw(sink)?;
+            }
+            MarkerStyle::Pixel { color } => sink.set_pixel(&self.center, &color),
+            ref style => {
+                let unit_polygon = style
+                    .unit_polygon()
+                    .expect("Every other marker style is a polygon");
+                self.draw_polygon(&unit_polygon, sink)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(marker: &Marker) -> Vec<Point<u32>> {
+        marker
+            .get_mask()
+            .unwrap()
+            .into_iter()
+            .flat_map(|m| m.points)
+            .collect()
+    }
+
+    fn every_style(size: u32) -> Vec<MarkerStyle> {
+        let color = colors::RED;
+        vec![
+            MarkerStyle::FilledTriangleUp { size, color },
+            MarkerStyle::HollowTriangleUp { size, color },
+            MarkerStyle::FilledTriangleDown { size, color },
+            MarkerStyle::HollowTriangleDown { size, color },
+            MarkerStyle::FilledTriangleLeft { size, color },
+            MarkerStyle::HollowTriangleLeft { size, color },
+            MarkerStyle::FilledTriangleRight { size, color },
+            MarkerStyle::HollowTriangleRight { size, color },
+            MarkerStyle::FilledDiamond { size, color },
+            MarkerStyle::HollowDiamond { size, color },
+            MarkerStyle::FilledStar { size, color },
+            MarkerStyle::HollowStar { size, color },
+            MarkerStyle::Cross { size, color },
+            MarkerStyle::Plus { size, color },
+            MarkerStyle::Point { size, color },
+            MarkerStyle::Pixel { color },
+        ]
+    }
+
+    #[test]
+    fn shapes_stay_within_limits() {
+        for style in every_style(5) {
+            let marker = Marker::new(Point::new(20, 20), style.clone());
+            let (min, max) = (*marker.limits().min(), *marker.limits().max());
+            let points = points(&marker);
+            assert!(!points.is_empty(), "{style:?} drew nothing");
+            assert!(
+                points
+                    .iter()
+                    .all(|p| p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y),
+                "{style:?} drew outside its limits"
+            );
+        }
+    }
+
+    #[test]
+    fn restyling_keeps_the_shape() {
+        for style in every_style(5) {
+            let restyled = style.with_size(3).with_color(colors::BLUE);
+            assert_eq!(
+                std::mem::discriminant(&style),
+                std::mem::discriminant(&restyled)
+            );
+            assert_eq!(restyled.color(), colors::BLUE);
+        }
+        assert_eq!(
+            MarkerStyle::Pixel { color: colors::RED }
+                .with_size(3)
+                .size(),
+            0
+        );
+    }
+
+    #[test]
+    fn triangles_point_the_right_way() {
+        let color = colors::RED;
+        let up = points(&Marker::new(
+            Point::new(10, 10),
+            MarkerStyle::FilledTriangleUp { size: 4, color },
+        ));
+        // the apex is a single pixel at the top, the base spans the bottom
+        assert!(up.contains(&Point::new(10, 14)));
+        assert!(up.iter().filter(|p| p.y == 14).all(|p| p.x == 10));
+        assert!(up.contains(&Point::new(6, 6)) && up.contains(&Point::new(14, 6)));
+
+        let right = points(&Marker::new(
+            Point::new(10, 10),
+            MarkerStyle::HollowTriangleRight { size: 4, color },
+        ));
+        assert!(right.contains(&Point::new(14, 10)));
+        assert!(!right.contains(&Point::new(14, 14)));
+    }
+
+    #[test]
+    fn filled_shapes_cover_their_center_and_hollow_ones_do_not() {
+        let color = colors::RED;
+        let center = Point::new(10, 10);
+        let filled = Marker::new(center, MarkerStyle::FilledDiamond { size: 4, color });
+        let hollow = Marker::new(center, MarkerStyle::HollowDiamond { size: 4, color });
+        assert!(points(&filled).contains(&center));
+        assert!(!points(&hollow).contains(&center));
+        assert!(points(&hollow).contains(&Point::new(10, 14)));
+    }
+
+    #[test]
+    fn pixel_is_a_single_point() {
+        let marker = Marker::new(Point::new(3, 4), MarkerStyle::Pixel { color: colors::RED });
+        assert_eq!(points(&marker), vec![Point::new(3, 4)]);
+    }
+
+    #[test]
+    fn stroke_thickens_outlines_within_limits() {
+        let hollow = every_style(5)
+            .into_iter()
+            .chain([MarkerStyle::HollowSquare {
+                size: 5,
+                color: colors::RED,
+            }])
+            .filter(|style| !style.is_filled());
+        for style in hollow {
+            let thin = points(&Marker::new(Point::new(20, 20), style.clone()));
+            let marker = Marker::new(Point::new(20, 20), style.clone()).with_stroke(1);
+            let (min, max) = (*marker.limits().min(), *marker.limits().max());
+            let thick = points(&marker);
+            assert!(
+                thin.iter().all(|p| thick.contains(p)),
+                "{style:?} lost pixels"
+            );
+            assert!(thick.len() > thin.len(), "{style:?} did not thicken");
+            assert!(
+                thick
+                    .iter()
+                    .all(|p| p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y),
+                "{style:?} drew outside its limits"
+            );
+        }
+    }
+
+    #[test]
+    fn magnified_pixel_covers_its_block() {
+        let style = MarkerStyle::Pixel { color: colors::RED }.magnified(4);
+        assert_eq!(style.size(), 2);
+        assert_eq!(MarkerStyle::default().magnified(2).size(), 1);
     }
 }
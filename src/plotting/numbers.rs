@@ -185,7 +185,814 @@ ee
   eeeeee
 ";
 
-pub fn get_bitmap(c: char, style: &TextStyle) -> Vec<Vec<bool>> {
+const CHAR_UPPER_A: &str = "
+    AA
+  AA  AA
+AA      AA
+AA      AA
+AA      AA
+AAAAAAAAAA
+AA      AA
+AA      AA
+AA      AA
+AA      AA
+AA      AA
+";
+const CHAR_UPPER_B: &str = "
+BBBBBBBB
+BB      BB
+BB      BB
+BB      BB
+BB      BB
+BBBBBBBB
+BB      BB
+BB      BB
+BB      BB
+BB      BB
+BBBBBBBB
+";
+const CHAR_UPPER_C: &str = "
+  CCCCCC
+CC      CC
+CC
+CC
+CC
+CC
+CC
+CC
+CC
+CC      CC
+  CCCCCC
+";
+const CHAR_UPPER_D: &str = "
+DDDDDDDD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DD      DD
+DDDDDDDD
+";
+const CHAR_UPPER_E: &str = "
+EEEEEEEEEE
+EE
+EE
+EE
+EE
+EEEEEEEE
+EE
+EE
+EE
+EE
+EEEEEEEEEE
+";
+const CHAR_UPPER_F: &str = "
+FFFFFFFFFF
+FF
+FF
+FF
+FF
+FFFFFFFF
+FF
+FF
+FF
+FF
+FF
+";
+const CHAR_UPPER_G: &str = "
+  GGGGGG
+GG      GG
+GG
+GG
+GG
+GG  GGGGGG
+GG      GG
+GG      GG
+GG      GG
+GG      GG
+  GGGGGG
+";
+const CHAR_UPPER_H: &str = "
+HH      HH
+HH      HH
+HH      HH
+HH      HH
+HH      HH
+HHHHHHHHHH
+HH      HH
+HH      HH
+HH      HH
+HH      HH
+HH      HH
+";
+const CHAR_UPPER_I: &str = "
+IIIIIIIIII
+    II
+    II
+    II
+    II
+    II
+    II
+    II
+    II
+    II
+IIIIIIIIII
+";
+const CHAR_UPPER_J: &str = "
+    JJJJJJ
+        JJ
+        JJ
+        JJ
+        JJ
+        JJ
+        JJ
+        JJ
+JJ      JJ
+JJ      JJ
+  JJJJJJ
+";
+const CHAR_UPPER_K: &str = "
+KK      KK
+KK      KK
+KK    KK
+KK    KK
+KK  KK
+KKKK
+KK  KK
+KK    KK
+KK    KK
+KK      KK
+KK      KK
+";
+const CHAR_UPPER_L: &str = "
+LL
+LL
+LL
+LL
+LL
+LL
+LL
+LL
+LL
+LL
+LLLLLLLLLL
+";
+const CHAR_UPPER_M: &str = "
+MM      MM
+MMMM  MMMM
+MM  MM  MM
+MM  MM  MM
+MM      MM
+MM      MM
+MM      MM
+MM      MM
+MM      MM
+MM      MM
+MM      MM
+";
+const CHAR_UPPER_N: &str = "
+NN      NN
+NN      NN
+NNNN    NN
+NNNN    NN
+NN  NN  NN
+NN  NN  NN
+NN  NN  NN
+NN    NNNN
+NN    NNNN
+NN      NN
+NN      NN
+";
+const CHAR_UPPER_O: &str = "
+  OOOOOO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+OO      OO
+  OOOOOO
+";
+const CHAR_UPPER_P: &str = "
+PPPPPPPP
+PP      PP
+PP      PP
+PP      PP
+PP      PP
+PPPPPPPP
+PP
+PP
+PP
+PP
+PP
+";
+const CHAR_UPPER_Q: &str = "
+  QQQQQQ
+QQ      QQ
+QQ      QQ
+QQ      QQ
+QQ      QQ
+QQ      QQ
+QQ      QQ
+QQ      QQ
+QQ  QQ  QQ
+QQ    QQ
+  QQQQ  QQ
+";
+const CHAR_UPPER_R: &str = "
+RRRRRRRR
+RR      RR
+RR      RR
+RR      RR
+RR      RR
+RRRRRRRR
+RR  RR
+RR    RR
+RR    RR
+RR      RR
+RR      RR
+";
+const CHAR_UPPER_S: &str = "
+  SSSSSS
+SS      SS
+SS
+SS
+SS
+  SSSSSS
+        SS
+        SS
+        SS
+SS      SS
+  SSSSSS
+";
+const CHAR_UPPER_T: &str = "
+TTTTTTTTTT
+    TT
+    TT
+    TT
+    TT
+    TT
+    TT
+    TT
+    TT
+    TT
+    TT
+";
+const CHAR_UPPER_U: &str = "
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+UU      UU
+  UUUUUU
+";
+const CHAR_UPPER_V: &str = "
+VV      VV
+VV      VV
+VV      VV
+VV      VV
+VV      VV
+VV      VV
+VV      VV
+  VV  VV
+  VV  VV
+  VV  VV
+    VV
+";
+const CHAR_UPPER_W: &str = "
+WW      WW
+WW      WW
+WW      WW
+WW      WW
+WW      WW
+WW      WW
+WW  WW  WW
+WW  WW  WW
+WW  WW  WW
+WWWW  WWWW
+WW      WW
+";
+const CHAR_UPPER_X: &str = "
+XX      XX
+XX      XX
+  XX  XX
+  XX  XX
+    XX
+    XX
+    XX
+  XX  XX
+  XX  XX
+XX      XX
+XX      XX
+";
+const CHAR_UPPER_Y: &str = "
+YY      YY
+YY      YY
+YY      YY
+  YY  YY
+  YY  YY
+    YY
+    YY
+    YY
+    YY
+    YY
+    YY
+";
+const CHAR_UPPER_Z: &str = "
+ZZZZZZZZZZ
+        ZZ
+        ZZ
+      ZZ
+      ZZ
+    ZZ
+  ZZ
+  ZZ
+ZZ
+ZZ
+ZZZZZZZZZZ
+";
+const CHAR_LOWER_A: &str = "
+          
+          
+          
+          
+  aaaaaa
+        aa
+  aaaaaaaa
+aa      aa
+aa      aa
+aa    aaaa
+  aaaa  aa
+";
+const CHAR_LOWER_B: &str = "
+bb
+bb
+bb
+bb
+bbbbbbbb
+bb      bb
+bb      bb
+bb      bb
+bb      bb
+bb      bb
+bbbbbbbb
+";
+const CHAR_LOWER_C: &str = "
+          
+          
+          
+          
+  cccccc
+cc      cc
+cc
+cc
+cc
+cc      cc
+  cccccc
+";
+const CHAR_LOWER_D: &str = "
+        dd
+        dd
+        dd
+        dd
+  dddddddd
+dd      dd
+dd      dd
+dd      dd
+dd      dd
+dd      dd
+  dddddddd
+";
+const CHAR_LOWER_F: &str = "
+    ffff
+  ff    ff
+  ff
+  ff
+ffffffff
+  ff
+  ff
+  ff
+  ff
+  ff
+  ff
+";
+const CHAR_LOWER_G: &str = "
+          
+          
+          
+          
+  gggggggg
+gg      gg
+gg      gg
+  gggggggg
+        gg
+gg      gg
+  gggggg
+";
+const CHAR_LOWER_H: &str = "
+hh
+hh
+hh
+hh
+hhhhhhhh
+hh      hh
+hh      hh
+hh      hh
+hh      hh
+hh      hh
+hh      hh
+";
+const CHAR_LOWER_I: &str = "
+          
+    ii
+          
+          
+  iiii
+    ii
+    ii
+    ii
+    ii
+    ii
+  iiiiii
+";
+const CHAR_LOWER_J: &str = "
+          
+      jj
+          
+          
+    jjjj
+      jj
+      jj
+      jj
+      jj
+jj    jj
+  jjjj
+";
+const CHAR_LOWER_K: &str = "
+kk
+kk
+kk
+kk
+kk    kk
+kk  kk
+kkkk
+kkkk
+kk  kk
+kk    kk
+kk      kk
+";
+const CHAR_LOWER_L: &str = "
+  llll
+    ll
+    ll
+    ll
+    ll
+    ll
+    ll
+    ll
+    ll
+    ll
+  llllll
+";
+const CHAR_LOWER_M: &str = "
+          
+          
+          
+          
+mmmm  mm
+mm  mm  mm
+mm  mm  mm
+mm  mm  mm
+mm  mm  mm
+mm  mm  mm
+mm  mm  mm
+";
+const CHAR_LOWER_N: &str = "
+          
+          
+          
+          
+nn  nnnn
+nnnn    nn
+nn      nn
+nn      nn
+nn      nn
+nn      nn
+nn      nn
+";
+const CHAR_LOWER_O: &str = "
+          
+          
+          
+          
+  oooooo
+oo      oo
+oo      oo
+oo      oo
+oo      oo
+oo      oo
+  oooooo
+";
+const CHAR_LOWER_P: &str = "
+          
+          
+          
+          
+pppppppp
+pp      pp
+pp      pp
+pppppppp
+pp
+pp
+pp
+";
+const CHAR_LOWER_Q: &str = "
+          
+          
+          
+          
+  qqqqqqqq
+qq      qq
+qq      qq
+  qqqqqqqq
+        qq
+        qq
+        qq
+";
+const CHAR_LOWER_R: &str = "
+          
+          
+          
+          
+rr  rrrr
+rrrr    rr
+rr
+rr
+rr
+rr
+rr
+";
+const CHAR_LOWER_S: &str = "
+          
+          
+          
+          
+  ssssssss
+ss
+ss
+  ssssss
+        ss
+        ss
+ssssssss
+";
+const CHAR_LOWER_T: &str = "
+          
+  tt
+  tt
+  tt
+tttttttt
+  tt
+  tt
+  tt
+  tt
+  tt    tt
+    tttt
+";
+const CHAR_LOWER_U: &str = "
+          
+          
+          
+          
+uu      uu
+uu      uu
+uu      uu
+uu      uu
+uu      uu
+uu    uuuu
+  uuuu  uu
+";
+const CHAR_LOWER_V: &str = "
+          
+          
+          
+          
+vv      vv
+vv      vv
+vv      vv
+vv      vv
+  vv  vv
+  vv  vv
+    vv
+";
+const CHAR_LOWER_W: &str = "
+          
+          
+          
+          
+ww      ww
+ww      ww
+ww      ww
+ww      ww
+ww  ww  ww
+ww  ww  ww
+  ww  ww
+";
+const CHAR_LOWER_X: &str = "
+          
+          
+          
+          
+xx      xx
+  xx  xx
+  xx  xx
+    xx
+  xx  xx
+  xx  xx
+xx      xx
+";
+const CHAR_LOWER_Y: &str = "
+          
+          
+          
+          
+yy      yy
+yy      yy
+yy      yy
+  yyyyyyyy
+        yy
+yy      yy
+  yyyyyy
+";
+const CHAR_LOWER_Z: &str = "
+          
+          
+          
+          
+zzzzzzzzzz
+        zz
+      zz
+    zz
+  zz
+zz
+zzzzzzzzzz
+";
+const CHAR_COLON: &str = "
+          
+          
+    ##
+    ##
+          
+          
+          
+          
+    ##
+    ##
+          
+";
+const CHAR_COMMA: &str = "
+          
+          
+          
+          
+          
+          
+          
+          
+    ##
+    ##
+  ##
+";
+const CHAR_PERCENT: &str = "
+####    ##
+####    ##
+      ##
+      ##
+    ##
+    ##
+    ##
+  ##
+  ##
+##    ####
+##    ####
+";
+const CHAR_OPEN_PAREN: &str = "
+      ##
+    ##
+  ##
+  ##
+  ##
+  ##
+  ##
+  ##
+  ##
+    ##
+      ##
+";
+const CHAR_CLOSE_PAREN: &str = "
+  ##
+    ##
+      ##
+      ##
+      ##
+      ##
+      ##
+      ##
+      ##
+    ##
+  ##
+";
+const CHAR_SLASH: &str = "
+        ##
+        ##
+      ##
+      ##
+    ##
+    ##
+    ##
+  ##
+  ##
+##
+##
+";
+const CHAR_UNDERSCORE: &str = "
+          
+          
+          
+          
+          
+          
+          
+          
+          
+          
+##########
+";
+const CHAR_PLUS: &str = "
+          
+          
+          
+    ##
+    ##
+##########
+    ##
+    ##
+          
+          
+          
+";
+const CHAR_EXCLAMATION: &str = "
+    ##
+    ##
+    ##
+    ##
+    ##
+    ##
+    ##
+    ##
+          
+    ##
+    ##
+";
+const CHAR_QUESTION: &str = "
+  ######
+##      ##
+        ##
+        ##
+      ##
+    ##
+    ##
+    ##
+          
+    ##
+    ##
+";
+const CHAR_DEGREE: &str = "
+  ####    
+##    ##  
+##    ##  
+  ####    
+          
+          
+          
+          
+          
+          
+          
+";
+
+fn get_str_map(c: char) -> Option<&'static str> {
     let str_map = match c {
         '0' => NUM_ZERO,
         '1' => NUM_ONE,
@@ -201,7 +1008,81 @@ pub fn get_bitmap(c: char, style: &TextStyle) -> Vec<Vec<bool>> {
         '.' => CHAR_DECIMAL,
         '-' => CHAR_DASH,
         'e' => CHAR_E,
-        _ => panic!("Bitmap not defined for character: '{c}'"),
+        'A' => CHAR_UPPER_A,
+        'B' => CHAR_UPPER_B,
+        'C' => CHAR_UPPER_C,
+        'D' => CHAR_UPPER_D,
+        'E' => CHAR_UPPER_E,
+        'F' => CHAR_UPPER_F,
+        'G' => CHAR_UPPER_G,
+        'H' => CHAR_UPPER_H,
+        'I' => CHAR_UPPER_I,
+        'J' => CHAR_UPPER_J,
+        'K' => CHAR_UPPER_K,
+        'L' => CHAR_UPPER_L,
+        'M' => CHAR_UPPER_M,
+        'N' => CHAR_UPPER_N,
+        'O' => CHAR_UPPER_O,
+        'P' => CHAR_UPPER_P,
+        'Q' => CHAR_UPPER_Q,
+        'R' => CHAR_UPPER_R,
+        'S' => CHAR_UPPER_S,
+        'T' => CHAR_UPPER_T,
+        'U' => CHAR_UPPER_U,
+        'V' => CHAR_UPPER_V,
+        'W' => CHAR_UPPER_W,
+        'X' => CHAR_UPPER_X,
+        'Y' => CHAR_UPPER_Y,
+        'Z' => CHAR_UPPER_Z,
+        'a' => CHAR_LOWER_A,
+        'b' => CHAR_LOWER_B,
+        'c' => CHAR_LOWER_C,
+        'd' => CHAR_LOWER_D,
+        'f' => CHAR_LOWER_F,
+        'g' => CHAR_LOWER_G,
+        'h' => CHAR_LOWER_H,
+        'i' => CHAR_LOWER_I,
+        'j' => CHAR_LOWER_J,
+        'k' => CHAR_LOWER_K,
+        'l' => CHAR_LOWER_L,
+        'm' => CHAR_LOWER_M,
+        'n' => CHAR_LOWER_N,
+        'o' => CHAR_LOWER_O,
+        'p' => CHAR_LOWER_P,
+        'q' => CHAR_LOWER_Q,
+        'r' => CHAR_LOWER_R,
+        's' => CHAR_LOWER_S,
+        't' => CHAR_LOWER_T,
+        'u' => CHAR_LOWER_U,
+        'v' => CHAR_LOWER_V,
+        'w' => CHAR_LOWER_W,
+        'x' => CHAR_LOWER_X,
+        'y' => CHAR_LOWER_Y,
+        'z' => CHAR_LOWER_Z,
+        ':' => CHAR_COLON,
+        ',' => CHAR_COMMA,
+        '%' => CHAR_PERCENT,
+        '(' => CHAR_OPEN_PAREN,
+        ')' => CHAR_CLOSE_PAREN,
+        '/' => CHAR_SLASH,
+        '_' => CHAR_UNDERSCORE,
+        '+' => CHAR_PLUS,
+        '!' => CHAR_EXCLAMATION,
+        '?' => CHAR_QUESTION,
+        '°' => CHAR_DEGREE,
+        _ => return None,
+    };
+    Some(str_map)
+}
+
+/// Checks whether a character can be drawn with the built-in bitmap font.
+pub fn has_bitmap(c: char) -> bool {
+    get_str_map(c).is_some()
+}
+
+pub fn get_bitmap(c: char, style: &TextStyle) -> Vec<Vec<bool>> {
+    let Some(str_map) = get_str_map(c) else {
+        panic!("Bitmap not defined for character: '{c}'");
     };
 
     // note that bitmaps are written to be human-readable; they need to be modified to be
@@ -0,0 +1,487 @@
+use super::{
+    colors,
+    common::{Drawable, FloatConvertable, MaskPoints, PixelSink},
+    legend::Legend,
+    limits::Limits,
+    point::Point,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+use rgb::RGB8;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+// radius of the percentage labels for a full pie, as a fraction of the outer radius
+const PIE_LABEL_RADIUS: f64 = 0.65;
+
+const SLICE_COLORS: &[RGB8] = &[
+    colors::STEEL_BLUE,
+    colors::DARK_ORANGE,
+    colors::FOREST_GREEN,
+    colors::CRIMSON,
+    colors::MEDIUM_PURPLE,
+    colors::SIENNA,
+    colors::ORCHID,
+    colors::GRAY,
+    colors::OLIVE,
+    colors::DARK_TURQUOISE,
+];
+
+/// A filled ring sector, with angles in radians measured counter-clockwise from the positive x
+/// axis. An inner radius of zero gives a plain pie wedge.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Wedge {
+    center: Point<f64>,
+    inner_radius: f64,
+    outer_radius: f64,
+    start_angle: f64,
+    sweep: f64,
+    color: RGB8,
+}
+
+impl Wedge {
+    pub fn new(
+        center: Point<f64>,
+        inner_radius: f64,
+        outer_radius: f64,
+        start_angle: f64,
+        sweep: f64,
+        color: RGB8,
+    ) -> Wedge {
+        if inner_radius < 0. || inner_radius > outer_radius {
+            panic!(
+                "Wedge radii must satisfy 0 <= inner ({inner_radius}) <= outer ({outer_radius})"
+            );
+        }
+        Wedge {
+            center,
+            inner_radius,
+            outer_radius,
+            start_angle,
+            sweep: sweep.clamp(0., TAU),
+            color,
+        }
+    }
+
+    pub fn center(&self) -> &Point<f64> {
+        &self.center
+    }
+
+    pub fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    pub fn outer_radius(&self) -> f64 {
+        self.outer_radius
+    }
+
+    pub fn start_angle(&self) -> f64 {
+        self.start_angle
+    }
+
+    pub fn sweep(&self) -> f64 {
+        self.sweep
+    }
+
+    pub fn mid_angle(&self) -> f64 {
+        self.start_angle + self.sweep / 2.
+    }
+
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Checks whether a pixel center falls inside the wedge. The closing edge is excluded so that
+    /// neighboring wedges do not overlap.
+    pub fn contains(&self, point: &Point<f64>) -> bool {
+        let (dx, dy) = (point.x - self.center.x, point.y - self.center.y);
+        let radius = dx.hypot(dy);
+        if radius < self.inner_radius || radius > self.outer_radius {
+            return false;
+        }
+        if self.sweep >= TAU {
+            return true;
+        }
+        let angle = (dy.atan2(dx) - self.start_angle).rem_euclid(TAU);
+        angle < self.sweep
+    }
+}
+
+impl Drawable for Wedge {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        // scan each row across the chord of the full circle, and draw the runs of pixels inside
+        // the wedge as spans
+        let min_y = (self.center.y - self.outer_radius).floor().max(0.) as u32;
+        let max_y = (self.center.y + self.outer_radius).ceil().max(0.) as u32;
+
+        for y in min_y..=max_y {
+            let dy = y as f64 - self.center.y;
+            if dy.abs() > self.outer_radius {
+                continue;
+            }
+            let half_chord = (self.outer_radius * self.outer_radius - dy * dy).sqrt();
+            let min_x = (self.center.x - half_chord).floor().max(0.) as u32;
+            let max_x = (self.center.x + half_chord).ceil().max(0.) as u32;
+
+            let mut run_start = None;
+            for x in min_x..=max_x {
+                let inside = self.contains(&Point::new(x as f64, y as f64));
+                match (inside, run_start) {
+                    (true, None) => run_start = Some(x),
+                    (false, Some(start)) => {
+                        sink.set_span(y, start, x - 1, &self.color);
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(start) = run_start {
+                sink.set_span(y, start, max_x, &self.color);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PieSlice {
+    label: String,
+    value: f64,
+    color: RGB8,
+    explode: f64,
+}
+
+impl PieSlice {
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn color(&self) -> RGB8 {
+        self.color
+    }
+
+    /// Distance the slice is pulled out from the center, as a fraction of the outer radius.
+    pub fn explode(&self) -> f64 {
+        self.explode
+    }
+}
+
+/// Pie or donut chart of labeled values. Slices are drawn clockwise from the top of the circle
+/// in the order given, and fill the largest circle that fits the drawable area of the canvas.
+#[derive(Debug, Clone)]
+pub struct PieChart {
+    slices: Vec<PieSlice>,
+    // inner radius as a fraction of the outer radius; zero for a full pie
+    hole: f64,
+    percentages: Option<TextStyle>,
+    legend: Option<TextStyle>,
+}
+
+impl PieChart {
+    /// Creates a pie chart from (label, value) pairs. Colors are assigned from a default palette
+    /// that repeats after ten slices.
+    pub fn new<S: AsRef<str>>(data: &[(S, f64)]) -> PieChart {
+        if data.is_empty() {
+            panic!("Pie chart requires at least one slice");
+        }
+        if let Some((label, value)) = data
+            .iter()
+            .find(|(_, value)| !value.is_finite() || *value < 0.)
+        {
+            panic!(
+                "Pie chart values must be finite and non-negative; '{}' is {value}",
+                label.as_ref()
+            );
+        }
+        if data.iter().all(|(_, value)| *value == 0.) {
+            panic!("Pie chart values cannot all be zero");
+        }
+
+        let slices = data
+            .iter()
+            .enumerate()
+            .map(|(i, (label, value))| PieSlice {
+                label: label.as_ref().to_string(),
+                value: *value,
+                color: SLICE_COLORS[i % SLICE_COLORS.len()],
+                explode: 0.,
+            })
+            .collect();
+        PieChart {
+            slices,
+            hole: 0.,
+            percentages: None,
+            legend: None,
+        }
+    }
+
+    /// Overrides the slice colors, in slice order; colors repeat if there are fewer colors than
+    /// slices.
+    pub fn with_colors(mut self, colors: &[RGB8]) -> Self {
+        if colors.is_empty() {
+            panic!("Pie chart colors cannot be empty");
+        }
+        self.slices
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, slice)| slice.color = colors[i % colors.len()]);
+        self
+    }
+
+    /// Pulls a slice away from the center by a fraction of the outer radius.
+    pub fn with_exploded(mut self, label: &str, offset: f64) -> Self {
+        if !(0. ..=1.).contains(&offset) {
+            panic!("Exploded slice offset must be between 0 and 1, got {offset}");
+        }
+        let slice = self
+            .slices
+            .iter_mut()
+            .find(|slice| slice.label == label)
+            .unwrap_or_else(|| panic!("No pie slice labeled '{label}'"));
+        slice.explode = offset;
+        self
+    }
+
+    /// Turns the pie into a donut, with the hole size given as a fraction of the outer radius.
+    pub fn with_hole(mut self, fraction: f64) -> Self {
+        if !(0. ..1.).contains(&fraction) {
+            panic!("Donut hole must be at least 0 and less than 1, got {fraction}");
+        }
+        self.hole = fraction;
+        self
+    }
+
+    /// Draws the percentage of the total on each slice that is wide enough to fit it.
+    pub fn with_percentages(mut self, style: TextStyle) -> Self {
+        self.percentages = Some(style);
+        self
+    }
+
+    /// Adds a legend with the slice labels to the right of the chart.
+    pub fn with_legend(mut self, style: TextStyle) -> Self {
+        self.legend = Some(style);
+        self
+    }
+
+    pub fn slices(&self) -> &[PieSlice] {
+        &self.slices
+    }
+
+    pub fn hole(&self) -> f64 {
+        self.hole
+    }
+
+    pub fn total(&self) -> f64 {
+        self.slices.iter().map(|slice| slice.value).sum()
+    }
+
+    pub fn legend(&self) -> Option<Legend> {
+        self.legend.as_ref().map(|style| {
+            self.slices
+                .iter()
+                .fold(Legend::new(style.clone()), |legend, slice| {
+                    legend.with_entry(&slice.label, slice.color)
+                })
+        })
+    }
+
+    /// Gets the horizontal space (in pixels) that must be left to the right of the chart.
+    pub fn reserved_width(&self) -> u32 {
+        self.legend().map(|l| l.reserved_width()).unwrap_or(0)
+    }
+
+    /// Gets the wedge for each slice, sized to fit the given area. Empty slices have no wedge.
+    pub fn wedges(&self, area: &Limits<u32>) -> Vec<Wedge> {
+        let area = area.convert_to_f64();
+        let center = Point::new(
+            (area.min().x + area.max().x) / 2.,
+            (area.min().y + area.max().y) / 2.,
+        );
+        let (span_x, span_y) = area.span();
+        let max_explode = self
+            .slices
+            .iter()
+            .map(|slice| slice.explode)
+            .fold(0., f64::max);
+        // leave room for exploded slices to move out without leaving the area
+        let outer_radius = f64::min(span_x, span_y) / 2. / (1. + max_explode);
+        let inner_radius = outer_radius * self.hole;
+
+        let total = self.total();
+        let mut end_angle = FRAC_PI_2;
+        self.slices
+            .iter()
+            .filter_map(|slice| {
+                let sweep = slice.value / total * TAU;
+                // slices run clockwise, so each wedge starts where the next one ends
+                let start_angle = end_angle - sweep;
+                let mid_angle = start_angle + sweep / 2.;
+                end_angle = start_angle;
+                if sweep == 0. {
+                    return None;
+                }
+                let offset = slice.explode * outer_radius;
+                let wedge_center = Point::new(
+                    center.x + offset * mid_angle.cos(),
+                    center.y + offset * mid_angle.sin(),
+                );
+                Some(Wedge::new(
+                    wedge_center,
+                    inner_radius,
+                    outer_radius,
+                    start_angle,
+                    sweep,
+                    slice.color,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn draw<S: PixelSink + ?Sized>(&self, area: &Limits<u32>, sink: &mut S) -> Result<()> {
+        for wedge in self.wedges(area) {
+            wedge.draw(sink)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_mask(&self, area: &Limits<u32>) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(area, &mut masks)?;
+        Ok(masks)
+    }
+
+    pub fn get_labels(&self, area: &Limits<u32>) -> Result<Vec<Label>> {
+        let Some(style) = &self.percentages else {
+            return Ok(Vec::new());
+        };
+
+        let total = self.total();
+        let nonzero_slices = self.slices.iter().filter(|slice| slice.value > 0.);
+        let labels = self
+            .wedges(area)
+            .into_iter()
+            .zip(nonzero_slices)
+            .filter_map(|(wedge, slice)| {
+                let percent = slice.value / total * 100.;
+                let txt = Text::new(&format!("{percent:.0}%"), style.clone());
+
+                let radius = if self.hole == 0. {
+                    wedge.outer_radius * PIE_LABEL_RADIUS
+                } else {
+                    (wedge.inner_radius + wedge.outer_radius) / 2.
+                };
+                // skip slices too narrow to hold the label
+                let arc_length = wedge.sweep * radius;
+                if wedge.sweep < TAU && arc_length < txt.width() as f64 {
+                    return None;
+                }
+
+                let angle = wedge.mid_angle();
+                let center = Point::new(
+                    (wedge.center.x + radius * angle.cos()).round().max(0.) as u32,
+                    (wedge.center.y + radius * angle.sin()).round().max(0.) as u32,
+                );
+                Some(Label::new(txt, TextPositioning::Centered(center)))
+            })
+            .collect();
+        Ok(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area() -> Limits<u32> {
+        Limits::new(Point::new(0, 0), Point::new(100, 100))
+    }
+
+    fn disk_usage() -> PieChart {
+        PieChart::new(&[("home", 50.), ("var", 25.), ("tmp", 25.)])
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_values_panic() {
+        PieChart::new(&[("a", 1.), ("b", -1.)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn all_zero_values_panic() {
+        PieChart::new(&[("a", 0.), ("b", 0.)]);
+    }
+
+    #[test]
+    fn wedge_contains_respects_radii_and_angles() {
+        let wedge = Wedge::new(Point::new(0., 0.), 2., 10., 0., FRAC_PI_2, colors::RED);
+        assert!(wedge.contains(&Point::new(5., 5.)));
+        assert!(!wedge.contains(&Point::new(1., 1.)));
+        assert!(!wedge.contains(&Point::new(9., 9.)));
+        assert!(!wedge.contains(&Point::new(-5., 5.)));
+        assert!(!wedge.contains(&Point::new(5., -5.)));
+    }
+
+    #[test]
+    fn wedges_run_clockwise_from_the_top() {
+        let wedges = disk_usage().wedges(&area());
+        assert_eq!(wedges.len(), 3);
+        // the first half of the pie covers the right-hand side
+        assert!((wedges[0].start_angle() + FRAC_PI_2).abs() < 1e-9);
+        assert!((wedges[0].sweep() - TAU / 2.).abs() < 1e-9);
+        assert!(wedges[0].contains(&Point::new(90., 50.)));
+        assert!(wedges[1].contains(&Point::new(20., 30.)));
+        assert!(wedges[2].contains(&Point::new(20., 70.)));
+    }
+
+    #[test]
+    fn slices_fill_the_circle_without_overlap() {
+        let chart = disk_usage().with_hole(0.5);
+        let masks = chart.get_mask(&area()).unwrap();
+        let count = masks.iter().map(|m| m.points.len()).sum::<usize>();
+        let mut unique = masks
+            .iter()
+            .flat_map(|m| m.points.iter().map(|p| (p.x, p.y)))
+            .collect::<Vec<_>>();
+        unique.sort();
+        unique.dedup();
+        assert!(count - unique.len() < 10, "too many overlapping pixels");
+        // the hole is empty
+        assert!(!unique.contains(&(50, 50)));
+        assert!(unique.contains(&(50, 25)));
+    }
+
+    #[test]
+    fn exploded_slice_moves_away_from_center() {
+        let plain = disk_usage().wedges(&area());
+        let exploded = disk_usage().with_exploded("home", 0.2).wedges(&area());
+        assert!(exploded[0].center().x > plain[0].center().x);
+        assert_eq!(exploded[1].center(), exploded[2].center());
+        // the radius shrinks so the exploded slice still fits
+        let outer = exploded[0].center().x + exploded[0].outer_radius();
+        assert!(outer <= 100. + 1e-9);
+    }
+
+    #[test]
+    fn percentages_skip_narrow_slices() {
+        let chart =
+            PieChart::new(&[("big", 99.), ("small", 1.)]).with_percentages(TextStyle::default());
+        let labels = chart.get_labels(&area()).unwrap();
+        assert_eq!(labels.len(), 1);
+        let chars = labels[0].txt().chars();
+        assert_eq!(chars.iter().map(|c| c.value()).collect::<String>(), "99%");
+    }
+
+    #[test]
+    fn legend_lists_every_slice() {
+        let chart = disk_usage().with_legend(TextStyle::default());
+        let legend = chart.legend().unwrap();
+        assert_eq!(legend.entries().len(), 3);
+        assert_eq!(legend.entries()[1].label(), "var");
+        assert!(chart.reserved_width() > 0);
+        assert_eq!(disk_usage().reserved_width(), 0);
+    }
+}
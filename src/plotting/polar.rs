@@ -0,0 +1,443 @@
+use super::{
+    common::{Drawable, MaskPoints, PixelSink},
+    legend::Legend,
+    limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    point::Point,
+    series::Series,
+    text::{Label, Text, TextPositioning, TextStyle},
+};
+use crate::common::Result;
+use std::f64::consts::TAU;
+
+const DEFAULT_NUM_RINGS: u32 = 4;
+const DEFAULT_SPOKE_INTERVAL: u32 = 30;
+const LABEL_GAP: f64 = 4.;
+// pixels between samples along the grid rings
+const RING_SAMPLE_SPACING: f64 = 2.;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum AngleUnit {
+    #[default]
+    Radians,
+    Degrees,
+}
+
+/// Where 0 degrees is and which way angles increase.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum PolarOrientation {
+    /// 0 degrees points right and angles increase counter-clockwise.
+    #[default]
+    Math,
+    /// 0 degrees points up and angles increase clockwise, like a compass bearing.
+    Compass,
+}
+
+/// Graph of (theta, r) points drawn around a center, with circular grid rings, angular spokes
+/// and degree labels in place of rectangular axes. The graph fills the largest circle that fits
+/// the drawable area of the canvas.
+#[derive(Debug, Clone)]
+pub struct PolarGraph {
+    data: Vec<Series<f64>>,
+    unit: AngleUnit,
+    orientation: PolarOrientation,
+    max_radius: Option<f64>,
+    num_rings: u32,
+    spoke_interval: u32,
+    grid: Option<LineStyle>,
+    labels: Option<TextStyle>,
+    legend: Option<TextStyle>,
+}
+
+impl Default for PolarGraph {
+    fn default() -> PolarGraph {
+        PolarGraph::new()
+    }
+}
+
+impl PolarGraph {
+    pub fn new() -> PolarGraph {
+        PolarGraph {
+            data: Vec::new(),
+            unit: AngleUnit::default(),
+            orientation: PolarOrientation::default(),
+            max_radius: None,
+            num_rings: DEFAULT_NUM_RINGS,
+            spoke_interval: DEFAULT_SPOKE_INTERVAL,
+            grid: None,
+            labels: None,
+            legend: None,
+        }
+    }
+
+    /// Adds a series whose points are (theta, r) pairs. Points with a negative radius are not
+    /// drawn.
+    pub fn with_series(mut self, series: Series<f64>) -> Self {
+        self.data.push(series);
+        self
+    }
+
+    pub fn with_angle_unit(mut self, unit: AngleUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn with_orientation(mut self, orientation: PolarOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the radius of the outer ring; points further out are not drawn.
+    pub fn with_max_radius(mut self, max_radius: f64) -> Self {
+        if max_radius <= 0. {
+            panic!("Polar max radius must be positive, got {max_radius}");
+        }
+        self.max_radius = Some(max_radius);
+        self
+    }
+
+    /// Draws grid rings and spokes with the given line style.
+    pub fn with_grid(mut self, line_style: LineStyle) -> Self {
+        self.grid = Some(line_style);
+        self
+    }
+
+    pub fn with_rings(mut self, num_rings: u32) -> Self {
+        if num_rings == 0 {
+            panic!("Polar graph must have at least one ring");
+        }
+        self.num_rings = num_rings;
+        self
+    }
+
+    /// Sets the angle between spokes, in degrees. Must evenly divide 360.
+    pub fn with_spoke_interval(mut self, degrees: u32) -> Self {
+        if degrees == 0 || 360 % degrees != 0 {
+            panic!("Spoke interval must evenly divide 360 degrees, got {degrees}");
+        }
+        self.spoke_interval = degrees;
+        self
+    }
+
+    /// Draws degree labels outside the spokes and radius labels along the rings.
+    pub fn with_labels(mut self, style: TextStyle) -> Self {
+        self.labels = Some(style);
+        self
+    }
+
+    /// Adds a legend listing the labeled series to the right of the graph.
+    pub fn with_legend(mut self, style: TextStyle) -> Self {
+        self.legend = Some(style);
+        self
+    }
+
+    pub fn data(&self) -> &[Series<f64>] {
+        &self.data
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Gets the radius of the outer ring: the explicit max radius, or the largest radius in the
+    /// data rounded up to 1, 2 or 5 times a power of ten so the ring labels are round numbers.
+    pub fn max_radius(&self) -> f64 {
+        self.max_radius.unwrap_or_else(|| {
+            let max = self
+                .data
+                .iter()
+                .flat_map(|series| series.data().iter().map(|p| p.y))
+                .fold(0., f64::max);
+            if max <= 0. || !max.is_finite() {
+                return 1.;
+            }
+            let magnitude = 10f64.powf(max.log10().floor());
+            [1., 2., 5., 10.]
+                .iter()
+                .map(|step| step * magnitude)
+                .find(|&nice| nice >= max)
+                .unwrap_or(10. * magnitude)
+        })
+    }
+
+    pub fn legend(&self) -> Option<Legend> {
+        let style = self.legend.as_ref()?;
+        let legend = self
+            .data
+            .iter()
+            .filter_map(|series| series.label().map(|label| (label, series.legend_color())))
+            .fold(Legend::new(style.clone()), |legend, (label, color)| {
+                legend.with_entry(label, color)
+            });
+        (!legend.is_empty()).then_some(legend)
+    }
+
+    /// Gets the horizontal space (in pixels) that must be left to the right of the graph.
+    pub fn reserved_width(&self) -> u32 {
+        self.legend().map(|l| l.reserved_width()).unwrap_or(0)
+    }
+
+    fn spoke_degrees(&self) -> Vec<u32> {
+        (0..360).step_by(self.spoke_interval as usize).collect()
+    }
+
+    fn degree_texts(&self, style: &TextStyle) -> Vec<Text> {
+        self.spoke_degrees()
+            .iter()
+            .map(|deg| Text::new(&format!("{deg}°"), style.clone()))
+            .collect()
+    }
+
+    /// Converts an angle in the graph's unit and orientation to radians measured
+    /// counter-clockwise from the positive x axis.
+    fn screen_angle(&self, theta: f64) -> f64 {
+        let radians = match self.unit {
+            AngleUnit::Radians => theta,
+            AngleUnit::Degrees => theta.to_radians(),
+        };
+        match self.orientation {
+            PolarOrientation::Math => radians,
+            PolarOrientation::Compass => TAU / 4. - radians,
+        }
+    }
+
+    /// Gets the center and pixel radius of the outer ring within the given area, leaving room
+    /// for degree labels.
+    pub fn geometry(&self, area: &Limits<u32>) -> (Point<f64>, f64) {
+        let center = Point::new(
+            (area.min().x + area.max().x) as f64 / 2.,
+            (area.min().y + area.max().y) as f64 / 2.,
+        );
+        let (span_x, span_y) = area.span();
+        let margin = match &self.labels {
+            Some(style) => {
+                self.degree_texts(style)
+                    .iter()
+                    .map(|txt| usize::max(txt.width(), txt.height()) as f64)
+                    .fold(0., f64::max)
+                    + LABEL_GAP
+            }
+            None => 0.,
+        };
+        let radius = (u32::min(span_x, span_y) as f64 / 2. - margin).max(0.);
+        (center, radius)
+    }
+
+    fn to_pixel(&self, center: &Point<f64>, angle: f64, radius: f64) -> Point<f64> {
+        Point::new(
+            center.x + radius * angle.cos(),
+            center.y + radius * angle.sin(),
+        )
+    }
+
+    fn draw_grid<S: PixelSink + ?Sized>(
+        &self,
+        line_style: &LineStyle,
+        center: &Point<f64>,
+        radius: f64,
+        sink: &mut S,
+    ) -> Result<()> {
+        for ring in 1..=self.num_rings {
+            let ring_radius = radius * ring as f64 / self.num_rings as f64;
+            let samples = usize::max(8, (TAU * ring_radius / RING_SAMPLE_SPACING) as usize);
+            let points = (0..=samples)
+                .map(|i| {
+                    let angle = TAU * i as f64 / samples as f64;
+                    self.to_pixel(center, angle, ring_radius).round()
+                })
+                .collect::<Vec<_>>();
+            for pair in points.windows(2) {
+                let pos = LinePositioning::BetweenPoints {
+                    start: pair[0],
+                    end: pair[1],
+                };
+                Line::new(pos, *line_style).draw(sink)?;
+            }
+        }
+        for deg in self.spoke_degrees() {
+            let angle = self.screen_angle_degrees(deg as f64);
+            let pos = LinePositioning::BetweenPoints {
+                start: center.round(),
+                end: self.to_pixel(center, angle, radius).round(),
+            };
+            Line::new(pos, *line_style).draw(sink)?;
+        }
+        Ok(())
+    }
+
+    fn screen_angle_degrees(&self, degrees: f64) -> f64 {
+        match self.unit {
+            AngleUnit::Radians => self.screen_angle(degrees.to_radians()),
+            AngleUnit::Degrees => self.screen_angle(degrees),
+        }
+    }
+
+    /// Gets the graph for drawing at `factor` times the resolution, with the grid and series
+    /// scaled to cover about the same area.
+    pub(crate) fn magnified(&self, factor: u32) -> PolarGraph {
+        let mut graph = self.clone();
+        graph.grid = self.grid.map(|style| style.magnified(factor));
+        graph.data = self
+            .data
+            .iter()
+            .map(|series| series.magnified(factor))
+            .collect();
+        graph
+    }
+
+    pub fn draw<S: PixelSink + ?Sized>(&self, area: &Limits<u32>, sink: &mut S) -> Result<()> {
+        let (center, radius) = self.geometry(area);
+        let max_radius = self.max_radius();
+
+        if let Some(line_style) = &self.grid {
+            self.draw_grid(line_style, &center, radius, sink)?;
+        }
+
+        for series in &self.data {
+            let visible = series.filter(|p| (0. ..=max_radius).contains(&p.y));
+            if visible.data().is_empty() {
+                continue;
+            }
+            let pixels = visible
+                .data()
+                .iter()
+                .map(|p| {
+                    let angle = self.screen_angle(p.x);
+                    self.to_pixel(&center, angle, p.y / max_radius * radius)
+                })
+                .collect::<Vec<_>>();
+            visible.clone_with(&pixels).draw(sink)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_mask(&self, area: &Limits<u32>) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(area, &mut masks)?;
+        Ok(masks)
+    }
+
+    pub fn get_labels(&self, area: &Limits<u32>) -> Result<Vec<Label>> {
+        let Some(style) = &self.labels else {
+            return Ok(Vec::new());
+        };
+        let (center, radius) = self.geometry(area);
+
+        // degree labels sit just outside the outer ring, at the end of each spoke
+        let degree_labels = self
+            .degree_texts(style)
+            .into_iter()
+            .zip(self.spoke_degrees())
+            .map(|(txt, deg)| {
+                let angle = self.screen_angle_degrees(deg as f64);
+                let offset = usize::max(txt.width(), txt.height()) as f64 / 2. + LABEL_GAP;
+                let pos = self.to_pixel(&center, angle, radius + offset).round();
+                Label::new(txt, TextPositioning::Centered(pos))
+            });
+
+        // radius labels run up through the sector right of vertical, where they are stacked
+        // rather than side by side and don't cover a spoke
+        let label_angle = (90. - self.spoke_interval as f64 / 2.).to_radians();
+        let max_radius = self.max_radius();
+        let radius_labels = (1..=self.num_rings).map(|ring| {
+            let fraction = ring as f64 / self.num_rings as f64;
+            let txt = Text::from_number(max_radius * fraction, 3, style.clone());
+            let pos = self
+                .to_pixel(&center, label_angle, radius * fraction)
+                .round();
+            Label::new(txt, TextPositioning::Centered(pos))
+        });
+
+        Ok(degree_labels.chain(radius_labels).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn area() -> Limits<u32> {
+        Limits::new(Point::new(0, 0), Point::new(100, 100))
+    }
+
+    fn lit_points(graph: &PolarGraph) -> Vec<Point<u32>> {
+        graph
+            .get_mask(&area())
+            .unwrap()
+            .into_iter()
+            .flat_map(|m| m.points)
+            .collect()
+    }
+
+    #[test]
+    fn points_are_placed_by_angle_and_radius() {
+        let series = Series::new(&[Point::new(90., 10.)]).with_marker_style(
+            crate::plotting::marker::MarkerStyle::FilledSquare {
+                size: 0,
+                color: colors::WHITE,
+            },
+        );
+        let graph = PolarGraph::new()
+            .with_angle_unit(AngleUnit::Degrees)
+            .with_series(series.clone());
+        // the only point is on the outer ring, straight up
+        assert_eq!(lit_points(&graph), vec![Point::new(50, 100)]);
+
+        let graph = graph.with_orientation(PolarOrientation::Compass);
+        assert_eq!(lit_points(&graph), vec![Point::new(100, 50)]);
+    }
+
+    #[test]
+    fn points_beyond_max_radius_are_not_drawn() {
+        let series = Series::new(&[Point::new(0., 1.), Point::new(0., 5.), Point::new(0., -1.)]);
+        let graph = PolarGraph::new().with_max_radius(2.).with_series(series);
+        let points = lit_points(&graph);
+        assert!(points.iter().all(|p| p.x <= 75 + 2));
+        assert!(!points.is_empty());
+    }
+
+    #[test]
+    fn max_radius_rounds_up_to_a_round_number() {
+        let graph = |r: f64| PolarGraph::new().with_series(Series::new(&[Point::new(0., r)]));
+        assert_eq!(graph(3.99).max_radius(), 5.);
+        assert_eq!(graph(0.15).max_radius(), 0.2);
+        assert_eq!(graph(10.).max_radius(), 10.);
+        assert_eq!(graph(3.99).with_max_radius(3.).max_radius(), 3.);
+    }
+
+    #[test]
+    fn grid_draws_rings_and_spokes() {
+        let series = Series::new(&[Point::new(0., 1.)]);
+        let graph = PolarGraph::new()
+            .with_series(series)
+            .with_rings(2)
+            .with_grid(LineStyle::default());
+        let points = lit_points(&graph);
+        // inner ring at the top and the vertical spoke below the center
+        assert!(points.contains(&Point::new(50, 75)));
+        assert!(points.contains(&Point::new(50, 10)));
+    }
+
+    #[test]
+    fn labels_leave_room_outside_the_rings() {
+        let graph = PolarGraph::new()
+            .with_series(Series::new(&[Point::new(0., 1.)]))
+            .with_labels(TextStyle::default());
+        let (_, radius) = graph.geometry(&area());
+        assert!(radius < 50.);
+
+        let labels = graph.get_labels(&area()).unwrap();
+        assert_eq!(labels.len(), 12 + DEFAULT_NUM_RINGS as usize);
+        for label in &labels[..12] {
+            assert!(area().contains(label.pos().point()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn uneven_spoke_interval_panics() {
+        PolarGraph::new().with_spoke_interval(25);
+    }
+}
@@ -0,0 +1,314 @@
+use super::{
+    arrow::Arrow,
+    colormaps::Colormap,
+    colors,
+    common::{
+        Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable, magnify,
+    },
+    limits::Limits,
+    line::LineStyle,
+    point::{Point, PointCollection},
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+// fraction of the grid spacing covered by the longest arrow when scaling automatically
+const AUTO_SCALE_FILL: f64 = 0.9;
+
+/// Vector field drawn as one arrow per (x, y, dx, dy) row. Each arrow starts at (x, y) and points
+/// along (dx, dy) multiplied by a common scale, so arrow lengths (and their heads) are
+/// proportional to the field magnitude. Arrows can optionally be colored by their magnitude.
+#[derive(Debug, Clone)]
+pub struct Quiver<T: Graphable> {
+    tails: Vec<Point<T>>,
+    tips: Vec<Point<T>>,
+    magnitudes: Vec<f64>,
+    vectors: Vec<Point<f64>>,
+    scale: f64,
+    color: RGB8,
+    colormap: Option<Colormap>,
+    thickness: u32,
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for Quiver<T> {
+    type ConvertTo = Quiver<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        self.map_points(|p| p.convert_to(convert_fn))
+    }
+}
+
+impl Quiver<f64> {
+    /// Creates a field from (x, y, dx, dy) rows. Arrows are scaled so that the longest one
+    /// nearly reaches the next grid position. Panics if there are no rows or any value is not
+    /// finite.
+    pub fn new(rows: &[(f64, f64, f64, f64)]) -> Quiver<f64> {
+        if rows.is_empty() {
+            panic!("Quiver plot must have at least one row");
+        }
+        if let Some(row) = rows
+            .iter()
+            .find(|(x, y, dx, dy)| [x, y, dx, dy].iter().any(|v| !v.is_finite()))
+        {
+            panic!("Quiver values must be finite, got {row:?}");
+        }
+
+        let tails = rows
+            .iter()
+            .map(|&(x, y, _, _)| Point::new(x, y))
+            .collect::<Vec<_>>();
+        let vectors = rows
+            .iter()
+            .map(|&(_, _, dx, dy)| Point::new(dx, dy))
+            .collect::<Vec<_>>();
+        let magnitudes = vectors
+            .iter()
+            .map(|v| v.dist(&Point::new(0., 0.)))
+            .collect::<Vec<_>>();
+
+        let quiver = Quiver {
+            tips: tails.clone(),
+            tails,
+            magnitudes,
+            vectors,
+            scale: 1.,
+            color: colors::WHITE,
+            colormap: None,
+            thickness: 0,
+        };
+        let scale = quiver.auto_scale();
+        quiver.with_scale(scale)
+    }
+
+    /// Sets the factor applied to every (dx, dy) vector, in place of the automatic scale.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        if !scale.is_finite() || scale <= 0. {
+            panic!("Quiver scale must be positive, got {scale}");
+        }
+        self.scale = scale;
+        self.tips = self
+            .tails
+            .iter()
+            .zip(&self.vectors)
+            .map(|(&tail, &vector)| tail + vector * scale)
+            .collect();
+        self
+    }
+
+    /// Gets the scale at which the longest arrow covers most of the smallest gap between
+    /// distinct x or y positions.
+    fn auto_scale(&self) -> f64 {
+        let max_magnitude = self.magnitudes.iter().copied().fold(0., f64::max);
+        if max_magnitude == 0. {
+            return 1.;
+        }
+
+        let smallest_gap = |values: Vec<f64>| {
+            let mut values = values;
+            values.sort_by(f64::total_cmp);
+            values
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .filter(|gap| *gap > 0.)
+                .fold(f64::INFINITY, f64::min)
+        };
+        let gap = f64::min(
+            smallest_gap(self.tails.iter().map(|p| p.x).collect()),
+            smallest_gap(self.tails.iter().map(|p| p.y).collect()),
+        );
+        match gap.is_finite() {
+            true => AUTO_SCALE_FILL * gap / max_magnitude,
+            false => 1. / max_magnitude,
+        }
+    }
+}
+
+impl<T: Graphable> Quiver<T> {
+    pub fn with_color(mut self, color: RGB8) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Colors each arrow by its magnitude, from the smallest to the largest in the field.
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Gets the field for drawing at `factor` times the resolution, with the arrow thickness
+    /// scaled to cover about the same width.
+    pub(crate) fn magnified(&self, factor: u32) -> Quiver<T> {
+        let mut quiver = self.clone();
+        quiver.thickness = magnify(self.thickness, factor);
+        quiver
+    }
+
+    pub fn tails(&self) -> &[Point<T>] {
+        &self.tails
+    }
+
+    pub fn tips(&self) -> &[Point<T>] {
+        &self.tips
+    }
+
+    pub fn magnitudes(&self) -> &[f64] {
+        &self.magnitudes
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn colormap(&self) -> Option<Colormap> {
+        self.colormap
+    }
+
+    /// Gets the smallest and largest magnitude, e.g. for a matching [`Colorbar`].
+    ///
+    /// [`Colorbar`]: super::colorbar::Colorbar
+    pub fn magnitude_range(&self) -> (f64, f64) {
+        let min = self
+            .magnitudes
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let max = self.magnitudes.iter().copied().fold(0., f64::max);
+        (min, max)
+    }
+
+    /// Gets the limits covering every tail and tip.
+    pub fn limits(&self) -> Limits<T> {
+        self.tails
+            .iter()
+            .chain(&self.tips)
+            .copied()
+            .collect::<Vec<_>>()
+            .limits()
+            .unwrap()
+    }
+
+    fn map_points<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> Quiver<U> {
+        Quiver {
+            tails: self.tails.iter().map(|&p| map_fn(p)).collect(),
+            tips: self.tips.iter().map(|&p| map_fn(p)).collect(),
+            magnitudes: self.magnitudes.clone(),
+            vectors: self.vectors.clone(),
+            scale: self.scale,
+            color: self.color,
+            colormap: self.colormap,
+            thickness: self.thickness,
+        }
+    }
+
+    fn arrow_color(&self, magnitude: f64) -> RGB8 {
+        match self.colormap {
+            Some(colormap) => {
+                let (min, max) = self.magnitude_range();
+                colormap.color_for(magnitude, min, max)
+            }
+            None => self.color,
+        }
+    }
+}
+
+impl<T: Graphable> Drawable for Quiver<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        let quiver = self.convert_to_f64();
+        let arrows = quiver
+            .tails
+            .iter()
+            .zip(&quiver.tips)
+            .zip(&quiver.magnitudes);
+        for ((&tail, &tip), &magnitude) in arrows {
+            let style = LineStyle::Solid {
+                color: quiver.arrow_color(magnitude),
+                thickness: quiver.thickness,
+            };
+            Arrow::new(tail, tip, style).draw(sink)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, U> Scalable<T, U> for Quiver<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = Quiver<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        self.map_points(|p| p.scale_to(old_limits, new_limits))
+    }
+}
+
+impl<T> Shiftable<T> for Quiver<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(self, amount: Point<T>) -> Self {
+        self.map_points(|p| p + amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> Quiver<f64> {
+        Quiver::new(&[
+            (0., 0., 1., 0.),
+            (1., 0., 0., 2.),
+            (0., 1., -1., -1.),
+            (1., 1., 0., 0.),
+        ])
+    }
+
+    #[test]
+    fn longest_arrow_nearly_fills_grid_spacing() {
+        let quiver = field();
+        assert_eq!(quiver.scale(), 0.45);
+        assert_eq!(quiver.tips()[0], Point::new(0.45, 0.));
+        assert_eq!(quiver.tips()[1], Point::new(1., 0.9));
+        // zero vectors have their tip on the tail
+        assert_eq!(quiver.tips()[3], Point::new(1., 1.));
+        assert_eq!(quiver.magnitude_range(), (0., 2.));
+    }
+
+    #[test]
+    fn explicit_scale_moves_tips_and_limits() {
+        let quiver = field().with_scale(1.);
+        assert_eq!(quiver.tips()[1], Point::new(1., 2.));
+        assert_eq!(
+            quiver.limits(),
+            Limits::new(Point::new(-1., 0.), Point::new(1., 2.))
+        );
+    }
+
+    #[test]
+    fn colormap_colors_arrows_by_magnitude() {
+        let quiver = Quiver::new(&[(0., 0., 10., 0.), (20., 0., 40., 0.)])
+            .with_scale(1.)
+            .with_colormap(Colormap::Viridis);
+        let masks = quiver.get_mask().unwrap();
+        assert!(
+            masks
+                .iter()
+                .any(|m| m.color == Colormap::Viridis.color_at(0.))
+        );
+        assert!(
+            masks
+                .iter()
+                .any(|m| m.color == Colormap::Viridis.color_at(1.))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_finite_rows_panic() {
+        Quiver::new(&[(0., 0., f64::NAN, 1.)]);
+    }
+}
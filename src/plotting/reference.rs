@@ -0,0 +1,238 @@
+use super::{
+    common::{
+        Convertable, Drawable, FloatConvertable, Graphable, MaskPoints, PixelSink, Scalable,
+        Shiftable,
+    },
+    limits::Limits,
+    line::{Line, LineStyle},
+    line_positioning::LinePositioning,
+    point::Point,
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+/// Annotation at fixed data coordinates that runs across the whole graph area, e.g. a threshold
+/// or SLO target (lines) or an incident window (spans). Unlike [`GridLines`], the positions are
+/// chosen by the caller rather than derived from the limits. References are plotted against the
+/// primary y axis and widen the graph limits so that they are always visible.
+///
+/// [`GridLines`]: super::grid_lines::GridLines
+#[derive(Debug, Clone)]
+pub enum Reference<T: Graphable> {
+    /// Horizontal line at a y value.
+    HLine { y: T, style: LineStyle },
+    /// Vertical line at an x value.
+    VLine { x: T, style: LineStyle },
+    /// Band between two y values, filled across the graph.
+    HSpan { min: T, max: T, fill: RGB8 },
+    /// Band between two x values, filled up the graph.
+    VSpan { min: T, max: T, fill: RGB8 },
+}
+
+impl<T: Graphable, U: Graphable> Convertable<U> for Reference<T> {
+    type ConvertTo = Reference<U>;
+    fn convert_to(&self, convert_fn: fn(f64) -> U) -> Self::ConvertTo {
+        self.map_points(|p| p.convert_to(convert_fn))
+    }
+}
+
+impl<T: Graphable> Reference<T> {
+    /// Gets the x range the reference must be visible in, if it has one.
+    pub fn x_range(&self) -> Option<(T, T)> {
+        match *self {
+            Reference::VLine { x, .. } => Some((x, x)),
+            Reference::VSpan { min, max, .. } => Some((min, max)),
+            Reference::HLine { .. } | Reference::HSpan { .. } => None,
+        }
+    }
+
+    /// Gets the y range the reference must be visible in, if it has one.
+    pub fn y_range(&self) -> Option<(T, T)> {
+        match *self {
+            Reference::HLine { y, .. } => Some((y, y)),
+            Reference::HSpan { min, max, .. } => Some((min, max)),
+            Reference::VLine { .. } | Reference::VSpan { .. } => None,
+        }
+    }
+
+    pub fn is_span(&self) -> bool {
+        matches!(self, Reference::HSpan { .. } | Reference::VSpan { .. })
+    }
+
+    // each position is mapped as both coordinates of a point, keeping the one along its axis
+    fn map_points<U: Graphable, F: Fn(Point<T>) -> Point<U>>(&self, map_fn: F) -> Reference<U> {
+        let map_x = |x: T| map_fn(Point::new(x, x)).x;
+        let map_y = |y: T| map_fn(Point::new(y, y)).y;
+        match *self {
+            Reference::HLine { y, style } => Reference::HLine { y: map_y(y), style },
+            Reference::VLine { x, style } => Reference::VLine { x: map_x(x), style },
+            Reference::HSpan { min, max, fill } => Reference::HSpan {
+                min: map_y(min),
+                max: map_y(max),
+                fill,
+            },
+            Reference::VSpan { min, max, fill } => Reference::VSpan {
+                min: map_x(min),
+                max: map_x(max),
+                fill,
+            },
+        }
+    }
+}
+
+impl<T: FloatConvertable + Graphable> Reference<T> {
+    /// Draws the reference across the given graph area. Anything outside the area (e.g. beyond
+    /// explicit graph limits) is not drawn.
+    pub fn draw<S: PixelSink + ?Sized>(&self, limits: &Limits<T>, sink: &mut S) -> Result<()> {
+        let limits = limits.convert_to_f64();
+        let (min, max) = (*limits.min(), *limits.max());
+        let (span_x, span_y) = limits.span();
+
+        match self.convert_to_f64() {
+            Reference::HLine { y, style } if (min.y..=max.y).contains(&y) => {
+                let start = Point::new(min.x, y);
+                let positioning = LinePositioning::Horizontal {
+                    start,
+                    length: span_x,
+                };
+                Line::new(positioning, style).draw(sink)?;
+            }
+            Reference::VLine { x, style } if (min.x..=max.x).contains(&x) => {
+                let start = Point::new(x, min.y);
+                let positioning = LinePositioning::Vertical {
+                    start,
+                    length: span_y,
+                };
+                Line::new(positioning, style).draw(sink)?;
+            }
+            Reference::HSpan {
+                min: y_min,
+                max: y_max,
+                fill,
+            } => fill_rect(
+                Point::new(min.x, y_min.max(min.y)),
+                Point::new(max.x, y_max.min(max.y)),
+                fill,
+                sink,
+            ),
+            Reference::VSpan {
+                min: x_min,
+                max: x_max,
+                fill,
+            } => fill_rect(
+                Point::new(x_min.max(min.x), min.y),
+                Point::new(x_max.min(max.x), max.y),
+                fill,
+                sink,
+            ),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn get_mask(&self, limits: &Limits<T>) -> Result<Vec<MaskPoints>> {
+        let mut masks = Vec::new();
+        self.draw(limits, &mut masks)?;
+        Ok(masks)
+    }
+}
+
+/// Draws a filled rectangle, or nothing if the corners are out of order.
+fn fill_rect<S: PixelSink + ?Sized>(min: Point<f64>, max: Point<f64>, color: RGB8, sink: &mut S) {
+    if min.x > max.x || min.y > max.y {
+        return;
+    }
+    sink.set_rect(&min.round(), &max.round(), &color);
+}
+
+impl<T, U> Scalable<T, U> for Reference<T>
+where
+    T: FloatConvertable + Graphable,
+    U: FloatConvertable + Graphable,
+{
+    type ScaleTo = Reference<f64>;
+    fn scale_to(self, old_limits: &Limits<T>, new_limits: &Limits<U>) -> Self::ScaleTo {
+        self.map_points(|p| p.scale_to(old_limits, new_limits))
+    }
+}
+
+impl<T> Shiftable<T> for Reference<T>
+where
+    T: FloatConvertable + Graphable,
+{
+    fn shift_by(self, amount: Point<T>) -> Self {
+        self.map_points(|p| p + amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::colors;
+
+    fn area() -> Limits<f64> {
+        Limits::new(Point::new(10., 10.), Point::new(20., 30.))
+    }
+
+    fn points(masks: Vec<MaskPoints>) -> Vec<Point<u32>> {
+        masks.into_iter().flat_map(|m| m.points).collect()
+    }
+
+    #[test]
+    fn lines_cross_the_whole_area() {
+        let style = LineStyle::Solid {
+            color: colors::RED,
+            thickness: 0,
+        };
+        let hline = points(
+            Reference::HLine { y: 15., style }
+                .get_mask(&area())
+                .unwrap(),
+        );
+        assert!(hline.contains(&Point::new(10, 15)));
+        assert!(hline.contains(&Point::new(20, 15)));
+        assert!(hline.iter().all(|p| p.y == 15));
+
+        let vline = points(
+            Reference::VLine { x: 12., style }
+                .get_mask(&area())
+                .unwrap(),
+        );
+        assert!(vline.contains(&Point::new(12, 10)));
+        assert!(vline.contains(&Point::new(12, 30)));
+        assert!(vline.iter().all(|p| p.x == 12));
+    }
+
+    #[test]
+    fn spans_are_clipped_to_the_area() {
+        let span = points(
+            Reference::HSpan {
+                min: 25.,
+                max: 40.,
+                fill: colors::GRAY,
+            }
+            .get_mask(&area())
+            .unwrap(),
+        );
+        assert_eq!(span.len(), 11 * 6);
+        assert!(span.iter().all(|p| (25..=30).contains(&p.y)));
+
+        let outside = Reference::VSpan {
+            min: 40.,
+            max: 50.,
+            fill: colors::GRAY,
+        }
+        .get_mask(&area())
+        .unwrap();
+        assert!(points(outside).is_empty());
+    }
+
+    #[test]
+    fn lines_outside_the_area_are_not_drawn() {
+        let reference = Reference::HLine {
+            y: 35.,
+            style: LineStyle::default(),
+        };
+        assert!(reference.get_mask(&area()).unwrap().is_empty());
+    }
+}
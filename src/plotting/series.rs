@@ -1,8 +1,10 @@
 use super::{
+    colormaps::Colormap,
     common::{
-        Convertable, Drawable, FloatConvertable, Graphable, MaskPoints, Scalable, Shiftable,
-        UIntConvertable,
+        Convertable, Drawable, FloatConvertable, Graphable, PixelSink, Scalable, Shiftable,
+        UIntConvertable, magnify,
     },
+    interpolation::LineInterpolation,
     limits::Limits,
     line::{Line, LineStyle},
     line_positioning::LinePositioning,
@@ -10,13 +12,36 @@ use super::{
     point::Point,
 };
 use crate::common::Result;
+use rgb::RGB8;
 use std::ops::{Add, Div, Mul, Sub};
 
+/// Per-point overrides applied on top of the series marker style. Unset fields fall back to the
+/// series marker style.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PointStyle {
+    pub color: Option<RGB8>,
+    pub size: Option<u32>,
+}
+
+/// The y axis a series is plotted against. Secondary series are scaled to the right-hand y
+/// axis of the graph, independently of the primary series.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum YAxis {
+    #[default]
+    Primary,
+    Secondary,
+}
+
 #[derive(Debug, Clone)]
 pub struct Series<T: Graphable> {
     data: Vec<Point<T>>,
     marker_style: MarkerStyle,
+    point_styles: Option<Vec<PointStyle>>,
     line_style: Option<LineStyle>,
+    interpolation: LineInterpolation,
+    label: Option<String>,
+    y_axis: YAxis,
+    marker_stroke: u32,
 }
 
 impl<T: Graphable, U: Graphable> Convertable<U> for Series<T> {
@@ -40,20 +65,84 @@ impl<T: Graphable> Series<T> {
         Series {
             data: Vec::from(data),
             marker_style: MarkerStyle::default(),
+            point_styles: None,
             line_style: None,
+            interpolation: LineInterpolation::default(),
+            label: None,
+            y_axis: YAxis::default(),
+            marker_stroke: 0,
         }
     }
 
+    /// Creates a series with the same styling but different data. Any per-point styles are kept
+    /// as-is, so the new data should correspond one-to-one with the existing data.
     pub fn clone_with<U: Graphable>(&self, data: &[Point<U>]) -> Series<U> {
         let marker_style = self.marker_style.clone();
+        let point_styles = self.point_styles.clone();
         let line_style = self.line_style.clone();
         Series {
             data: Vec::from(data),
             marker_style,
+            point_styles,
             line_style,
+            interpolation: self.interpolation,
+            label: self.label.clone(),
+            y_axis: self.y_axis,
+            marker_stroke: self.marker_stroke,
         }
     }
 
+    /// Creates a series containing only the points that satisfy the predicate, keeping any
+    /// per-point styles aligned with the remaining points.
+    pub fn filter<F: Fn(&Point<T>) -> bool>(&self, predicate: F) -> Series<T> {
+        let keep = self.data.iter().map(predicate).collect::<Vec<_>>();
+        let data = self
+            .data
+            .iter()
+            .zip(&keep)
+            .filter(|(_, keep)| **keep)
+            .map(|(p, _)| *p)
+            .collect::<Vec<_>>();
+        let point_styles = self.point_styles.as_ref().map(|styles| {
+            styles
+                .iter()
+                .zip(&keep)
+                .filter(|(_, keep)| **keep)
+                .map(|(style, _)| *style)
+                .collect::<Vec<_>>()
+        });
+
+        let mut series = self.clone_with(&data);
+        series.point_styles = point_styles;
+        series
+    }
+
+    /// Gets the series for drawing at `factor` times the resolution, with the line thickness,
+    /// marker sizes and marker outlines scaled to cover about the same area.
+    pub(crate) fn magnified(&self, factor: u32) -> Series<T> {
+        // pixel markers have no size for points to override
+        let pixels = matches!(self.marker_style, MarkerStyle::Pixel { .. });
+        let point_styles = self.point_styles.as_ref().map(|styles| {
+            styles
+                .iter()
+                .map(|style| PointStyle {
+                    color: style.color,
+                    size: match pixels {
+                        true => None,
+                        false => style.size.map(|size| magnify(size, factor)),
+                    },
+                })
+                .collect()
+        });
+
+        let mut series = self.clone();
+        series.marker_style = self.marker_style.magnified(factor);
+        series.point_styles = point_styles;
+        series.line_style = self.line_style.map(|style| style.magnified(factor));
+        series.marker_stroke = magnify(self.marker_stroke, factor);
+        series
+    }
+
     pub fn data(&self) -> &[Point<T>] {
         &self.data
     }
@@ -66,6 +155,64 @@ impl<T: Graphable> Series<T> {
         &self.line_style
     }
 
+    pub fn interpolation(&self) -> LineInterpolation {
+        self.interpolation
+    }
+
+    pub fn y_axis(&self) -> YAxis {
+        self.y_axis
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Gets the color that represents the series in a legend: the line color if the series has
+    /// a line, otherwise the marker color.
+    pub fn legend_color(&self) -> RGB8 {
+        match &self.line_style {
+            Some(line_style) => line_style.color(),
+            None => self.marker_style.color(),
+        }
+    }
+
+    pub fn point_styles(&self) -> Option<&[PointStyle]> {
+        self.point_styles.as_deref()
+    }
+
+    /// Gets the marker style for the point at the given index, with any per-point overrides
+    /// applied.
+    pub fn marker_style_at(&self, index: usize) -> MarkerStyle {
+        let point_style = self
+            .point_styles
+            .as_ref()
+            .and_then(|styles| styles.get(index))
+            .copied()
+            .unwrap_or_default();
+
+        let mut style = self.marker_style.clone();
+        if let Some(color) = point_style.color {
+            style = style.with_color(color);
+        }
+        if let Some(size) = point_style.size {
+            style = style.with_size(size);
+        }
+        style
+    }
+
+    /// Gets the largest marker size used by any point in the series.
+    pub fn max_marker_size(&self) -> u32 {
+        let base_size = self.marker_style.size();
+        match &self.point_styles {
+            Some(styles) => styles
+                .iter()
+                .map(|style| style.size.unwrap_or(base_size))
+                .max()
+                .unwrap_or(base_size),
+            None => base_size,
+        }
+    }
+
     pub fn with_marker_style(mut self, marker_style: MarkerStyle) -> Self {
         self.marker_style = marker_style;
         self
@@ -75,32 +222,151 @@ impl<T: Graphable> Series<T> {
         self.line_style = Some(line_style);
         self
     }
-}
 
-impl<T: UIntConvertable + Graphable> Drawable for Series<T> {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
-        let mut mask_points = self
-            .data()
+    /// Sets how the line between consecutive points is drawn. Only used if the series has a line
+    /// style.
+    pub fn with_interpolation(mut self, interpolation: LineInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Selects the y axis the series is plotted against.
+    pub fn with_y_axis(mut self, y_axis: YAxis) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
+    /// Sets the name shown for the series in the graph legend.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Sets per-point style overrides. There must be exactly one style per data point.
+    pub fn with_point_styles(mut self, point_styles: &[PointStyle]) -> Self {
+        if point_styles.len() != self.data.len() {
+            panic!(
+                "Number of point styles ({}) must match number of data points ({})",
+                point_styles.len(),
+                self.data.len()
+            );
+        }
+        self.point_styles = Some(Vec::from(point_styles));
+        self
+    }
+
+    /// Colors each marker by mapping the corresponding value through the colormap, where the
+    /// range [min, max] spans the full colormap. There must be exactly one value per data point.
+    pub fn with_color_values(self, values: &[f64], colormap: Colormap, min: f64, max: f64) -> Self {
+        let colors = values
+            .iter()
+            .map(|&v| colormap.color_for(v, min, max))
+            .collect::<Vec<_>>();
+        self.update_point_styles(values.len(), |i, style| style.color = Some(colors[i]))
+    }
+
+    /// Sizes each marker by linearly mapping the corresponding value from the range of all values
+    /// to [min_size, max_size]. There must be exactly one value per data point.
+    pub fn with_size_values(self, values: &[f64], min_size: u32, max_size: u32) -> Self {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let span = max - min;
+        let size_span = max_size.saturating_sub(min_size) as f64;
+
+        let sizes = values
             .iter()
-            .flat_map(|&p| {
-                Marker::new(p.convert_to_u32(), self.marker_style.clone())
-                    .get_mask()
-                    .unwrap()
+            .map(|&v| {
+                let t = if span > 0. { (v - min) / span } else { 0.5 };
+                min_size + (t * size_span).round() as u32
             })
             .collect::<Vec<_>>();
+        self.update_point_styles(values.len(), |i, style| style.size = Some(sizes[i]))
+    }
+
+    fn update_point_styles<F: Fn(usize, &mut PointStyle)>(
+        mut self,
+        num_values: usize,
+        update_fn: F,
+    ) -> Self {
+        if num_values != self.data.len() {
+            panic!(
+                "Number of values ({}) must match number of data points ({})",
+                num_values,
+                self.data.len()
+            );
+        }
+        let mut point_styles = self
+            .point_styles
+            .take()
+            .unwrap_or_else(|| vec![PointStyle::default(); self.data.len()]);
+        point_styles
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, style)| update_fn(i, style));
+        self.point_styles = Some(point_styles);
+        self
+    }
+}
+
+impl<T: UIntConvertable + Graphable> Drawable for Series<T> {
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        for (i, &p) in self.data().iter().enumerate() {
+            Marker::new(p.convert_to_u32(), self.marker_style_at(i))
+                .with_stroke(self.marker_stroke)
+                .draw(sink)?;
+        }
 
         // add lines if line styling is present
         if let Some(line_style) = &self.line_style {
-            for i in 0..self.data.len() - 1 {
-                let start = self.data[i];
-                let end = self.data[i + 1];
-                let pos = LinePositioning::BetweenPoints { start, end };
-                let line = Line::new(pos.convert_to_u32(), line_style.clone());
-                mask_points.extend(line.get_mask()?);
+            match self.interpolation {
+                LineInterpolation::Linear => {
+                    for pair in self.data.windows(2) {
+                        let pos = LinePositioning::BetweenPoints {
+                            start: pair[0],
+                            end: pair[1],
+                        };
+                        Line::new(pos.convert_to_u32(), *line_style).draw(sink)?;
+                    }
+                }
+                interpolation => {
+                    // the data is already in pixel space, so curves are sampled per pixel
+                    let data = self
+                        .data
+                        .iter()
+                        .map(|p| p.convert_to_f64())
+                        .collect::<Vec<_>>();
+                    let path = interpolation.path(&data);
+                    for pair in path.windows(2) {
+                        let pos = segment_positioning(pair[0].round(), pair[1].round());
+                        Line::new(pos, *line_style).draw(sink)?;
+                    }
+                }
             }
         };
 
-        Ok(mask_points)
+        Ok(())
+    }
+}
+
+/// Gets the positioning for a path segment. Flat segments (e.g. the risers and treads of steps)
+/// use horizontal/vertical positioning so they are drawn with the line thickness.
+fn segment_positioning(start: Point<u32>, end: Point<u32>) -> LinePositioning<u32> {
+    let (min, max) = (
+        Point::new(u32::min(start.x, end.x), u32::min(start.y, end.y)),
+        Point::new(u32::max(start.x, end.x), u32::max(start.y, end.y)),
+    );
+    if start.y == end.y {
+        LinePositioning::Horizontal {
+            start: min,
+            length: max.x - min.x,
+        }
+    } else if start.x == end.x {
+        LinePositioning::Vertical {
+            start: min,
+            length: max.y - min.y,
+        }
+    } else {
+        LinePositioning::BetweenPoints { start, end }
     }
 }
 
@@ -188,6 +454,7 @@ impl<T: Graphable> Sub<Point<T>> for Series<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plotting::colors;
 
     #[test]
     fn add_f32_to_series() {
@@ -255,4 +522,126 @@ mod tests {
         let data: Vec<Point<f32>> = vec![];
         Series::new(&data);
     }
+
+    fn three_point_series() -> Series<i32> {
+        Series::new(&[Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]).with_marker_style(
+            MarkerStyle::FilledCircle {
+                size: 2,
+                color: colors::WHITE,
+            },
+        )
+    }
+
+    #[test]
+    fn marker_style_at_without_overrides_uses_series_style() {
+        let series = three_point_series();
+        for i in 0..3 {
+            assert_eq!(series.marker_style_at(i).size(), 2);
+            assert_eq!(series.marker_style_at(i).color(), colors::WHITE);
+        }
+    }
+
+    #[test]
+    fn with_color_values_maps_through_colormap() {
+        let series =
+            three_point_series().with_color_values(&[0., 5., 10.], Colormap::Viridis, 0., 10.);
+        assert_eq!(
+            series.marker_style_at(0).color(),
+            Colormap::Viridis.color_at(0.)
+        );
+        assert_eq!(
+            series.marker_style_at(1).color(),
+            Colormap::Viridis.color_at(0.5)
+        );
+        assert_eq!(
+            series.marker_style_at(2).color(),
+            Colormap::Viridis.color_at(1.)
+        );
+        // size is untouched
+        assert_eq!(series.marker_style_at(2).size(), 2);
+    }
+
+    #[test]
+    fn with_size_values_maps_to_size_range() {
+        let series = three_point_series().with_size_values(&[10., 20., 30.], 1, 5);
+        assert_eq!(series.marker_style_at(0).size(), 1);
+        assert_eq!(series.marker_style_at(1).size(), 3);
+        assert_eq!(series.marker_style_at(2).size(), 5);
+        assert_eq!(series.max_marker_size(), 5);
+    }
+
+    #[test]
+    fn color_and_size_overrides_combine() {
+        let series = three_point_series()
+            .with_size_values(&[1., 2., 3.], 2, 6)
+            .with_color_values(&[1., 2., 3.], Colormap::Magma, 1., 3.);
+        let style = series.marker_style_at(2);
+        assert_eq!(style.size(), 6);
+        assert_eq!(style.color(), Colormap::Magma.color_at(1.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_color_values_wrong_length_panics() {
+        three_point_series().with_color_values(&[1., 2.], Colormap::Viridis, 0., 1.);
+    }
+
+    #[test]
+    fn filter_keeps_point_styles_aligned() {
+        let series = three_point_series()
+            .with_color_values(&[0., 5., 10.], Colormap::Viridis, 0., 10.)
+            .filter(|p| p.x != 1);
+        assert_eq!(series.data(), &[Point::new(0, 0), Point::new(2, 2)]);
+        assert_eq!(
+            series.marker_style_at(0).color(),
+            Colormap::Viridis.color_at(0.)
+        );
+        assert_eq!(
+            series.marker_style_at(1).color(),
+            Colormap::Viridis.color_at(1.)
+        );
+    }
+
+    #[test]
+    fn step_interpolation_draws_thick_flat_segments() {
+        let style = LineStyle::Solid {
+            color: colors::WHITE,
+            thickness: 1,
+        };
+        let series = Series::new(&[Point::new(10, 10), Point::new(20, 30)])
+            .with_line_style(style)
+            .with_interpolation(LineInterpolation::StepPost);
+        let points = series
+            .get_mask()
+            .unwrap()
+            .into_iter()
+            .flat_map(|m| m.points)
+            .collect::<Vec<_>>();
+
+        // the tread runs along y = 10 and the riser along x = 20, both with thickness
+        assert!(points.contains(&Point::new(15, 11)));
+        assert!(points.contains(&Point::new(21, 25)));
+        assert!(!points.contains(&Point::new(15, 20)));
+    }
+
+    #[test]
+    fn legend_color_prefers_line_color() {
+        let series = three_point_series().with_marker_style(MarkerStyle::FilledCircle {
+            size: 1,
+            color: colors::RED,
+        });
+        assert_eq!(series.legend_color(), colors::RED);
+        let series = series.with_line_style(LineStyle::Solid {
+            color: colors::BLUE,
+            thickness: 0,
+        });
+        assert_eq!(series.legend_color(), colors::BLUE);
+    }
+
+    #[test]
+    fn interpolation_is_kept_when_cloning() {
+        let series = three_point_series().with_interpolation(LineInterpolation::CatmullRom);
+        let cloned = series.clone_with(&[Point::new(0., 0.)]);
+        assert_eq!(cloned.interpolation(), LineInterpolation::CatmullRom);
+    }
 }
@@ -0,0 +1,170 @@
+use super::{
+    canvas::TerminalCanvas, colors, graph::Graph, line::LineStyle, marker::MarkerStyle,
+    point::Point, series::Series,
+};
+use crate::common::Result;
+use rgb::RGB8;
+
+/// Tiny line chart of a sequence of values with no axes, grid lines, labels or canvas buffer,
+/// meant to be sized to a few terminal cells and printed inline with text in a status line.
+/// Values are spaced evenly along the x axis in the order given.
+#[derive(Debug, Clone)]
+pub struct Sparkline {
+    values: Vec<f64>,
+    line_style: LineStyle,
+    end_marker: Option<MarkerStyle>,
+    y_range: Option<(f64, f64)>,
+    background: RGB8,
+}
+
+impl Sparkline {
+    /// Creates a sparkline from the given values. Panics if there are no values or any value is
+    /// not finite.
+    pub fn new(values: &[f64]) -> Sparkline {
+        if values.is_empty() {
+            panic!("Sparkline must have at least one value");
+        }
+        if let Some(value) = values.iter().find(|v| !v.is_finite()) {
+            panic!("Sparkline values must be finite, got {value}");
+        }
+        Sparkline {
+            values: Vec::from(values),
+            line_style: LineStyle::default(),
+            end_marker: None,
+            y_range: None,
+            background: colors::BLACK,
+        }
+    }
+
+    pub fn with_line_style(mut self, line_style: LineStyle) -> Self {
+        self.line_style = line_style;
+        self
+    }
+
+    /// Highlights the latest value with a marker.
+    pub fn with_end_marker(mut self, marker_style: MarkerStyle) -> Self {
+        self.end_marker = Some(marker_style);
+        self
+    }
+
+    /// Fixes the vertical range (e.g. 0 to 100 for a percentage) instead of fitting it to the
+    /// values. Panics unless `min < max` and both are finite.
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Self {
+        if !(min.is_finite() && max.is_finite() && min < max) {
+            panic!("Sparkline y range must have min < max, got {min} to {max}");
+        }
+        self.y_range = Some((min, max));
+        self
+    }
+
+    pub fn with_background(mut self, background: RGB8) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Gets the graph drawn for the sparkline: a single series, plus the end marker if any.
+    pub fn graph(&self) -> Graph<f64> {
+        let points = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Point::new(i as f64, v))
+            .collect::<Vec<_>>();
+        let mut graph = Graph::new().with_series(
+            Series::new(&points)
+                .with_marker_style(MarkerStyle::FilledSquare {
+                    size: 0,
+                    color: self.line_style.color(),
+                })
+                .with_line_style(self.line_style),
+        );
+        if let Some(marker_style) = &self.end_marker {
+            let last = points[points.len() - 1];
+            graph = graph.with_series(Series::new(&[last]).with_marker_style(marker_style.clone()));
+        }
+
+        // a flat line sits in the middle of the sparkline rather than at its edge
+        let (min, max) = match self.y_range {
+            Some(range) => range,
+            None => {
+                let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = self
+                    .values
+                    .iter()
+                    .copied()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                match min < max {
+                    true => (min, max),
+                    false => (min - 1., max + 1.),
+                }
+            }
+        };
+        graph = graph.with_y_limits(min, max);
+        if points.len() == 1 {
+            graph = graph.with_x_limits(-1., 1.);
+        }
+        graph
+    }
+
+    /// Draws the sparkline on a canvas of the given size in pixels, filling the whole canvas.
+    pub fn draw(&self, width: u32, height: u32) -> Result<TerminalCanvas<f64>> {
+        TerminalCanvas::new(width, height, self.background)
+            .with_graph(self.graph())
+            .draw()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit_pixels(canvas: &TerminalCanvas<f64>, width: u32) -> Vec<(u32, u32)> {
+        canvas
+            .get_bytes()
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, rgb)| rgb.iter().any(|&c| c != 0))
+            .map(|(i, _)| (i as u32 % width, i as u32 / width))
+            .collect()
+    }
+
+    #[test]
+    fn line_spans_the_whole_canvas() {
+        let canvas = Sparkline::new(&[1., 5., 2., 8.]).draw(40, 10).unwrap();
+        let lit = lit_pixels(&canvas, 40);
+        // rows are stored top to bottom
+        assert!(lit.contains(&(0, 9)));
+        assert!(lit.contains(&(39, 0)));
+        assert!(lit.iter().all(|&(x, y)| x < 40 && y < 10));
+    }
+
+    #[test]
+    fn flat_values_are_drawn_through_the_middle() {
+        let canvas = Sparkline::new(&[3., 3., 3.]).draw(20, 9).unwrap();
+        let lit = lit_pixels(&canvas, 20);
+        assert_eq!(lit.len(), 20);
+        assert!(lit.iter().all(|&(_, y)| y == 4));
+    }
+
+    #[test]
+    fn fixed_y_range_keeps_values_in_place() {
+        let canvas = Sparkline::new(&[0., 50.])
+            .with_y_range(0., 100.)
+            .draw(11, 11)
+            .unwrap();
+        let lit = lit_pixels(&canvas, 11);
+        assert!(lit.contains(&(0, 10)));
+        assert!(lit.contains(&(10, 5)));
+        assert!(lit.iter().all(|&(_, y)| y >= 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_values_panic() {
+        Sparkline::new(&[]);
+    }
+}
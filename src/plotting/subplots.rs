@@ -0,0 +1,327 @@
+use super::{
+    canvas::{BufferType, CanvasBuffer, checked_limits},
+    common::{FloatConvertable, Graphable},
+    graph::Graph,
+    limits::Limits,
+    point::Point,
+};
+use crate::common::Result;
+
+const DEFAULT_PANEL_BUFFER: u32 = 30;
+
+/// A graph placed in a grid of subplots. Rows are counted from the top and columns from the
+/// left, both starting at zero.
+#[derive(Debug, Clone)]
+pub struct Subplot<T: Graphable + FloatConvertable> {
+    graph: Graph<T>,
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+}
+
+impl<T: Graphable + FloatConvertable> Subplot<T> {
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    pub fn row_span(&self) -> usize {
+        self.row_span
+    }
+
+    pub fn col_span(&self) -> usize {
+        self.col_span
+    }
+
+    fn covers(&self, row: usize, col: usize) -> bool {
+        (self.row..self.row + self.row_span).contains(&row)
+            && (self.col..self.col + self.col_span).contains(&col)
+    }
+}
+
+/// Grid of graphs drawn into one canvas. Each panel has its own axes, limits and legend; the x
+/// and/or y limits can optionally be shared across all panels so that they are directly
+/// comparable.
+#[derive(Debug, Clone)]
+pub struct Subplots<T: Graphable + FloatConvertable> {
+    rows: usize,
+    cols: usize,
+    panels: Vec<Subplot<T>>,
+    share_x: bool,
+    share_y: bool,
+    panel_buffer: CanvasBuffer,
+}
+
+impl<T: Graphable + FloatConvertable> Subplots<T> {
+    pub fn new(rows: usize, cols: usize) -> Subplots<T> {
+        if rows == 0 || cols == 0 {
+            panic!("Subplot grid must have at least one row and column, got {rows}x{cols}");
+        }
+        Subplots {
+            rows,
+            cols,
+            panels: Vec::new(),
+            share_x: false,
+            share_y: false,
+            panel_buffer: CanvasBuffer::new(BufferType::Uniform(DEFAULT_PANEL_BUFFER)),
+        }
+    }
+
+    /// Places a graph in a single cell of the grid.
+    pub fn with_graph(self, row: usize, col: usize, graph: Graph<T>) -> Self {
+        self.with_spanning_graph(row, col, 1, 1, graph)
+    }
+
+    /// Places a graph so that it spans several rows and/or columns, starting from the given top
+    /// left cell.
+    pub fn with_spanning_graph(
+        mut self,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+        graph: Graph<T>,
+    ) -> Self {
+        if graph.is_empty() {
+            panic!("Cannot add empty graph to subplot ({row}, {col})");
+        }
+        if row_span == 0 || col_span == 0 {
+            panic!("Subplot spans must be at least 1, got {row_span}x{col_span}");
+        }
+        if row + row_span > self.rows || col + col_span > self.cols {
+            panic!(
+                "Subplot at ({row}, {col}) spanning {row_span}x{col_span} does not fit in a {}x{} grid",
+                self.rows, self.cols
+            );
+        }
+        let overlaps = (row..row + row_span).any(|r| {
+            (col..col + col_span).any(|c| self.panels.iter().any(|panel| panel.covers(r, c)))
+        });
+        if overlaps {
+            panic!("Subplot at ({row}, {col}) overlaps an existing subplot");
+        }
+
+        self.panels.push(Subplot {
+            graph,
+            row,
+            col,
+            row_span,
+            col_span,
+        });
+        self
+    }
+
+    /// Uses the same x limits, covering the data of every panel, for all panels.
+    pub fn with_shared_x(mut self) -> Self {
+        self.share_x = true;
+        self
+    }
+
+    /// Uses the same y limits, covering the data of every panel, for all panels.
+    pub fn with_shared_y(mut self) -> Self {
+        self.share_y = true;
+        self
+    }
+
+    /// Sets the space left around each panel for its tick labels.
+    pub fn with_panel_buffer(mut self, buffer_type: BufferType) -> Self {
+        self.panel_buffer = CanvasBuffer::new(buffer_type);
+        self
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn panels(&self) -> &[Subplot<T>] {
+        &self.panels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.panels.is_empty()
+    }
+
+    /// Gets the limits covering the data of every panel.
+    fn shared_limits(&self) -> Option<Limits<T>> {
+        let limits = self
+            .panels
+            .iter()
+            .filter_map(|panel| panel.graph.limits())
+            .collect::<Vec<_>>();
+        let first = limits.first()?;
+        let pick = |a: T, b: T, smaller: bool| match (a < b) == smaller {
+            true => a,
+            false => b,
+        };
+        let (min, max) = limits
+            .iter()
+            .fold((*first.min(), *first.max()), |(min, max), l| {
+                (
+                    Point::new(pick(min.x, l.min().x, true), pick(min.y, l.min().y, true)),
+                    Point::new(pick(max.x, l.max().x, false), pick(max.y, l.max().y, false)),
+                )
+            });
+        Some(Limits::new(min, max))
+    }
+
+    /// Gets the graph of each panel, in the order they were added, with any shared limits
+    /// applied.
+    pub fn graphs(&self) -> Vec<Graph<T>> {
+        let shared = self.shared_limits();
+        self.panels
+            .iter()
+            .map(|panel| {
+                let mut graph = panel.graph.clone();
+                if let (true, Some(limits)) = (self.share_x, &shared) {
+                    graph = graph.with_x_limits(limits.min().x, limits.max().x);
+                }
+                if let (true, Some(limits)) = (self.share_y, &shared) {
+                    graph = graph.with_y_limits(limits.min().y, limits.max().y);
+                }
+                graph
+            })
+            .collect()
+    }
+
+    /// Gets the buffer kept around each panel.
+    pub(crate) fn panel_buffer(&self) -> &CanvasBuffer {
+        &self.panel_buffer
+    }
+
+    /// Gets the area of each panel, in the order they were added, after dividing the given area
+    /// into equally sized cells and removing the panel buffer.
+    pub fn panel_limits(&self, area: &Limits<u32>) -> Result<Vec<Limits<u32>>> {
+        let cell_width = (area.max().x - area.min().x + 1) as f64 / self.cols as f64;
+        let cell_height = (area.max().y - area.min().y + 1) as f64 / self.rows as f64;
+        let buffer = &self.panel_buffer;
+
+        self.panels
+            .iter()
+            .map(|panel| {
+                let left = area.min().x as f64 + panel.col as f64 * cell_width;
+                let right = left + panel.col_span as f64 * cell_width - 1.;
+                // rows are counted from the top, but y increases upwards
+                let top = area.max().y as f64 - panel.row as f64 * cell_height;
+                let bottom = top - panel.row_span as f64 * cell_height + 1.;
+
+                let min = Point::new(
+                    left.round() as u32 + buffer.left,
+                    bottom.round() as u32 + buffer.bottom,
+                );
+                let max = Point::new(
+                    (right.round() as u32).saturating_sub(buffer.right),
+                    (top.round() as u32).saturating_sub(buffer.top),
+                );
+                checked_limits(min, max)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotting::series::Series;
+
+    fn graph(points: &[(i32, i32)]) -> Graph<i32> {
+        let points = points
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect::<Vec<_>>();
+        Graph::new().with_series(Series::new(&points))
+    }
+
+    fn area() -> Limits<u32> {
+        Limits::new(Point::new(0, 0), Point::new(199, 99))
+    }
+
+    #[test]
+    #[should_panic]
+    fn panel_outside_grid_panics() {
+        Subplots::new(1, 2).with_graph(0, 2, graph(&[(0, 0)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn overlapping_panels_panic() {
+        Subplots::new(2, 2)
+            .with_spanning_graph(0, 0, 2, 1, graph(&[(0, 0)]))
+            .with_graph(1, 0, graph(&[(0, 0)]));
+    }
+
+    #[test]
+    fn panels_split_the_area_from_the_top_left() {
+        let subplots = Subplots::new(2, 2)
+            .with_panel_buffer(BufferType::None)
+            .with_graph(0, 0, graph(&[(0, 0)]))
+            .with_graph(1, 1, graph(&[(0, 0)]));
+        let limits = subplots.panel_limits(&area()).unwrap();
+        assert_eq!(
+            limits[0],
+            Limits::new(Point::new(0, 50), Point::new(99, 99))
+        );
+        assert_eq!(
+            limits[1],
+            Limits::new(Point::new(100, 0), Point::new(199, 49))
+        );
+    }
+
+    #[test]
+    fn spanning_panels_cover_several_cells() {
+        let subplots = Subplots::new(2, 2)
+            .with_panel_buffer(BufferType::Uniform(5))
+            .with_spanning_graph(0, 0, 1, 2, graph(&[(0, 0)]))
+            .with_graph(1, 0, graph(&[(0, 0)]));
+        let limits = subplots.panel_limits(&area()).unwrap();
+        assert_eq!(
+            limits[0],
+            Limits::new(Point::new(5, 55), Point::new(194, 94))
+        );
+        assert_eq!(limits[1], Limits::new(Point::new(5, 5), Point::new(94, 44)));
+    }
+
+    #[test]
+    fn grid_too_dense_for_its_buffer_errors() {
+        let mut subplots = Subplots::new(4, 4);
+        for row in 0..4 {
+            for col in 0..4 {
+                subplots = subplots.with_graph(row, col, graph(&[(0, 0)]));
+            }
+        }
+        let area = Limits::new(Point::new(0, 0), Point::new(199, 199));
+        let err = subplots.panel_limits(&area).unwrap_err();
+        assert!(err.to_string().contains("Canvas too small"));
+    }
+
+    #[test]
+    fn shared_axes_use_combined_limits() {
+        let subplots = Subplots::new(1, 2)
+            .with_graph(0, 0, graph(&[(0, 0), (10, 5)]))
+            .with_graph(0, 1, graph(&[(-5, 2), (3, 20)]))
+            .with_shared_x();
+        let graphs = subplots.graphs();
+        assert_eq!(
+            graphs[0].limits().unwrap(),
+            Limits::new(Point::new(-5, 0), Point::new(10, 5))
+        );
+        assert_eq!(
+            graphs[1].limits().unwrap(),
+            Limits::new(Point::new(-5, 2), Point::new(10, 20))
+        );
+
+        let graphs = subplots.with_shared_y().graphs();
+        assert_eq!(graphs[0].limits(), graphs[1].limits());
+    }
+}
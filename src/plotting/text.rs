@@ -1,6 +1,8 @@
+#[cfg(feature = "ttf")]
+use super::font::Font;
 use super::{
     colors,
-    common::{Drawable, IntConvertable, MaskPoints, UIntConvertable},
+    common::{AlphaMaskPoints, Drawable, IntConvertable, MaskPoints, PixelSink, UIntConvertable},
     limits::Limits,
     numbers,
     point::Point,
@@ -8,11 +10,20 @@ use super::{
 use crate::common::Result;
 use rgb::RGB8;
 
+/// Where text is placed relative to a point. The variants are also usable as functions from a
+/// point to a positioning, e.g. to choose an alignment before the point is known.
 #[derive(Debug, Clone)]
 pub enum TextPositioning {
+    /// The point is at the center of the text.
     Centered(Point<u32>),
+    /// The point is at the middle of the left edge, so the text extends to the right of it.
     LeftAligned(Point<u32>),
-    // to add...
+    /// The point is at the middle of the right edge, so the text extends to the left of it.
+    RightAligned(Point<u32>),
+    /// The point is at the middle of the bottom edge, so the text sits above it.
+    Above(Point<u32>),
+    /// The point is at the middle of the top edge, so the text hangs below it.
+    Below(Point<u32>),
 }
 
 impl TextPositioning {
@@ -20,26 +31,49 @@ impl TextPositioning {
         match self {
             Self::Centered(_) => Self::Centered(new_point),
             Self::LeftAligned(_) => Self::LeftAligned(new_point),
+            Self::RightAligned(_) => Self::RightAligned(new_point),
+            Self::Above(_) => Self::Above(new_point),
+            Self::Below(_) => Self::Below(new_point),
         }
     }
 
     pub fn point(&self) -> &Point<u32> {
         match self {
-            Self::Centered(point) => point,
-            Self::LeftAligned(point) => point,
+            Self::Centered(point)
+            | Self::LeftAligned(point)
+            | Self::RightAligned(point)
+            | Self::Above(point)
+            | Self::Below(point) => point,
         }
     }
 }
 
+// coverage at or above which a pixel is drawn when text is not blended
+const HALF_COVERAGE: u8 = 128;
+
 #[derive(Debug, Clone)]
 pub struct TextChar {
     value: char,
-    bitmap: Vec<Vec<bool>>,
+    // coverage of each pixel from 0 to 255, with rows from the bottom up
+    bitmap: Vec<Vec<u8>>,
 }
 
 impl TextChar {
     pub fn new(value: char, style: &TextStyle) -> TextChar {
-        let bitmap = numbers::get_bitmap(value, style);
+        #[cfg(feature = "ttf")]
+        if let Some(font) = &style.font {
+            let bitmap = pad(font.rasterize(value, style.scale), style.padding);
+            return TextChar { value, bitmap };
+        }
+
+        let bitmap = numbers::get_bitmap(value, style)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|on| if on { u8::MAX } else { 0 })
+                    .collect()
+            })
+            .collect();
         TextChar { value, bitmap }
     }
 
@@ -55,27 +89,83 @@ impl TextChar {
         self.value
     }
 
+    /// Rotates the character bitmap 90 degrees counter-clockwise.
+    pub fn rotated(&self) -> TextChar {
+        let height = self.height();
+        let bitmap = (0..self.width())
+            .map(|i| {
+                (0..height)
+                    .map(|j| self.bitmap[height - 1 - j][i])
+                    .collect()
+            })
+            .collect();
+        TextChar {
+            value: self.value,
+            bitmap,
+        }
+    }
+
+    /// Gets the pixels that are at least half covered by the character.
     pub fn get_mask(&self, lower_left: Point<u32>, style: TextStyle) -> Result<Vec<MaskPoints>> {
+        let masks = self.get_alpha_mask(lower_left, style)?;
+        Ok(masks.into_iter().map(opaque_points).collect())
+    }
+
+    /// Gets every pixel covered by the character, along with its coverage.
+    pub fn get_alpha_mask(
+        &self,
+        lower_left: Point<u32>,
+        style: TextStyle,
+    ) -> Result<Vec<AlphaMaskPoints>> {
         let mut points = Vec::new();
         for i in 0..self.height() {
             for j in 0..self.width() {
-                if self.bitmap[i][j] {
+                if self.bitmap[i][j] > 0 {
                     let shift = Point::new(j as i32, i as i32);
                     let point = lower_left.convert_to_i32() + shift;
-                    points.push(point.convert_to_u32());
+                    points.push((point.convert_to_u32(), self.bitmap[i][j]));
                 }
             }
         }
         let color = style.color;
-        Ok(vec![MaskPoints { points, color }])
+        Ok(vec![AlphaMaskPoints { points, color }])
+    }
+}
+
+fn opaque_points(mask: AlphaMaskPoints) -> MaskPoints {
+    let points = mask
+        .points
+        .into_iter()
+        .filter(|&(_, alpha)| alpha >= HALF_COVERAGE)
+        .map(|(point, _)| point)
+        .collect();
+    MaskPoints {
+        points,
+        color: mask.color,
     }
 }
 
+/// Adds empty pixels on every side of a character, like the padding of the bitmap font.
+#[cfg(feature = "ttf")]
+fn pad(bitmap: Vec<Vec<u8>>, padding: usize) -> Vec<Vec<u8>> {
+    let width = bitmap.first().map_or(0, |row| row.len()) + 2 * padding;
+    let empty_rows = std::iter::repeat_n(vec![0; width], padding);
+    let rows = bitmap.into_iter().map(|row| {
+        let mut padded = vec![0; padding];
+        padded.extend(row);
+        padded.resize(width, 0);
+        padded
+    });
+    empty_rows.clone().chain(rows).chain(empty_rows).collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct TextStyle {
     color: RGB8,
     scale: usize,
     padding: usize,
+    #[cfg(feature = "ttf")]
+    font: Option<Font>,
 }
 
 impl TextStyle {
@@ -88,6 +178,8 @@ impl TextStyle {
             color,
             scale,
             padding,
+            #[cfg(feature = "ttf")]
+            font: None,
         }
     }
 
@@ -96,6 +188,8 @@ impl TextStyle {
             color: colors::BLACK,
             scale: 1,
             padding: 1,
+            #[cfg(feature = "ttf")]
+            font: None,
         }
     }
 
@@ -104,6 +198,8 @@ impl TextStyle {
             color,
             scale: 1,
             padding: 1,
+            #[cfg(feature = "ttf")]
+            font: None,
         }
     }
 
@@ -118,14 +214,64 @@ impl TextStyle {
     pub fn padding(&self) -> usize {
         self.padding
     }
+
+    /// Checks whether a character can be drawn in this style: by the font if one is set, or
+    /// else by the built-in bitmap font.
+    pub fn can_draw(&self, c: char) -> bool {
+        #[cfg(feature = "ttf")]
+        if let Some(font) = &self.font {
+            return font.has_glyph(c);
+        }
+        numbers::has_bitmap(c)
+    }
+
+    /// Sizes text to match the terminal's own text, given the size of a terminal cell in pixels
+    /// (e.g. `pix_per_col` and `pix_per_row` from [`get_window_size`]). The bitmap font is scaled
+    /// to be at most as tall as a row and two columns wide, but never below a scale of 1; a
+    /// TrueType/OpenType font is sized so that a line of text is as tall as a row.
+    ///
+    /// [`get_window_size`]: crate::get_window_size
+    pub fn with_cell_size(mut self, pix_per_col: u32, pix_per_row: u32) -> Self {
+        #[cfg(feature = "ttf")]
+        if let Some(font) = self.font.take() {
+            self.font = Some(font.with_row_height(pix_per_row));
+            self.scale = 1;
+            return self;
+        }
+
+        let char_width = numbers::CHAR_WIDTH + 2 * self.padding;
+        let char_height = numbers::CHAR_HEIGHT + 2 * self.padding;
+        let scale = usize::min(
+            pix_per_row as usize / char_height,
+            2 * pix_per_col as usize / char_width,
+        );
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Draws text with a TrueType/OpenType font, anti-aliased, instead of the bitmap font. The
+    /// scale multiplies the pixel size of the font.
+    #[cfg(feature = "ttf")]
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    #[cfg(feature = "ttf")]
+    pub fn font(&self) -> Option<&Font> {
+        self.font.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Text {
     style: TextStyle,
     chars: Vec<TextChar>,
+    // size of the text before it is rotated
     width: usize,
     height: usize,
+    // counter-clockwise, in degrees from 0 up to 360
+    rotation: f64,
 }
 
 impl Text {
@@ -142,15 +288,68 @@ impl Text {
             chars,
             width,
             height,
+            rotation: 0.,
         }
     }
 
+    /// Rotates the text 90 degrees counter-clockwise, so that it reads from bottom to top.
+    pub fn rotated(self) -> Text {
+        self.with_rotation(90.)
+    }
+
+    /// Rotates the text counter-clockwise about its center by an angle in degrees, replacing any
+    /// previous rotation; e.g. 270 reads from top to bottom and 45 slants up to the right. Quarter
+    /// turns keep the bitmap font sharp, while other angles blend the edges of the characters.
+    /// Panics if the angle is not finite.
+    pub fn with_rotation(mut self, degrees: f64) -> Text {
+        if !degrees.is_finite() {
+            panic!("Text rotation must be finite, got {degrees}");
+        }
+        self.rotation = degrees.rem_euclid(360.);
+        self
+    }
+
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    pub fn is_rotated(&self) -> bool {
+        self.rotation != 0.
+    }
+
+    /// Gets the height of the box around the text, once rotated.
     pub fn height(&self) -> usize {
-        self.height
+        let (sin, cos) = self.sin_cos();
+        (self.width as f64 * sin.abs() + self.height as f64 * cos.abs()).ceil() as usize
     }
 
+    /// Gets the width of the box around the text, once rotated.
     pub fn width(&self) -> usize {
-        self.width
+        let (sin, cos) = self.sin_cos();
+        (self.width as f64 * cos.abs() + self.height as f64 * sin.abs()).ceil() as usize
+    }
+
+    fn sin_cos(&self) -> (f64, f64) {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        // quarter turns are made exact, so that pixels map onto pixels
+        match self.rotation % 90. == 0. {
+            true => (sin.round(), cos.round()),
+            false => (sin, cos),
+        }
+    }
+
+    /// Gets the coverage of each pixel of the text before it is rotated, with the characters
+    /// side by side and rows from the bottom up.
+    fn unrotated_coverage(&self) -> Vec<Vec<u8>> {
+        let mut coverage = vec![vec![0; self.width]; self.height];
+        let mut left = 0;
+        for c in &self.chars {
+            for (row, char_row) in coverage.iter_mut().zip(&c.bitmap) {
+                row[left..left + char_row.len()].copy_from_slice(char_row);
+            }
+            left += c.width();
+        }
+        coverage
     }
 
     pub fn chars(&self) -> &[TextChar] {
@@ -241,50 +440,100 @@ impl Label {
         &self.pos
     }
 
+    /// Gets the lower left corner of the text, which may lie outside the canvas.
+    fn lower_left(&self) -> Point<i32> {
+        let point = self.pos.point().convert_to_i32();
+        let (width, height) = (self.txt.width() as i32, self.txt.height() as i32);
+        let shift = match self.pos {
+            TextPositioning::Centered(_) => Point::new(-width / 2, -height / 2),
+            TextPositioning::LeftAligned(_) => Point::new(0, -height / 2),
+            TextPositioning::RightAligned(_) => Point::new(-width, -height / 2),
+            TextPositioning::Above(_) => Point::new(-width / 2, 0),
+            TextPositioning::Below(_) => Point::new(-width / 2, -height),
+        };
+        point + shift
+    }
+
     pub fn limits(&self) -> Limits<u32> {
-        match &self.pos {
-            TextPositioning::Centered(center) => {
-                let shift_floor = Point::new(
-                    (self.txt.width as f64 / 2.).floor() as u32,
-                    (self.txt.height as f64 / 2.).floor() as u32,
-                );
-                let shift_ceil = Point::new(
-                    (self.txt.width as f64 / 2.).ceil() as u32,
-                    (self.txt.height as f64 / 2.).ceil() as u32,
-                );
-                let min = Point::new(
-                    center.x.saturating_sub(shift_floor.x),
-                    center.y.saturating_sub(shift_floor.y),
-                );
-                let max = *center + shift_ceil;
-                Limits::new(min, max)
+        let lower_left = self.lower_left();
+        let size = Point::new(self.txt.width() as i32, self.txt.height() as i32);
+        Limits::new(
+            lower_left.convert_to_u32(),
+            (lower_left + size).convert_to_u32(),
+        )
+    }
+
+    /// Gets the lower left and upper right pixels of the text, which may lie outside the canvas,
+    /// or `None` for empty text.
+    pub(crate) fn extent(&self) -> Option<(Point<i32>, Point<i32>)> {
+        let (width, height) = (self.txt.width() as i32, self.txt.height() as i32);
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let lower_left = self.lower_left();
+        Some((lower_left, lower_left + Point::new(width - 1, height - 1)))
+    }
+}
+
+impl Label {
+    /// Gets every pixel covered by the text along with its coverage, so that anti-aliased edges
+    /// can be blended with the canvas.
+    pub fn get_alpha_mask(&self) -> Result<Vec<AlphaMaskPoints>> {
+        let lower_left = self.lower_left();
+        let coverage = self.txt.unrotated_coverage();
+        let (width, height) = (self.txt.width(), self.txt.height());
+        let (sin, cos) = self.txt.sin_cos();
+        let source_center = (self.txt.width as f64 / 2., self.txt.height as f64 / 2.);
+
+        let mut points = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                // turn the center of each pixel back about the center of the text to find where
+                // in the unrotated text it comes from
+                let dx = x as f64 + 0.5 - width as f64 / 2.;
+                let dy = y as f64 + 0.5 - height as f64 / 2.;
+                let source_x = dx * cos + dy * sin + source_center.0 - 0.5;
+                let source_y = -dx * sin + dy * cos + source_center.1 - 0.5;
+                let alpha = sample(&coverage, source_x, source_y);
+
+                let point = lower_left + Point::new(x as i32, y as i32);
+                if alpha > 0 && point.x >= 0 && point.y >= 0 {
+                    points.push((point.convert_to_u32(), alpha));
+                }
             }
-            _ => panic!("Not implemented"),
         }
+        let color = self.txt.style.color;
+        Ok(vec![AlphaMaskPoints { points, color }])
     }
 }
 
+/// Gets the coverage at a point between pixels, interpolated from the four pixels around it.
+/// Anything outside the pixels is uncovered.
+fn sample(coverage: &[Vec<u8>], x: f64, y: f64) -> u8 {
+    let (left, bottom) = (x.floor(), y.floor());
+    let (fx, fy) = (x - left, y - bottom);
+    let at = |x: f64, y: f64| {
+        if x < 0. || y < 0. {
+            return 0.;
+        }
+        let value = coverage.get(y as usize).and_then(|row| row.get(x as usize));
+        value.copied().unwrap_or(0) as f64
+    };
+    let value = at(left, bottom) * (1. - fx) * (1. - fy)
+        + at(left + 1., bottom) * fx * (1. - fy)
+        + at(left, bottom + 1.) * (1. - fx) * fy
+        + at(left + 1., bottom + 1.) * fx * fy;
+    value.round() as u8
+}
+
 impl Drawable for Label {
-    fn get_mask(&self) -> Result<Vec<MaskPoints>> {
-        let mask_points = match &self.pos {
-            TextPositioning::Centered(center) => {
-                let height_shift: i32 = (self.txt.height / 2).try_into().unwrap();
-                let width_shift: i32 = (self.txt.width / 2).try_into().unwrap();
-                let mut masks = Vec::new();
-                self.txt.chars.iter().fold(-width_shift, |acc, c| {
-                    let char_lower_left = center.convert_to_i32() + Point::new(acc, -height_shift);
-                    masks.extend(
-                        c.get_mask(char_lower_left.convert_to_u32(), self.txt.style.clone())
-                            .unwrap(),
-                    );
-                    let char_width: i32 = c.width().try_into().unwrap();
-                    acc + char_width
-                });
-                masks
-            }
-            _ => panic!("Not implemented"),
-        };
-        Ok(mask_points)
+    /// Draws the pixels that are at least half covered by the text.
+    fn draw<S: PixelSink + ?Sized>(&self, sink: &mut S) -> Result<()> {
+        for mask in self.get_alpha_mask()? {
+            let mask = opaque_points(mask);
+            sink.set_pixels(&mask.points, &mask.color);
+        }
+        Ok(())
     }
 }
 
@@ -293,23 +542,62 @@ mod test {
     use super::*;
 
     #[test]
-    #[should_panic(expected = "Not implemented")]
-    fn label_limits_left_aligned_panics() {
+    fn label_limits_left_aligned_extend_right_of_point() {
         let style = TextStyle::default();
         let txt = Text::new("0", style);
+        let (width, height) = (txt.width() as u32, txt.height() as u32);
         let pos = TextPositioning::LeftAligned(Point::new(50, 50));
         let label = Label::new(txt, pos);
-        let _ = label.limits();
+
+        let limits = label.limits();
+        assert_eq!(limits.min().x, 50);
+        assert_eq!(limits.max().x, 50 + width);
+        assert_eq!(limits.min().y, 50 - height / 2);
     }
 
     #[test]
-    #[should_panic(expected = "Not implemented")]
-    fn label_get_mask_left_aligned_panics() {
-        let style = TextStyle::default();
-        let txt = Text::new("0", style);
-        let pos = TextPositioning::LeftAligned(Point::new(50, 50));
-        let label = Label::new(txt, pos);
-        let _ = label.get_mask();
+    fn label_mask_stays_within_limits_for_every_alignment() {
+        let alignments: [fn(Point<u32>) -> TextPositioning; 5] = [
+            TextPositioning::Centered,
+            TextPositioning::LeftAligned,
+            TextPositioning::RightAligned,
+            TextPositioning::Above,
+            TextPositioning::Below,
+        ];
+        for alignment in alignments {
+            let label = Label::new(
+                Text::new("a1", TextStyle::default()),
+                alignment(Point::new(50, 50)),
+            );
+            let limits = label.limits();
+            let mask = label.get_mask().unwrap();
+            assert!(!mask[0].points.is_empty());
+            for p in mask.iter().flat_map(|m| &m.points) {
+                assert!(limits.contains(p), "{p:?} outside {limits:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn label_alignments_place_text_around_point() {
+        let label = |pos| Label::new(Text::new("ab", TextStyle::default()), pos);
+        let point = Point::new(50, 50);
+        assert_eq!(
+            label(TextPositioning::RightAligned(point)).limits().max().x,
+            50
+        );
+        assert_eq!(label(TextPositioning::Above(point)).limits().min().y, 50);
+        assert_eq!(label(TextPositioning::Below(point)).limits().max().y, 50);
+    }
+
+    #[test]
+    fn cell_size_scales_bitmap_text_to_row_height() {
+        let style = |col, row| TextStyle::default().with_cell_size(col, row).scale();
+        // the bitmap font is 13 pixels tall and 12 wide with padding
+        assert_eq!(style(8, 16), 1);
+        assert_eq!(style(20, 40), 3);
+        assert_eq!(style(6, 40), 1);
+        assert_eq!(style(2, 4), 1);
     }
 
     #[test]
@@ -340,6 +628,91 @@ mod test {
         assert!(!mask[0].points.is_empty(), "Label mask should contain drawn points");
     }
 
+    #[test]
+    fn rotated_text_swaps_dimensions() {
+        let txt = Text::new("ab", TextStyle::default());
+        let rotated = txt.clone().rotated();
+        assert!(rotated.is_rotated());
+        assert_eq!(rotated.width(), txt.height());
+        assert_eq!(rotated.height(), txt.width());
+        assert_eq!(rotated.clone().rotated().width(), rotated.width());
+    }
+
+    #[test]
+    fn rotated_char_maps_bottom_row_to_right_column() {
+        let c = TextChar::new('_', &TextStyle::new(colors::WHITE, 1, 0));
+        let rotated = c.rotated();
+        // the underscore is a single bottom row, so after rotating it becomes the right column
+        assert!(rotated.bitmap.iter().all(|row| *row.last().unwrap() > 0));
+        assert!(rotated.bitmap.iter().all(|row| row[0] == 0));
+    }
+
+    #[test]
+    fn rotated_label_mask_stays_within_limits() {
+        let txt = Text::new("Mon", TextStyle::default()).rotated();
+        let label = Label::new(txt, TextPositioning::Centered(Point::new(50, 50)));
+        let limits = label.limits();
+        let mask = label.get_mask().unwrap();
+        for p in mask.iter().flat_map(|m| &m.points) {
+            assert!(limits.contains(p), "{p:?} outside {limits:?}");
+        }
+    }
+
+    #[test]
+    fn quarter_turns_map_pixels_exactly() {
+        let style = TextStyle::new(colors::WHITE, 1, 0);
+        let points = |degrees| {
+            let txt = Text::new("_", style.clone()).with_rotation(degrees);
+            let label = Label::new(txt, TextPositioning::LeftAligned(Point::new(50, 50)));
+            let limits = label.limits();
+            let mask = label.get_alpha_mask().unwrap();
+            assert!(mask[0].points.iter().all(|&(_, alpha)| alpha == u8::MAX));
+            (limits, mask[0].points.len())
+        };
+        let (flat, count) = points(0.);
+        for degrees in [90., 180., 270.] {
+            assert_eq!(points(degrees).1, count);
+        }
+
+        // the underscore is at the bottom, so it is on the right after a quarter turn
+        // counter-clockwise and on the left after three
+        let edge_x = |degrees| {
+            let txt = Text::new("_", style.clone()).with_rotation(degrees);
+            let label = Label::new(txt, TextPositioning::LeftAligned(Point::new(50, 50)));
+            label.get_mask().unwrap()[0].points[0].x
+        };
+        assert_eq!(edge_x(90.), 50 + flat.max().y - flat.min().y - 1);
+        assert_eq!(edge_x(270.), 50);
+    }
+
+    #[test]
+    fn arbitrary_rotation_bounds_the_turned_text() {
+        let txt = Text::new("label", TextStyle::default());
+        let (width, height) = (txt.width() as f64, txt.height() as f64);
+        let slanted = txt.with_rotation(45.);
+        let diagonal = (width + height) * std::f64::consts::FRAC_1_SQRT_2;
+        assert_eq!(slanted.width(), diagonal.ceil() as usize);
+        assert_eq!(slanted.height(), diagonal.ceil() as usize);
+
+        let label = Label::new(slanted, TextPositioning::Centered(Point::new(100, 100)));
+        let limits = label.limits();
+        let mask = label.get_alpha_mask().unwrap();
+        assert!(!mask[0].points.is_empty());
+        for (p, _) in &mask[0].points {
+            assert!(limits.contains(p), "{p:?} outside {limits:?}");
+        }
+        // the corners of the box are left empty by a diagonal line of text
+        assert!(!mask[0].points.iter().any(|(p, _)| p == limits.min()));
+    }
+
+    #[test]
+    fn rotation_is_normalized() {
+        let txt = Text::new("a", TextStyle::default());
+        assert_eq!(txt.clone().with_rotation(450.).rotation(), 90.);
+        assert_eq!(txt.clone().with_rotation(-90.).rotation(), 270.);
+        assert!(!txt.with_rotation(360.).is_rotated());
+    }
+
     #[test]
     fn num_to_str_within_range_gt_zero() {
         let number: f64 = 25.;
@@ -0,0 +1,455 @@
+use super::colors;
+use crate::common::Result;
+use rgb::RGB8;
+use std::path::Path;
+
+/// Colors for every part of a plot: the canvas background, the axes, grid lines and text, the
+/// palette that series are colored from in turn, and a foreground color for marks that do not
+/// belong to a series, such as reference lines.
+///
+/// Themes can be loaded from INI or TOML style files of `key = value` lines, e.g.
+///
+/// ```text
+/// # based on the light theme, with a warmer background
+/// base = "light"
+/// background = "#fdf6e3"
+/// palette = ["navy", "crimson", "#2aa198"]
+/// ```
+///
+/// The keys are `base` (a built-in theme to start from, `dark` by default), `background`,
+/// `foreground`, `axes`, `grid`, `text` and `palette`. Colors are anything accepted by
+/// [`colors::parse`], e.g. names, hex codes or `rgb(r, g, b)`; the palette is a list of colors
+/// separated by commas, optionally in square brackets. Blank lines, comments starting with `#` or `;` and
+/// section headers like `[theme]` are ignored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Theme {
+    background: RGB8,
+    foreground: RGB8,
+    axes: RGB8,
+    grid: RGB8,
+    text: RGB8,
+    palette: Vec<RGB8>,
+}
+
+const THEME_NAMES: &[&str] = &["dark", "light", "solarized", "high-contrast", "colorblind"];
+
+// a background at least this bright calls for the light theme
+const LIGHT_LUMINANCE: f64 = 0.5;
+// weight of the foreground color in grid lines that blend with a terminal's colors
+const TERMINAL_GRID_WEIGHT: f64 = 0.3;
+
+impl Theme {
+    /// White text and axes on black, with saturated series colors.
+    pub fn dark() -> Theme {
+        Theme {
+            background: colors::BLACK,
+            foreground: colors::YELLOW,
+            axes: colors::WHITE,
+            grid: colors::GRAY,
+            text: colors::WHITE,
+            palette: vec![
+                colors::BLUE,
+                colors::RED,
+                colors::LIME,
+                colors::ORANGE,
+                colors::CYAN,
+                colors::MAGENTA,
+                colors::GOLD,
+                colors::MEDIUM_PURPLE,
+                colors::SALMON,
+                colors::LIGHT_GRAY,
+            ],
+        }
+    }
+
+    /// Black text and axes on white, with series colors dark enough to stand out on white.
+    pub fn light() -> Theme {
+        Theme {
+            background: colors::WHITE,
+            foreground: colors::DARK_ORANGE,
+            axes: colors::BLACK,
+            grid: colors::GAINSBORO,
+            text: colors::BLACK,
+            palette: vec![
+                colors::BLUE,
+                colors::CRIMSON,
+                colors::GREEN,
+                colors::DARK_ORANGE,
+                colors::DARK_CYAN,
+                colors::DARK_MAGENTA,
+                colors::DARK_GOLDEN_ROD,
+                colors::BLUE_VIOLET,
+                colors::BROWN,
+                colors::DIM_GRAY,
+            ],
+        }
+    }
+
+    /// The dark variant of Ethan Schoonover's Solarized palette.
+    pub fn solarized() -> Theme {
+        Theme {
+            background: RGB8::new(0, 43, 54),
+            foreground: RGB8::new(181, 137, 0),
+            axes: RGB8::new(147, 161, 161),
+            grid: RGB8::new(88, 110, 117),
+            text: RGB8::new(131, 148, 150),
+            palette: vec![
+                RGB8::new(38, 139, 210),
+                RGB8::new(220, 50, 47),
+                RGB8::new(133, 153, 0),
+                RGB8::new(203, 75, 22),
+                RGB8::new(42, 161, 152),
+                RGB8::new(211, 54, 130),
+                RGB8::new(181, 137, 0),
+                RGB8::new(108, 113, 196),
+            ],
+        }
+    }
+
+    /// Pure white on black with the brightest series colors, for low vision or glare.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: colors::BLACK,
+            foreground: colors::WHITE,
+            axes: colors::WHITE,
+            grid: colors::DIM_GRAY,
+            text: colors::WHITE,
+            palette: vec![
+                colors::YELLOW,
+                colors::CYAN,
+                colors::MAGENTA,
+                colors::LIME,
+                colors::ORANGE,
+                colors::DEEP_SKY_BLUE,
+                colors::RED,
+                colors::WHITE,
+            ],
+        }
+    }
+
+    /// The Okabe-Ito palette on black, whose colors stay distinct with the common forms of color
+    /// blindness.
+    pub fn colorblind() -> Theme {
+        Theme {
+            background: colors::BLACK,
+            foreground: colors::LIGHT_GRAY,
+            axes: colors::WHITE,
+            grid: colors::GRAY,
+            text: colors::WHITE,
+            palette: vec![
+                RGB8::new(230, 159, 0),
+                RGB8::new(86, 180, 233),
+                RGB8::new(0, 158, 115),
+                RGB8::new(240, 228, 66),
+                RGB8::new(0, 114, 178),
+                RGB8::new(213, 94, 0),
+                RGB8::new(204, 121, 167),
+            ],
+        }
+    }
+
+    /// Case-insensitive built-in theme lookup, e.g. "dark", "Light" or "high_contrast".
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "solarized" => Some(Theme::solarized()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "colorblind" | "colorblind-safe" => Some(Theme::colorblind()),
+            _ => None,
+        }
+    }
+
+    /// Returns the names of the built-in themes.
+    pub fn all_names() -> &'static [&'static str] {
+        THEME_NAMES
+    }
+
+    /// Gets the light theme for a light background and the dark theme otherwise, e.g. to match
+    /// the terminal's background.
+    pub fn for_background(background: RGB8) -> Theme {
+        let luminance = (0.2126 * background.r as f64
+            + 0.7152 * background.g as f64
+            + 0.0722 * background.b as f64)
+            / 255.;
+        match luminance >= LIGHT_LUMINANCE {
+            true => Theme::light(),
+            false => Theme::dark(),
+        }
+    }
+
+    /// Gets a theme that blends with a terminal's color scheme: the terminal's background, its
+    /// foreground for text and axes, faint grid lines between the two, and the light or dark
+    /// theme's colors for everything else.
+    pub fn for_terminal(background: RGB8, foreground: RGB8) -> Theme {
+        let mix = |bg: u8, fg: u8| {
+            ((bg as f64 * (1. - TERMINAL_GRID_WEIGHT)) + fg as f64 * TERMINAL_GRID_WEIGHT).round()
+                as u8
+        };
+        let grid = RGB8::new(
+            mix(background.r, foreground.r),
+            mix(background.g, foreground.g),
+            mix(background.b, foreground.b),
+        );
+        Theme::for_background(background)
+            .with_background(background)
+            .with_axes(foreground)
+            .with_grid(grid)
+            .with_text(foreground)
+    }
+
+    /// Loads a theme from a file; see [`Theme`] for the format.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Theme> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read theme '{}': {e}", path.display()))?;
+        Theme::parse(&contents)
+    }
+
+    /// Parses a theme from the contents of a theme file; see [`Theme`] for the format.
+    pub fn parse(contents: &str) -> Result<Theme> {
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with(';')
+                || (line.starts_with('[') && line.ends_with(']'))
+            {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                format!(
+                    "Theme line {}: expected 'key = value', got '{line}'",
+                    index + 1
+                )
+            })?;
+            entries.push((index + 1, key.trim().to_ascii_lowercase(), value.trim()));
+        }
+
+        // the base theme applies first, wherever it is in the file
+        let mut theme = Theme::dark();
+        for (line, _, value) in entries.iter().filter(|(_, key, _)| key == "base") {
+            let name = unquote(value);
+            theme = Theme::from_name(name).ok_or_else(|| {
+                format!(
+                    "Theme line {line}: unknown base theme '{name}'. Valid themes: {}",
+                    THEME_NAMES.join(", ")
+                )
+            })?;
+        }
+
+        for (line, key, value) in entries {
+            let with_line = |e: String| format!("Theme line {line}: {e}");
+            match key.as_str() {
+                "base" => (),
+                "background" => theme.background = parse_color(value).map_err(with_line)?,
+                "foreground" => theme.foreground = parse_color(value).map_err(with_line)?,
+                "axes" => theme.axes = parse_color(value).map_err(with_line)?,
+                "grid" => theme.grid = parse_color(value).map_err(with_line)?,
+                "text" => theme.text = parse_color(value).map_err(with_line)?,
+                "palette" => {
+                    let list = value.trim_start_matches('[').trim_end_matches(']');
+                    theme.palette = split_list(list)
+                        .into_iter()
+                        .filter(|color| !color.is_empty())
+                        .map(parse_color)
+                        .collect::<std::result::Result<_, _>>()
+                        .map_err(with_line)?;
+                    if theme.palette.is_empty() {
+                        return Err(with_line("palette must have at least one color".into()).into());
+                    }
+                }
+                _ => {
+                    return Err(with_line(format!(
+                        "unknown key '{key}'. Valid keys: base, background, foreground, axes, \
+                         grid, text, palette"
+                    ))
+                    .into());
+                }
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn with_background(mut self, color: RGB8) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn with_foreground(mut self, color: RGB8) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    pub fn with_axes(mut self, color: RGB8) -> Self {
+        self.axes = color;
+        self
+    }
+
+    pub fn with_grid(mut self, color: RGB8) -> Self {
+        self.grid = color;
+        self
+    }
+
+    pub fn with_text(mut self, color: RGB8) -> Self {
+        self.text = color;
+        self
+    }
+
+    /// Sets the colors that series are given in turn. Panics if there are no colors.
+    pub fn with_palette(mut self, palette: &[RGB8]) -> Self {
+        if palette.is_empty() {
+            panic!("Theme palette must have at least one color");
+        }
+        self.palette = palette.to_vec();
+        self
+    }
+
+    pub fn background(&self) -> RGB8 {
+        self.background
+    }
+
+    pub fn foreground(&self) -> RGB8 {
+        self.foreground
+    }
+
+    pub fn axes(&self) -> RGB8 {
+        self.axes
+    }
+
+    pub fn grid(&self) -> RGB8 {
+        self.grid
+    }
+
+    pub fn text(&self) -> RGB8 {
+        self.text
+    }
+
+    pub fn palette(&self) -> &[RGB8] {
+        &self.palette
+    }
+
+    /// Gets the color of the series with the given index, cycling through the palette.
+    pub fn series_color(&self, index: usize) -> RGB8 {
+        self.palette[index % self.palette.len()]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
+
+fn parse_color(value: &str) -> std::result::Result<RGB8, String> {
+    colors::parse(unquote(value)).map_err(|e| e.to_string())
+}
+
+/// Splits a list on the commas that are not inside parentheses, e.g. of `rgb(r, g, b)` colors.
+fn split_list(list: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    items.push(list[start..].trim());
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_name_is_a_built_in_theme() {
+        for name in Theme::all_names() {
+            assert!(Theme::from_name(name).is_some(), "{name}");
+        }
+        assert_eq!(
+            Theme::from_name("High_Contrast"),
+            Some(Theme::high_contrast())
+        );
+        assert_eq!(Theme::from_name("neon"), None);
+    }
+
+    #[test]
+    fn background_brightness_picks_light_or_dark() {
+        assert_eq!(Theme::for_background(colors::WHITE), Theme::light());
+        assert_eq!(
+            Theme::for_background(RGB8::new(253, 246, 227)),
+            Theme::light()
+        );
+        assert_eq!(Theme::for_background(RGB8::new(40, 42, 54)), Theme::dark());
+    }
+
+    #[test]
+    fn terminal_theme_uses_terminal_colors() {
+        let (background, foreground) = (RGB8::new(40, 42, 54), RGB8::new(248, 248, 242));
+        let theme = Theme::for_terminal(background, foreground);
+        assert_eq!(theme.background(), background);
+        assert_eq!((theme.text(), theme.axes()), (foreground, foreground));
+        assert_eq!(theme.grid(), RGB8::new(102, 104, 110));
+        assert_eq!(theme.palette(), Theme::dark().palette());
+    }
+
+    #[test]
+    fn parse_overrides_base_theme() {
+        let theme = Theme::parse(
+            "# warm light theme\n\
+             [theme]\n\
+             background = \"#FDF6E3\"\n\
+             palette = [\"navy\", 'crimson', #2aa198, rgb(1, 2, 3)]\n\
+             base = light\n",
+        )
+        .unwrap();
+        assert_eq!(theme.background(), RGB8::new(253, 246, 227));
+        assert_eq!(
+            theme.palette(),
+            &[
+                colors::NAVY,
+                colors::CRIMSON,
+                RGB8::new(42, 161, 152),
+                RGB8::new(1, 2, 3)
+            ]
+        );
+        assert_eq!(theme.text(), Theme::light().text());
+    }
+
+    #[test]
+    fn parse_defaults_to_dark_theme() {
+        assert_eq!(Theme::parse("").unwrap(), Theme::dark());
+        let theme = Theme::parse("grid = dim_gray").unwrap();
+        assert_eq!(theme, Theme::dark().with_grid(colors::DIM_GRAY));
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let err = |contents: &str| Theme::parse(contents).unwrap_err().to_string();
+        assert!(err("text = white\nborder = red").contains("line 2"));
+        assert!(err("text = #12345").contains("#rrggbb"));
+        assert!(err("text = blurple").contains("Unknown color"));
+        assert!(err("base = neon").contains("unknown base theme"));
+        assert!(err("palette = []").contains("at least one color"));
+        assert!(err("text white").contains("key = value"));
+    }
+
+    #[test]
+    fn series_colors_cycle_through_palette() {
+        let theme = Theme::dark().with_palette(&[colors::RED, colors::BLUE]);
+        assert_eq!(theme.series_color(0), colors::RED);
+        assert_eq!(theme.series_color(3), colors::BLUE);
+    }
+}
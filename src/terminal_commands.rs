@@ -1,4 +1,5 @@
 pub mod csi_cmds;
 pub mod images;
 pub mod kitty_cmds;
+pub mod osc_cmds;
 pub mod responses;
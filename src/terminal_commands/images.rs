@@ -61,6 +61,7 @@ impl Image {
                 (width, height)
             }
             PixelFormat::Rgb { width, height } => (width, height),
+            PixelFormat::RgbBounded { width, height, .. } => (width, height),
             PixelFormat::Rgba { width, height } => (width, height),
         };
         Ok(Image {
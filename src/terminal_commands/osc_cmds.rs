@@ -0,0 +1,118 @@
+use super::responses::TermCommand;
+use crate::common::Result;
+use rgb::RGB8;
+
+const CMD_START: &[u8] = b"\x1b]";
+// terminals answer a query with the same terminator it was sent with; BEL is the most widely
+// supported
+const CMD_END: &[u8] = b"\x07";
+
+/// Operating System Command, e.g. to query the terminal's colors.
+pub struct OscCommand {
+    cmd: Vec<u8>,
+    resp_start: Vec<u8>,
+}
+
+impl OscCommand {
+    /// Creates a query for the value of the given parameters, e.g. "11" for the background
+    /// color. The response repeats the parameters before the value.
+    pub fn query(params: &str) -> OscCommand {
+        let mut resp_start = Vec::from(CMD_START);
+        resp_start.extend_from_slice(params.as_bytes());
+        resp_start.push(b';');
+
+        let mut cmd = resp_start.clone();
+        cmd.push(b'?');
+        cmd.extend_from_slice(CMD_END);
+
+        OscCommand { cmd, resp_start }
+    }
+}
+
+impl TermCommand for OscCommand {
+    fn get_request(&self) -> &[u8] {
+        &self.cmd
+    }
+
+    fn get_response_start(&self) -> &[u8] {
+        &self.resp_start
+    }
+
+    fn get_response_end(&self) -> &[u8] {
+        CMD_END
+    }
+}
+
+/// Query the terminal's default foreground (text) color using OSC 10.
+pub fn get_foreground_color() -> Result<RGB8> {
+    parse_color_response(&OscCommand::query("10").execute_with_response()?)
+}
+
+/// Query the terminal's background color using OSC 11.
+pub fn get_background_color() -> Result<RGB8> {
+    parse_color_response(&OscCommand::query("11").execute_with_response()?)
+}
+
+/// Query a color of the terminal's 256-color palette using OSC 4, e.g. 1 for the ANSI red.
+pub fn get_palette_color(index: u8) -> Result<RGB8> {
+    let params = format!("4;{index}");
+    parse_color_response(&OscCommand::query(&params).execute_with_response()?)
+}
+
+/// Parses a color reported by the terminal in the X11 form "rgb:rrrr/gggg/bbbb", where each
+/// channel has one to four hex digits.
+fn parse_color_response(resp: &str) -> Result<RGB8> {
+    let invalid = || format!("Unexpected terminal color response '{resp}'");
+    let channels = resp
+        .strip_prefix("rgb:")
+        .ok_or_else(invalid)?
+        .split('/')
+        .map(|channel| {
+            let value = u32::from_str_radix(channel, 16).ok()?;
+            let max = match channel.len() {
+                1..=4 => (1u32 << (4 * channel.len())) - 1,
+                _ => return None,
+            };
+            Some((value * 255 + max / 2) / max)
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(invalid)?;
+
+    match channels[..] {
+        [r, g, b] => Ok(RGB8::new(r as u8, g as u8, b as u8)),
+        _ => Err(invalid().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_asks_for_value_and_expects_it_echoed() {
+        let cmd = OscCommand::query("11");
+        assert_eq!(cmd.get_request(), b"\x1b]11;?\x07");
+        assert_eq!(cmd.get_response_start(), b"\x1b]11;");
+
+        // palette responses repeat the color index
+        let cmd = OscCommand::query("4;1");
+        assert_eq!(cmd.get_request(), b"\x1b]4;1;?\x07");
+        assert_eq!(cmd.get_response_start(), b"\x1b]4;1;");
+    }
+
+    #[test]
+    fn color_responses_scale_to_eight_bits() {
+        let color = |resp| parse_color_response(resp).unwrap();
+        assert_eq!(color("rgb:ffff/8080/0000"), RGB8::new(255, 128, 0));
+        assert_eq!(color("rgb:fd/f6/e3"), RGB8::new(253, 246, 227));
+        assert_eq!(color("rgb:f/8/0"), RGB8::new(255, 136, 0));
+    }
+
+    #[test]
+    fn malformed_color_responses_error() {
+        assert!(parse_color_response("rgb:ffff/8080").is_err());
+        assert!(parse_color_response("ffff/8080/0000").is_err());
+        assert!(parse_color_response("rgb:fffff/0/0").is_err());
+        assert!(parse_color_response("rgb:zz/00/00").is_err());
+    }
+}
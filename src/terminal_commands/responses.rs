@@ -3,10 +3,13 @@ use crossterm::terminal;
 use std::{
     error::Error,
     fmt,
-    io::{self, Read, Write},
-    time::Instant,
+    io::{self, Write},
+    time::{Duration, Instant},
 };
 
+// how long to wait for a response before deciding the terminal does not support a query
+const RESPONSE_TIMEOUT: Duration = Duration::from_millis(1000);
+
 #[derive(Debug)]
 pub struct TerminalCommandError {}
 
@@ -41,30 +44,27 @@ pub trait TermCommand {
         let resp_end = self.get_response_end();
 
         let mut buf = Vec::<u8>::new();
-        let mut stdin = std::io::stdin().lock();
-        let mut byte_buf = [0u8; 1];
         let mut resp_recvd = false;
 
         self.execute()?;
         terminal::enable_raw_mode()?;
 
-        let watch = Instant::now();
-        while watch.elapsed().as_millis() < 1000 {
-            match stdin.read_exact(&mut byte_buf) {
-                Ok(_) => {
-                    buf.push(byte_buf[0]);
-                    if buf.len() > resp_start.len() && buf.ends_with(resp_end) {
-                        if buf.starts_with(resp_start) {
-                            resp_recvd = true;
-                            break;
-                        }
-
-                        // if buffer ends with the correct bytes but does not start with the correct
-                        // bytes, then it is not what we are looking for; clear and start again
-                        buf.clear();
-                    }
+        let deadline = Instant::now() + RESPONSE_TIMEOUT;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            // nothing read means the wait timed out (or stdin closed), so stop waiting
+            let Ok(Some(byte)) = read_stdin_byte_with_timeout(remaining) else {
+                break;
+            };
+            buf.push(byte);
+            if buf.len() > resp_start.len() && buf.ends_with(resp_end) {
+                if buf.starts_with(resp_start) {
+                    resp_recvd = true;
+                    break;
                 }
-                _ => (),
+
+                // if buffer ends with the correct bytes but does not start with the correct
+                // bytes, then it is not what we are looking for; clear and start again
+                buf.clear();
             }
         }
 
@@ -80,3 +80,42 @@ pub trait TermCommand {
         }
     }
 }
+
+/// Reads a single byte from stdin, waiting at most `timeout` for it to arrive, so that a
+/// terminal that ignores a query cannot block the read until the user presses a key. Reading one
+/// byte at a time leaves anything after the response, such as keystrokes or the reply to the
+/// next query, on stdin. Returns `None` if nothing arrived in time.
+#[cfg(unix)]
+fn read_stdin_byte_with_timeout(timeout: Duration) -> io::Result<Option<u8>> {
+    let mut poll_fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let millis = timeout.as_millis().clamp(1, i32::MAX as u128) as i32;
+    let mut byte = 0u8;
+    // SAFETY: the pollfd and the byte outlive the calls, and the read is bounded to one byte
+    unsafe {
+        match libc::poll(&mut poll_fd, 1, millis) {
+            ready if ready < 0 => Err(io::Error::last_os_error()),
+            0 => Ok(None),
+            _ => match libc::read(libc::STDIN_FILENO, (&raw mut byte).cast(), 1) {
+                count if count < 0 => Err(io::Error::last_os_error()),
+                0 => Ok(None),
+                _ => Ok(Some(byte)),
+            },
+        }
+    }
+}
+
+/// Reads a single byte from stdin. Without a way to poll stdin, this blocks until the terminal
+/// responds.
+#[cfg(not(unix))]
+fn read_stdin_byte_with_timeout(_timeout: Duration) -> io::Result<Option<u8>> {
+    use std::io::Read;
+    let mut byte = [0u8; 1];
+    match io::stdin().read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}